@@ -189,6 +189,9 @@ struct LastfmSection {
     /// Used to re-login automatically when the saved lastfm-edit session has
     /// expired (last.fm sessions last about a year).
     password: Option<String>,
+    /// Share the lastfm-edit rate-limit budget with other processes using the same
+    /// account (the lastfm-edit CLI, scrobble-store sync --via scrape).
+    shared_rate_limit: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -406,6 +409,17 @@ async fn plan(
 // =====================================================================================
 
 async fn build_edit_client(ctx: &Context) -> Result<lastfm_edit::LastFmEditClientImpl> {
+    let client = login_edit_client(ctx).await?;
+    if !ctx.config.lastfm.shared_rate_limit {
+        return Ok(client);
+    }
+    let username = &ctx.username;
+    let limiter = lastfm_edit::SharedRateLimiter::for_lastfm_edit_user(username)
+        .map_err(|e| format!("shared rate limit for {username}: {e}"))?;
+    Ok(client.with_shared_rate_limiter(Arc::new(limiter)))
+}
+
+async fn login_edit_client(ctx: &Context) -> Result<lastfm_edit::LastFmEditClientImpl> {
     let username = &ctx.username;
     match lastfm_edit::SessionPersistence::load_session(username) {
         Ok(session) => {
@@ -68,6 +68,7 @@ impl Subject {
             timestamp: newest_uts,
             album: self.album.clone(),
             album_artist: self.album_artist.clone(),
+            ..Default::default()
         }
     }
 }
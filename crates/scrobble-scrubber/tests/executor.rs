@@ -87,6 +87,8 @@ fn success(edit: &ExactScrobbleEdit) -> EditResponse {
         message: None,
         album_info: None,
         exact_scrobble_edit: edit.clone(),
+        verification: None,
+        failure_kind: None,
     }])
 }
 
@@ -96,6 +98,8 @@ fn failure(edit: &ExactScrobbleEdit) -> EditResponse {
         message: Some("rejected".into()),
         album_info: None,
         exact_scrobble_edit: edit.clone(),
+        verification: None,
+        failure_kind: None,
     }])
 }
 
@@ -77,6 +77,8 @@ fn success_response(edit: &lastfm_edit::ExactScrobbleEdit) -> EditResponse {
         message: None,
         album_info: None,
         exact_scrobble_edit: edit.clone(),
+        verification: None,
+        failure_kind: None,
     }])
 }
 
@@ -51,6 +51,7 @@ async fn check_mb_confirmation_rule(
             album_artist: None,
             playcount: 1,
             timestamp: Some(1_600_000_000 + idx as u64 * 100),
+            ..Default::default()
         })
         .collect();
 
@@ -178,6 +179,7 @@ async fn greatest_hits_to_original() {
         album_artist: Some("Queen".to_string()),
         timestamp: Some(1_600_000_000),
         playcount: 1,
+        ..Default::default()
     };
 
     let results = provider
@@ -254,6 +256,7 @@ async fn empty_track_batches_short_circuit_without_network() {
         album_artist: None,
         timestamp: None,
         playcount: 1,
+        ..Default::default()
     };
     // Disabled provider must not touch the network even with tracks present.
     assert!(comp
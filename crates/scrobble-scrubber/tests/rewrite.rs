@@ -32,6 +32,7 @@ fn should_apply_rewrite_rules_to_track() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Updated rules for whole-string replacement behavior
@@ -67,6 +68,7 @@ fn should_leave_track_unchanged_when_no_match() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     let rule = RewriteRule::new().with_track_name(SdRule::new(r" - \d{4} Remaster", ""));
@@ -88,6 +90,7 @@ fn should_apply_multiple_rules_sequentially() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Updated rules for whole-string replacement behavior
@@ -117,6 +120,7 @@ fn should_check_if_rule_applies_to_track() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     let rule_that_applies =
@@ -138,6 +142,7 @@ fn should_require_all_non_empty_rules_to_match() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Rule where both track_name and artist_name regexes match - should apply
@@ -196,6 +201,7 @@ fn handles_album_fields_in_rule_matching() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Rule where track_name and album_name both match - should apply
@@ -230,6 +236,7 @@ fn should_match_all_conditions_for_chris_thile_case() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Rule with both artist and album conditions - both must match for rule to apply
@@ -278,6 +285,7 @@ fn should_match_all_conditions_comprehensively() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 42,
+        ..Default::default()
     };
 
     // Test 1: Two conditions, both match - should apply
@@ -379,6 +387,7 @@ fn should_require_all_conditions_even_with_partial_matches() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 10,
+        ..Default::default()
     };
 
     // Test 1: Both regexes would partially match but we need ALL to apply
@@ -433,6 +442,7 @@ fn handles_edge_cases_requiring_all_conditions() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Test 1: Rule matching empty track name and existing artist
@@ -487,6 +497,7 @@ fn should_match_complex_regex_patterns_completely() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 5,
+        ..Default::default()
     };
 
     // Test 1: Complex patterns that all match
@@ -547,6 +558,7 @@ fn should_demonstrate_exact_match_behavior_for_chris_thile() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Test 1: Partial match 'Chris Thil' should match
@@ -606,6 +618,7 @@ fn should_use_anchored_regex_for_exact_matching() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Test 1: Anchored pattern '^Chris Thile$' should match exactly
@@ -661,6 +674,7 @@ fn should_use_anchored_regex_for_exact_matching() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     let rule_exact_anchored_longer =
@@ -694,6 +708,7 @@ fn handles_queen_track_with_chris_thile_rules() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Recreate the actual rules from the CLI output:
@@ -807,6 +822,7 @@ fn should_have_consistent_apply_and_matches_behavior() {
         album_artist: None,
         timestamp: Some(1234567890),
         playcount: 0,
+        ..Default::default()
     };
 
     // Rule with artist condition that WON'T match + album_artist condition that WILL match
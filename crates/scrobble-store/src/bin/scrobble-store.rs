@@ -35,6 +35,11 @@ struct Cli {
     #[arg(long, global = true)]
     git_commit: bool,
 
+    /// Share the lastfm-edit rate-limit budget with other processes using the same
+    /// account (only affects --via scrape)
+    #[arg(long, global = true, env = "LASTFM_EDIT_SHARED_RATE_LIMIT")]
+    shared_rate_limit: bool,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -287,10 +292,15 @@ fn build_source(cli: &Cli, username: &str) -> anyhow_lite::Result<Arc<dyn Scrobb
                 .map_err(|e| format!(
                     "no saved lastfm-edit session for {username} ({e}); log in once with the lastfm-edit CLI first"
                 ))?;
-            let client = lastfm_edit::LastFmEditClientImpl::from_session(
+            let mut client = lastfm_edit::LastFmEditClientImpl::from_session(
                 Box::new(http_client::native::NativeClient::new()),
                 session,
             );
+            if cli.shared_rate_limit {
+                let limiter = lastfm_edit::SharedRateLimiter::for_lastfm_edit_user(username)
+                    .map_err(|e| format!("shared rate limit for {username}: {e}"))?;
+                client = client.with_shared_rate_limiter(Arc::new(limiter));
+            }
             Ok(Arc::new(ScrapeSource::new(client)))
         }
     }
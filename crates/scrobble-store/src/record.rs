@@ -142,6 +142,7 @@ mod tests {
             timestamp,
             album: Some("Music Has the Right to Children".to_string()),
             album_artist: album_artist.map(str::to_string),
+            ..Default::default()
         }
     }
 
@@ -51,6 +51,8 @@ fn success_response(edit: &ExactScrobbleEdit) -> EditResponse {
         message: None,
         album_info: None,
         exact_scrobble_edit: edit.clone(),
+        verification: None,
+        failure_kind: None,
     }])
 }
 
@@ -60,6 +62,8 @@ fn failure_response(edit: &ExactScrobbleEdit, message: &str) -> EditResponse {
         message: Some(message.to_string()),
         album_info: None,
         exact_scrobble_edit: edit.clone(),
+        verification: None,
+        failure_kind: None,
     }])
 }
 
@@ -226,6 +230,7 @@ async fn failed_attempt_stays_pending_then_resume_retries_and_succeeds() {
             timestamp: Some(UTS),
             album: Some("Come Home With Me".to_string()),
             album_artist: None,
+            ..Default::default()
         }],
     };
     let outcomes = editor.resume_pending(&upstream).await.unwrap();
@@ -264,6 +269,7 @@ async fn crash_after_upstream_success_converges_without_resubmitting() {
             timestamp: Some(UTS),
             album: Some("Come Home With Me".to_string()),
             album_artist: None,
+            ..Default::default()
         }],
     };
 
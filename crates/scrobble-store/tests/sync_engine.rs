@@ -22,6 +22,7 @@ fn track(uts: u64, artist: &str, name: &str) -> Track {
         timestamp: Some(uts),
         album: Some("Album".to_string()),
         album_artist: None,
+        ..Default::default()
     }
 }
 
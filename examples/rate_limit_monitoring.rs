@@ -100,6 +100,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         );
                     }
                 }
+                ClientEvent::EditVerified {
+                    edit,
+                    status,
+                    duration_ms,
+                } => {
+                    println!(
+                        "🔎 Edit verification '{}' -> '{}': {status:?} ({duration_ms} ms)",
+                        edit.track_name_original, edit.track_name
+                    );
+                }
+                ClientEvent::PacingAdjusted {
+                    traffic,
+                    delay_ms,
+                    requests_per_minute,
+                    backoff,
+                } => {
+                    let direction = if backoff { "slowed" } else { "sped up" };
+                    println!(
+                        "⏱️ Pacing {direction} for {traffic:?}: {delay_ms}ms between requests (~{requests_per_minute}/min)"
+                    );
+                }
+                ClientEvent::ParseDrift { report } => {
+                    println!(
+                        "🧩 Parse drift on {} page {}: {:?}",
+                        report.page, report.page_number, report.anomalies
+                    );
+                }
+                ClientEvent::SessionRefreshed {
+                    username, reason, ..
+                } => {
+                    println!("🔑 Logged in again as {username}: {reason}");
+                }
+                ClientEvent::CacheHit { url, age_ms, .. } => {
+                    println!("🗄️ Cache hit ({age_ms} ms old): {url}");
+                }
+                ClientEvent::CacheMiss { url, .. } => {
+                    println!("🗄️ Cache miss: {url}");
+                }
+                ClientEvent::CacheInvalidated { change, dropped } => {
+                    println!("🗄️ Dropped {dropped} cached pages for {}", change.artist);
+                }
             }
         }
     });
@@ -187,6 +228,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     );
                 }
             }
+            ClientEvent::EditVerified {
+                edit,
+                status,
+                duration_ms,
+            } => {
+                println!(
+                    "📊 Latest event: Edit verification '{}' -> '{}': {status:?} ({duration_ms} ms)",
+                    edit.track_name_original, edit.track_name
+                );
+            }
+            ClientEvent::PacingAdjusted {
+                traffic,
+                delay_ms,
+                requests_per_minute,
+                backoff,
+            } => {
+                let direction = if backoff { "slowed" } else { "sped up" };
+                println!(
+                    "📊 Latest event: Pacing {direction} for {traffic:?}: {delay_ms}ms between requests (~{requests_per_minute}/min)"
+                );
+            }
+            ClientEvent::ParseDrift { report } => {
+                println!(
+                    "📊 Latest event: Parse drift on {} page {}: {:?}",
+                    report.page, report.page_number, report.anomalies
+                );
+            }
+            ClientEvent::SessionRefreshed {
+                username, reason, ..
+            } => {
+                println!("🔑 Latest event: Logged in again as {username}: {reason}");
+            }
+            ClientEvent::CacheHit { url, age_ms, .. } => {
+                println!("🗄️ Latest event: Cache hit ({age_ms} ms old): {url}");
+            }
+            ClientEvent::CacheMiss { url, .. } => {
+                println!("🗄️ Latest event: Cache miss: {url}");
+            }
+            ClientEvent::CacheInvalidated { change, dropped } => {
+                println!(
+                    "🗄️ Latest event: Dropped {dropped} cached pages for {}",
+                    change.artist
+                );
+            }
         }
     } else {
         println!("📊 No events have occurred yet");
@@ -1,19 +1,31 @@
 #[path = "shared/common.rs"]
 mod common;
 
-use lastfm_edit::{LastFmEditClient, Result};
+use lastfm_edit::{LastFmEditClient, Result, SimulatedEditClient};
 use regex::Regex;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let client = common::setup_client().await?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // With --simulate nothing is sent; the edits are previewed against the real library.
+    let real_client = common::setup_client().await?;
+    let simulation = args
+        .iter()
+        .any(|arg| arg == "--simulate")
+        .then(|| SimulatedEditClient::simulate(real_client.clone()));
+    let client: Box<dyn LastFmEditClient> = match &simulation {
+        Some(simulation) => Box::new(simulation.clone()),
+        None => Box::new(real_client),
+    };
 
     println!("=== Remaster & Year Removal Tool ===\n");
     println!("🎯 This will remove 'remastered' text and year suffixes from track names");
     println!("📝 Patterns include: '- 2009', '(2009)', '[2009]', '- Remaster', etc.\n");
 
-    let artist = std::env::args()
-        .nth(1)
+    let artist = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .cloned()
         .unwrap_or_else(|| "The Beatles".to_string());
 
     println!("🎵 Processing tracks for artist: {artist}\n");
@@ -175,5 +187,10 @@ async fn main() -> Result<()> {
         println!("\n✨ All changes completed successfully!");
     }
 
+    if let Some(simulation) = simulation {
+        println!("\n=== Simulated changes (nothing was sent) ===");
+        print!("{}", simulation.report());
+    }
+
     Ok(())
 }
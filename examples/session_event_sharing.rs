@@ -113,6 +113,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         );
                     }
                 }
+                ClientEvent::EditVerified {
+                    edit,
+                    status,
+                    duration_ms,
+                } => {
+                    println!(
+                        "🔎 Client1 monitor: Edit verification '{}' -> '{}': {status:?} ({duration_ms} ms)",
+                        edit.track_name_original, edit.track_name
+                    );
+                }
+                ClientEvent::PacingAdjusted {
+                    traffic,
+                    delay_ms,
+                    requests_per_minute,
+                    backoff,
+                } => {
+                    let direction = if backoff { "slowed" } else { "sped up" };
+                    println!(
+                        "⏱️ Client1 monitor: Pacing {direction} for {traffic:?}: {delay_ms}ms between requests (~{requests_per_minute}/min)"
+                    );
+                }
+                ClientEvent::ParseDrift { report } => {
+                    println!(
+                        "🧩 Client1 monitor: Parse drift on {} page {}: {:?}",
+                        report.page, report.page_number, report.anomalies
+                    );
+                }
+                ClientEvent::SessionRefreshed {
+                    username, reason, ..
+                } => {
+                    println!("🔑 Client1 monitor: Logged in again as {username}: {reason}");
+                }
+                ClientEvent::CacheHit { url, age_ms, .. } => {
+                    println!("🗄️ Client1 monitor: Cache hit ({age_ms} ms old): {url}");
+                }
+                ClientEvent::CacheMiss { url, .. } => {
+                    println!("🗄️ Client1 monitor: Cache miss: {url}");
+                }
+                ClientEvent::CacheInvalidated { change, dropped } => {
+                    println!(
+                        "🗄️ Client1 monitor: Dropped {dropped} cached pages for {}",
+                        change.artist
+                    );
+                }
             }
         }
     });
@@ -199,6 +243,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         );
                     }
                 }
+                ClientEvent::EditVerified {
+                    edit,
+                    status,
+                    duration_ms,
+                } => {
+                    println!(
+                        "🔎 Client2 monitor: Edit verification '{}' -> '{}': {status:?} ({duration_ms} ms)",
+                        edit.track_name_original, edit.track_name
+                    );
+                }
+                ClientEvent::PacingAdjusted {
+                    traffic,
+                    delay_ms,
+                    requests_per_minute,
+                    backoff,
+                } => {
+                    let direction = if backoff { "slowed" } else { "sped up" };
+                    println!(
+                        "⏱️ Client2 monitor: Pacing {direction} for {traffic:?}: {delay_ms}ms between requests (~{requests_per_minute}/min)"
+                    );
+                }
+                ClientEvent::ParseDrift { report } => {
+                    println!(
+                        "🧩 Client2 monitor: Parse drift on {} page {}: {:?}",
+                        report.page, report.page_number, report.anomalies
+                    );
+                }
+                ClientEvent::SessionRefreshed {
+                    username, reason, ..
+                } => {
+                    println!("🔑 Client2 monitor: Logged in again as {username}: {reason}");
+                }
+                ClientEvent::CacheHit { url, age_ms, .. } => {
+                    println!("🗄️ Client2 monitor: Cache hit ({age_ms} ms old): {url}");
+                }
+                ClientEvent::CacheMiss { url, .. } => {
+                    println!("🗄️ Client2 monitor: Cache miss: {url}");
+                }
+                ClientEvent::CacheInvalidated { change, dropped } => {
+                    println!(
+                        "🗄️ Client2 monitor: Dropped {dropped} cached pages for {}",
+                        change.artist
+                    );
+                }
             }
         }
     });
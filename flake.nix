@@ -419,7 +419,7 @@
 
         packages.lastfm-edit = pkgs.rustPlatform.buildRustPackage {
           pname = "lastfm-edit";
-          version = "7.0.1";
+          version = "8.0.0";
 
           src = rustSource;
 
@@ -427,6 +427,8 @@
             lockFile = ./Cargo.lock;
           };
 
+          buildFeatures = ["cli"];
+
           nativeBuildInputs = with pkgs; [
             pkg-config
           ];
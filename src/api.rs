@@ -1,13 +1,17 @@
 use crate::iterator::{ApiRecentTracksIterator, AsyncPaginatedIterator};
+use crate::retry;
 use crate::types::{
-    ClientEvent, ClientEventReceiver, RequestInfo, SharedEventBroadcaster, Track, TrackPage,
+    ClientConfig, ClientEvent, ClientEventReceiver, DelayReason, RateLimitBehavior, RateLimitType,
+    RequestInfo, SharedEventBroadcaster, Track, TrackPage,
 };
 use crate::Result;
 use async_trait::async_trait;
 use http_client::{HttpClient, Request};
 use http_types::{Method, Url};
-use serde::Deserialize;
-use std::sync::Arc;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use crate::types::LastFmError;
 
@@ -43,12 +47,24 @@ pub trait LastFmApiClient: Clone {
     ) -> Result<TrackPage>;
 }
 
+/// Root endpoint of the Last.fm JSON API.
+const API_ROOT: &str = "https://ws.audioscrobbler.com/2.0/";
+
+/// Maximum number of scrobbles `track.scrobble` accepts in one request.
+pub const MAX_SCROBBLES_PER_REQUEST: usize = 50;
+
+/// Page size requested from paginated user charts and listings.
+const API_PAGE_LIMIT: u32 = 200;
+
 #[derive(Clone)]
 pub struct LastFmApiClientImpl {
     client: Arc<dyn HttpClient + Send + Sync>,
     username: String,
     api_key: String,
     broadcaster: Arc<SharedEventBroadcaster>,
+    api_secret: Option<String>,
+    session_key: Arc<Mutex<Option<String>>>,
+    config: ClientConfig,
 }
 
 impl LastFmApiClientImpl {
@@ -62,9 +78,39 @@ impl LastFmApiClientImpl {
             username,
             api_key,
             broadcaster: Arc::new(SharedEventBroadcaster::new()),
+            api_secret: None,
+            session_key: Arc::new(Mutex::new(None)),
+            config: ClientConfig::default(),
         }
     }
 
+    /// Set the API shared secret used to sign write requests.
+    ///
+    /// Required for [`get_mobile_session`](Self::get_mobile_session),
+    /// [`scrobble`](Self::scrobble) and [`update_now_playing`](Self::update_now_playing).
+    pub fn with_api_secret(mut self, api_secret: String) -> Self {
+        self.api_secret = Some(api_secret);
+        self
+    }
+
+    /// Use a previously obtained session key instead of calling
+    /// [`get_mobile_session`](Self::get_mobile_session).
+    pub fn with_session_key(self, session_key: String) -> Self {
+        *self.session_key.lock().unwrap() = Some(session_key);
+        self
+    }
+
+    /// Set the retry and rate-limit configuration used by write requests.
+    pub fn with_client_config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The current API session key, if one has been set or obtained.
+    pub fn session_key(&self) -> Option<String> {
+        self.session_key.lock().unwrap().clone()
+    }
+
     pub fn subscribe(&self) -> ClientEventReceiver {
         self.broadcaster.subscribe()
     }
@@ -114,9 +160,452 @@ impl LastFmApiClientImpl {
     ) -> Box<dyn AsyncPaginatedIterator<Track>> {
         Box::new(ApiRecentTracksIterator::with_range(self.clone(), from, to))
     }
+
+    /// Exchange the account password for an API session key (`auth.getMobileSession`).
+    ///
+    /// The key is stored on this client (and shared with its clones) for later signed
+    /// calls, and returned so callers can persist it and restore it later with
+    /// [`with_session_key`](Self::with_session_key). Requires an API secret.
+    pub async fn get_mobile_session(&self, password: &str) -> Result<String> {
+        let body = self
+            .signed_post(
+                "auth.getMobileSession",
+                vec![
+                    ("username".to_string(), self.username.clone()),
+                    ("password".to_string(), password.to_string()),
+                ],
+                false,
+            )
+            .await?;
+
+        let response: ApiSessionResponse =
+            serde_json::from_str(&body).map_err(|e| LastFmError::Parse(e.to_string()))?;
+        *self.session_key.lock().unwrap() = Some(response.session.key.clone());
+        Ok(response.session.key)
+    }
+
+    /// Submit plays with `track.scrobble`.
+    ///
+    /// Scrobbles are sent in batches of [`MAX_SCROBBLES_PER_REQUEST`] and the returned
+    /// result covers every batch. When the first batch fails its error is returned, since
+    /// nothing was submitted. When a later batch fails, submission stops and the result
+    /// of the earlier batches is returned with the failure in
+    /// [`ScrobbleBatchResult::failed`]. Requires an API secret and a session key.
+    pub async fn scrobble(&self, scrobbles: &[ApiScrobble]) -> Result<ScrobbleBatchResult> {
+        let mut result = ScrobbleBatchResult::default();
+
+        for (index, batch) in scrobbles.chunks(MAX_SCROBBLES_PER_REQUEST).enumerate() {
+            let mut params = Vec::new();
+            for (i, scrobble) in batch.iter().enumerate() {
+                params.push((format!("artist[{i}]"), scrobble.artist.clone()));
+                params.push((format!("track[{i}]"), scrobble.track.clone()));
+                params.push((format!("timestamp[{i}]"), scrobble.timestamp.to_string()));
+                if let Some(album) = &scrobble.album {
+                    params.push((format!("album[{i}]"), album.clone()));
+                }
+                if let Some(album_artist) = &scrobble.album_artist {
+                    params.push((format!("albumArtist[{i}]"), album_artist.clone()));
+                }
+                if let Some(duration) = scrobble.duration {
+                    params.push((format!("duration[{i}]"), duration.to_string()));
+                }
+            }
+
+            let batch_result = match self.signed_post("track.scrobble", params, true).await {
+                Ok(body) => parse_scrobble_response(&body, batch),
+                Err(e) => Err(e),
+            };
+            match batch_result {
+                Ok(batch_result) => {
+                    result.accepted += batch_result.accepted;
+                    result.ignored.extend(batch_result.ignored);
+                }
+                Err(e) if index == 0 => return Err(e),
+                Err(error) => {
+                    let start = index * MAX_SCROBBLES_PER_REQUEST;
+                    log::warn!(
+                        "Scrobble batch {start}..{} failed; later batches were not sent: {error}",
+                        start + batch.len()
+                    );
+                    result.failed = Some(FailedScrobbleBatch {
+                        range: start..start + batch.len(),
+                        error,
+                    });
+                    break;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Tell Last.fm the user has started listening to a track (`track.updateNowPlaying`).
+    ///
+    /// Requires an API secret and a session key.
+    pub async fn update_now_playing(
+        &self,
+        artist: &str,
+        track: &str,
+        album: Option<&str>,
+    ) -> Result<()> {
+        let mut params = vec![
+            ("artist".to_string(), artist.to_string()),
+            ("track".to_string(), track.to_string()),
+        ];
+        if let Some(album) = album {
+            params.push(("album".to_string(), album.to_string()));
+        }
+
+        self.signed_post("track.updateNowPlaying", params, true)
+            .await?;
+        Ok(())
+    }
+
+    /// Profile details for the user (`user.getInfo`).
+    pub async fn get_user_info(&self) -> Result<ApiUserInfo> {
+        let body = self
+            .api_get("user.getInfo", &[("user", self.username.clone())])
+            .await?;
+        parse_user_info_response(&body)
+    }
+
+    /// A page of the user's most played artists over `period` (`user.getTopArtists`).
+    pub async fn get_top_artists(
+        &self,
+        period: ApiPeriod,
+        page: u32,
+    ) -> Result<ApiPage<ApiTopArtist>> {
+        let body = self
+            .api_get("user.getTopArtists", &self.chart_params(period, page))
+            .await?;
+        parse_top_artists_response(&body)
+    }
+
+    /// A page of the user's most played tracks over `period` (`user.getTopTracks`).
+    pub async fn get_top_tracks(
+        &self,
+        period: ApiPeriod,
+        page: u32,
+    ) -> Result<ApiPage<ApiTopTrack>> {
+        let body = self
+            .api_get("user.getTopTracks", &self.chart_params(period, page))
+            .await?;
+        parse_top_tracks_response(&body)
+    }
+
+    /// A page of the user's most played albums over `period` (`user.getTopAlbums`).
+    pub async fn get_top_albums(
+        &self,
+        period: ApiPeriod,
+        page: u32,
+    ) -> Result<ApiPage<ApiTopAlbum>> {
+        let body = self
+            .api_get("user.getTopAlbums", &self.chart_params(period, page))
+            .await?;
+        parse_top_albums_response(&body)
+    }
+
+    /// A page of the user's loved tracks, most recently loved first (`user.getLovedTracks`).
+    pub async fn get_loved_tracks(&self, page: u32) -> Result<ApiPage<ApiLovedTrack>> {
+        let body = self
+            .api_get(
+                "user.getLovedTracks",
+                &[
+                    ("user", self.username.clone()),
+                    ("page", page.to_string()),
+                    ("limit", API_PAGE_LIMIT.to_string()),
+                ],
+            )
+            .await?;
+        parse_loved_tracks_response(&body)
+    }
+
+    /// Metadata for a track, including the user's own play count (`track.getInfo`).
+    pub async fn get_track_info(&self, artist: &str, track: &str) -> Result<ApiTrackInfo> {
+        let body = self
+            .api_get(
+                "track.getInfo",
+                &[
+                    ("artist", artist.to_string()),
+                    ("track", track.to_string()),
+                    ("username", self.username.clone()),
+                ],
+            )
+            .await?;
+        parse_track_info_response(&body)
+    }
+
+    /// Metadata and track listing for an album (`album.getInfo`).
+    pub async fn get_album_info(&self, artist: &str, album: &str) -> Result<ApiAlbumInfo> {
+        let body = self
+            .api_get(
+                "album.getInfo",
+                &[
+                    ("artist", artist.to_string()),
+                    ("album", album.to_string()),
+                    ("username", self.username.clone()),
+                ],
+            )
+            .await?;
+        parse_album_info_response(&body)
+    }
+
+    /// Metadata for an artist (`artist.getInfo`).
+    pub async fn get_artist_info(&self, artist: &str) -> Result<ApiArtistInfo> {
+        let body = self
+            .api_get(
+                "artist.getInfo",
+                &[
+                    ("artist", artist.to_string()),
+                    ("username", self.username.clone()),
+                ],
+            )
+            .await?;
+        parse_artist_info_response(&body)
+    }
+
+    /// Last.fm's canonical spelling of an artist name (`artist.getCorrection`).
+    ///
+    /// Returns `None` when Last.fm has no correction for the name.
+    pub async fn get_artist_correction(&self, artist: &str) -> Result<Option<ApiArtistCorrection>> {
+        let body = self
+            .api_get("artist.getCorrection", &[("artist", artist.to_string())])
+            .await?;
+        parse_artist_correction_response(&body)
+    }
+
+    fn chart_params(&self, period: ApiPeriod, page: u32) -> Vec<(&'static str, String)> {
+        vec![
+            ("user", self.username.clone()),
+            ("period", period.as_str().to_string()),
+            ("page", page.to_string()),
+            ("limit", API_PAGE_LIMIT.to_string()),
+        ]
+    }
+
+    /// Unsigned GET of `api_method`, returning the raw response body.
+    async fn api_get(&self, api_method: &str, params: &[(&str, String)]) -> Result<String> {
+        let url = build_api_url(api_method, &self.api_key, params);
+        fetch_api_body(&self.client, &self.broadcaster, &url).await
+    }
+
+    /// Sign `params` for `api_method` and POST them, retrying rate limits according to
+    /// the configured [`RetryConfig`](crate::RetryConfig).
+    async fn signed_post(
+        &self,
+        api_method: &str,
+        params: Vec<(String, String)>,
+        requires_session: bool,
+    ) -> Result<String> {
+        let params = self.signed_params(api_method, params, requires_session)?;
+
+        // Mirror the scraping client: non-blocking mode never sleeps internally.
+        if self.config.rate_limit_behavior == RateLimitBehavior::ReturnError
+            || !self.config.retry.enabled
+        {
+            return self.signed_post_without_retry(api_method, &params).await;
+        }
+
+        let request_info = api_request_info(api_method);
+        let retry_result = retry::retry_with_backoff(
+            self.config.retry.clone(),
+            api_method,
+            || self.signed_post_without_retry(api_method, &params),
+            |delay, rate_limit_timestamp, operation_name| {
+                self.broadcaster.broadcast_event(ClientEvent::RateLimited {
+                    delay_seconds: delay,
+                    request: None,
+                    rate_limit_type: RateLimitType::ApiError,
+                    rate_limit_timestamp,
+                });
+                self.broadcaster.broadcast_event(ClientEvent::Delaying {
+                    delay_ms: delay * 1000,
+                    reason: DelayReason::RetryBackoff,
+                    request: None,
+                    delay_timestamp: rate_limit_timestamp,
+                });
+                log::debug!("{operation_name} rate limited, waiting {delay} seconds");
+            },
+            |total_duration, _operation_name| {
+                self.broadcaster
+                    .broadcast_event(ClientEvent::RateLimitEnded {
+                        request: request_info.clone(),
+                        rate_limit_type: RateLimitType::ApiError,
+                        total_rate_limit_duration_seconds: total_duration,
+                    });
+            },
+        )
+        .await?;
+
+        Ok(retry_result.result)
+    }
+
+    fn signed_params(
+        &self,
+        api_method: &str,
+        mut params: Vec<(String, String)>,
+        requires_session: bool,
+    ) -> Result<Vec<(String, String)>> {
+        let api_secret = self.api_secret.as_deref().ok_or_else(|| {
+            LastFmError::Auth("An API secret is required for signed API calls".to_string())
+        })?;
+
+        params.push(("method".to_string(), api_method.to_string()));
+        params.push(("api_key".to_string(), self.api_key.clone()));
+        if requires_session {
+            let session_key = self.session_key().ok_or_else(|| {
+                LastFmError::Auth(
+                    "No API session key; call get_mobile_session or with_session_key first"
+                        .to_string(),
+                )
+            })?;
+            params.push(("sk".to_string(), session_key));
+        }
+
+        let api_sig = sign_api_params(&params, api_secret);
+        params.push(("api_sig".to_string(), api_sig));
+        params.push(("format".to_string(), "json".to_string()));
+        Ok(params)
+    }
+
+    /// Single signed POST attempt, with the scraping client's event and rate-limit handling.
+    async fn signed_post_without_retry(
+        &self,
+        api_method: &str,
+        params: &[(String, String)],
+    ) -> Result<String> {
+        let request_info = api_request_info(api_method);
+        let request_start = std::time::Instant::now();
+
+        self.broadcaster
+            .broadcast_event(ClientEvent::RequestStarted {
+                request: request_info.clone(),
+            });
+
+        let form_string: String = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut request = Request::new(Method::Post, API_ROOT.parse::<Url>().unwrap());
+        let _ = request.insert_header("Content-Type", "application/x-www-form-urlencoded");
+        request.set_body(form_string);
+
+        let mut response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| LastFmError::Http(e.to_string()))?;
+
+        let status = response.status();
+        self.broadcaster
+            .broadcast_event(ClientEvent::RequestCompleted {
+                request: request_info.clone(),
+                status_code: status.into(),
+                duration_ms: request_start.elapsed().as_millis() as u64,
+            });
+
+        if self.config.rate_limit.detect_by_status && (status == 429 || status == 503) {
+            let retry_after = response
+                .header("retry-after")
+                .and_then(|h| h.get(0))
+                .and_then(|v| v.as_str().parse::<u64>().ok())
+                .unwrap_or(60);
+            let rate_limit_type = if status == 429 {
+                RateLimitType::Http429
+            } else {
+                RateLimitType::Http503
+            };
+            return Err(self.rate_limited(request_info, rate_limit_type, retry_after));
+        }
+
+        let body = response
+            .body_string()
+            .await
+            .map_err(|e| LastFmError::Http(e.to_string()))?;
+
+        if let Ok(api_error) = serde_json::from_str::<ApiErrorResponse>(&body) {
+            return Err(match api_error.error {
+                API_ERROR_RATE_LIMIT_EXCEEDED => {
+                    self.rate_limited(request_info, RateLimitType::ApiError, 60)
+                }
+                API_ERROR_AUTHENTICATION_FAILED | API_ERROR_INVALID_SESSION_KEY => {
+                    LastFmError::Auth(format!(
+                        "last.fm API error {}: {}",
+                        api_error.error, api_error.message
+                    ))
+                }
+                _ => LastFmError::Http(format!(
+                    "last.fm API error {}: {}",
+                    api_error.error, api_error.message
+                )),
+            });
+        }
+
+        if !status.is_success() {
+            return Err(LastFmError::Http(format!(
+                "{api_method} failed with HTTP {status}"
+            )));
+        }
+
+        Ok(body)
+    }
+
+    fn rate_limited(
+        &self,
+        request: RequestInfo,
+        rate_limit_type: RateLimitType,
+        retry_after: u64,
+    ) -> LastFmError {
+        self.broadcaster.broadcast_event(ClientEvent::RateLimited {
+            delay_seconds: retry_after,
+            request: Some(request),
+            rate_limit_type,
+            rate_limit_timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        });
+        LastFmError::RateLimit { retry_after }
+    }
+}
+
+/// API error code for "Authentication Failed" (bad username/password).
+const API_ERROR_AUTHENTICATION_FAILED: i64 = 4;
+/// API error code for "Invalid session key - Please re-authenticate".
+const API_ERROR_INVALID_SESSION_KEY: i64 = 9;
+/// API error code for "Rate limit exceeded".
+const API_ERROR_RATE_LIMIT_EXCEEDED: i64 = 29;
+
+fn api_request_info(api_method: &str) -> RequestInfo {
+    RequestInfo::from_url_and_method(&format!("{API_ROOT}?method={api_method}"), "POST")
+}
+
+/// Compute the `api_sig` for a signed API call.
+///
+/// Parameters are sorted by name and concatenated as `name` + `value`, the shared secret
+/// is appended, and the result is MD5-hashed. `format` and `callback` are not signed.
+pub(crate) fn sign_api_params(params: &[(String, String)], api_secret: &str) -> String {
+    let mut signed: Vec<&(String, String)> = params
+        .iter()
+        .filter(|(name, _)| name != "format" && name != "callback")
+        .collect();
+    signed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut payload = String::new();
+    for (name, value) in signed {
+        payload.push_str(name);
+        payload.push_str(value);
+    }
+    payload.push_str(api_secret);
+
+    format!("{:x}", md5::compute(payload.as_bytes()))
 }
 
 /// Build the `user.getRecentTracks` request URL, appending `from`/`to` only when present.
+///
+/// Always asks for `extended=1` so responses include the loved flag.
 pub(crate) fn build_recent_tracks_url(
     username: &str,
     api_key: &str,
@@ -125,7 +614,7 @@ pub(crate) fn build_recent_tracks_url(
     to: Option<u64>,
 ) -> String {
     let mut url = format!(
-        "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user={}&api_key={}&format=json&page={}&limit=200",
+        "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user={}&api_key={}&format=json&extended=1&page={}&limit=200",
         urlencoding::encode(username),
         urlencoding::encode(api_key),
         page
@@ -141,24 +630,29 @@ pub(crate) fn build_recent_tracks_url(
     url
 }
 
-/// Shared implementation of a single `user.getRecentTracks` page fetch.
+/// Build an unsigned read request URL for `api_method`, appending `params` in order.
+pub(crate) fn build_api_url(api_method: &str, api_key: &str, params: &[(&str, String)]) -> String {
+    let mut url = format!(
+        "{API_ROOT}?method={}&api_key={}&format=json",
+        urlencoding::encode(api_method),
+        urlencoding::encode(api_key)
+    );
+    for (name, value) in params {
+        url.push_str(&format!("&{name}={}", urlencoding::encode(value)));
+    }
+    url
+}
+
+/// GET `url` from the JSON API and return the response body.
 ///
-/// Builds the request URL (including the optional `from`/`to` unix-timestamp window),
-/// broadcasts `RequestStarted`/`RequestCompleted` events, and parses the JSON response.
-/// Used by both [`LastFmApiClientImpl`] and `LastFmEditClientImpl` so the request logic
-/// exists in exactly one place.
-pub(crate) async fn fetch_recent_tracks_page(
+/// Broadcasts `RequestStarted`/`RequestCompleted` events on `broadcaster`. Shared by every
+/// unsigned read so they all report requests the same way.
+pub(crate) async fn fetch_api_body(
     client: &Arc<dyn HttpClient + Send + Sync>,
     broadcaster: &SharedEventBroadcaster,
-    username: &str,
-    api_key: &str,
-    page: u32,
-    from: Option<u64>,
-    to: Option<u64>,
-) -> Result<TrackPage> {
-    let url = build_recent_tracks_url(username, api_key, page, from, to);
-
-    let request_info = RequestInfo::from_url_and_method(&url, "GET");
+    url: &str,
+) -> Result<String> {
+    let request_info = RequestInfo::from_url_and_method(url, "GET");
     let request_start = std::time::Instant::now();
 
     broadcaster.broadcast_event(ClientEvent::RequestStarted {
@@ -177,11 +671,44 @@ pub(crate) async fn fetch_recent_tracks_page(
         duration_ms: request_start.elapsed().as_millis() as u64,
     });
 
-    let body = response
+    response
         .body_string()
         .await
-        .map_err(|e| LastFmError::Http(e.to_string()))?;
+        .map_err(|e| LastFmError::Http(e.to_string()))
+}
+
+/// Deserialize an API response body, surfacing the API's own error payload when the body
+/// is not the expected shape.
+fn parse_api_body<T: DeserializeOwned>(json: &str) -> Result<T> {
+    serde_json::from_str(json).map_err(|e| {
+        if let Ok(api_error) = serde_json::from_str::<ApiErrorResponse>(json) {
+            LastFmError::Http(format!(
+                "last.fm API error {}: {}",
+                api_error.error, api_error.message
+            ))
+        } else {
+            LastFmError::Parse(e.to_string())
+        }
+    })
+}
 
+/// Shared implementation of a single `user.getRecentTracks` page fetch.
+///
+/// Builds the request URL (including the optional `from`/`to` unix-timestamp window),
+/// broadcasts `RequestStarted`/`RequestCompleted` events, and parses the JSON response.
+/// Used by both [`LastFmApiClientImpl`] and `LastFmEditClientImpl` so the request logic
+/// exists in exactly one place.
+pub(crate) async fn fetch_recent_tracks_page(
+    client: &Arc<dyn HttpClient + Send + Sync>,
+    broadcaster: &SharedEventBroadcaster,
+    username: &str,
+    api_key: &str,
+    page: u32,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<TrackPage> {
+    let url = build_recent_tracks_url(username, api_key, page, from, to);
+    let body = fetch_api_body(client, broadcaster, &url).await?;
     parse_api_recent_tracks_response(&body)
 }
 
@@ -221,17 +748,18 @@ pub struct ApiRecentTracks {
     pub attr: ApiPaginationAttr,
 }
 
-fn deserialize_one_or_many<'de, D>(deserializer: D) -> std::result::Result<Vec<ApiTrack>, D::Error>
+fn deserialize_one_or_many<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
 {
     #[derive(Deserialize)]
     #[serde(untagged)]
-    enum OneOrMany {
-        Many(Vec<ApiTrack>),
-        One(Box<ApiTrack>),
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(Box<T>),
     }
-    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+    Ok(match Option::<OneOrMany<T>>::deserialize(deserializer)? {
         None => Vec::new(),
         Some(OneOrMany::Many(tracks)) => tracks,
         Some(OneOrMany::One(track)) => vec![*track],
@@ -245,20 +773,818 @@ struct ApiErrorResponse {
     message: String,
 }
 
+/// A play to submit with [`LastFmApiClientImpl::scrobble`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiScrobble {
+    pub artist: String,
+    pub track: String,
+    /// When the track started playing (seconds since the Unix epoch)
+    pub timestamp: u64,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    /// Track length in seconds
+    pub duration: Option<u32>,
+}
+
+impl ApiScrobble {
+    pub fn new(artist: &str, track: &str, timestamp: u64) -> Self {
+        Self {
+            artist: artist.to_string(),
+            track: track.to_string(),
+            timestamp,
+            album: None,
+            album_artist: None,
+            duration: None,
+        }
+    }
+
+    pub fn with_album(mut self, album: &str) -> Self {
+        self.album = Some(album.to_string());
+        self
+    }
+
+    pub fn with_album_artist(mut self, album_artist: &str) -> Self {
+        self.album_artist = Some(album_artist.to_string());
+        self
+    }
+
+    pub fn with_duration(mut self, duration: u32) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+/// A submitted scrobble that Last.fm accepted the request for but did not record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredScrobble {
+    pub scrobble: ApiScrobble,
+    /// Last.fm's ignored-message code (e.g. 1 = artist ignored, 3 = timestamp too old)
+    pub code: u32,
+    pub message: String,
+}
+
+/// Outcome of [`LastFmApiClientImpl::scrobble`] across all batches.
+#[derive(Debug, Default)]
+pub struct ScrobbleBatchResult {
+    /// Number of scrobbles Last.fm recorded
+    pub accepted: u32,
+    /// Scrobbles Last.fm ignored, with the reason it gave
+    pub ignored: Vec<IgnoredScrobble>,
+    /// The batch that failed after earlier batches were submitted, if any
+    pub failed: Option<FailedScrobbleBatch>,
+}
+
+impl ScrobbleBatchResult {
+    pub fn all_accepted(&self) -> bool {
+        self.ignored.is_empty() && self.failed.is_none()
+    }
+}
+
+/// A `track.scrobble` batch that failed part-way through a submission.
+///
+/// `range` indexes the scrobbles passed to [`LastFmApiClientImpl::scrobble`]; none of
+/// them were recorded, and the scrobbles after `range.end` were not sent.
+#[derive(Debug)]
+pub struct FailedScrobbleBatch {
+    pub range: std::ops::Range<usize>,
+    pub error: LastFmError,
+}
+
+/// Time window for the `user.getTop*` charts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiPeriod {
+    #[default]
+    Overall,
+    SevenDay,
+    OneMonth,
+    ThreeMonth,
+    SixMonth,
+    TwelveMonth,
+}
+
+impl ApiPeriod {
+    /// The value of the API's `period` parameter.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiPeriod::Overall => "overall",
+            ApiPeriod::SevenDay => "7day",
+            ApiPeriod::OneMonth => "1month",
+            ApiPeriod::ThreeMonth => "3month",
+            ApiPeriod::SixMonth => "6month",
+            ApiPeriod::TwelveMonth => "12month",
+        }
+    }
+}
+
+/// One page of a paginated API listing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiPage<T> {
+    pub items: Vec<T>,
+    pub page_number: u32,
+    pub has_next_page: bool,
+    pub total_pages: Option<u32>,
+}
+
+/// Profile details from `user.getInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiUserInfo {
+    pub name: String,
+    pub real_name: Option<String>,
+    pub url: String,
+    pub country: Option<String>,
+    /// Total scrobbles
+    pub playcount: u64,
+    pub artist_count: Option<u64>,
+    pub album_count: Option<u64>,
+    pub track_count: Option<u64>,
+    /// When the account was created (seconds since the Unix epoch)
+    pub registered: Option<u64>,
+    pub subscriber: bool,
+}
+
+/// An entry of `user.getTopArtists`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTopArtist {
+    pub rank: u32,
+    pub name: String,
+    pub mbid: Option<String>,
+    pub playcount: u64,
+}
+
+/// An entry of `user.getTopTracks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTopTrack {
+    pub rank: u32,
+    pub name: String,
+    pub artist: String,
+    pub mbid: Option<String>,
+    pub playcount: u64,
+    /// Track length in seconds, when Last.fm knows it
+    pub duration: Option<u32>,
+}
+
+/// An entry of `user.getTopAlbums`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTopAlbum {
+    pub rank: u32,
+    pub name: String,
+    pub artist: String,
+    pub mbid: Option<String>,
+    pub playcount: u64,
+}
+
+/// An entry of `user.getLovedTracks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiLovedTrack {
+    pub name: String,
+    pub artist: String,
+    pub mbid: Option<String>,
+    /// When the track was loved (seconds since the Unix epoch)
+    pub loved_at: Option<u64>,
+}
+
+/// Track metadata from `track.getInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTrackInfo {
+    pub name: String,
+    pub mbid: Option<String>,
+    pub artist: String,
+    pub artist_mbid: Option<String>,
+    pub album: Option<String>,
+    pub album_mbid: Option<String>,
+    /// Track length in milliseconds, when Last.fm knows it
+    pub duration_ms: Option<u64>,
+    pub listeners: u64,
+    pub playcount: u64,
+    /// The user's own play count
+    pub user_playcount: Option<u64>,
+    pub user_loved: Option<bool>,
+    /// Top tags, most popular first
+    pub tags: Vec<String>,
+}
+
+/// Album metadata from `album.getInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiAlbumInfo {
+    pub name: String,
+    pub artist: String,
+    pub mbid: Option<String>,
+    pub listeners: u64,
+    pub playcount: u64,
+    /// The user's own play count
+    pub user_playcount: Option<u64>,
+    pub tracks: Vec<ApiAlbumTrack>,
+    pub tags: Vec<String>,
+}
+
+/// A track on an [`ApiAlbumInfo`] listing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiAlbumTrack {
+    /// Position on the album
+    pub rank: Option<u32>,
+    pub name: String,
+    pub artist: String,
+    /// Track length in seconds, when Last.fm knows it
+    pub duration: Option<u32>,
+}
+
+/// Artist metadata from `artist.getInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiArtistInfo {
+    pub name: String,
+    pub mbid: Option<String>,
+    pub listeners: u64,
+    pub playcount: u64,
+    /// The user's own play count
+    pub user_playcount: Option<u64>,
+    pub tags: Vec<String>,
+}
+
+/// Last.fm's canonical form of an artist name, from `artist.getCorrection`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiArtistCorrection {
+    pub name: String,
+    pub mbid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiSessionResponse {
+    session: ApiSession,
+}
+
+#[derive(Deserialize)]
+struct ApiSession {
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct ApiScrobblesResponse {
+    scrobbles: ApiScrobbles,
+}
+
+#[derive(Deserialize)]
+struct ApiScrobbles {
+    #[serde(default, deserialize_with = "deserialize_one_or_many")]
+    scrobble: Vec<ApiScrobbleResult>,
+    #[serde(rename = "@attr")]
+    attr: ApiScrobblesAttr,
+}
+
+#[derive(Deserialize)]
+struct ApiScrobblesAttr {
+    #[serde(deserialize_with = "deserialize_number_or_string")]
+    accepted: u32,
+}
+
+#[derive(Deserialize)]
+struct ApiScrobbleResult {
+    #[serde(rename = "ignoredMessage")]
+    ignored_message: ApiIgnoredMessage,
+}
+
+#[derive(Deserialize)]
+struct ApiIgnoredMessage {
+    #[serde(deserialize_with = "deserialize_number_or_string")]
+    code: u32,
+    #[serde(rename = "#text", default)]
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString<T> {
+    Number(T),
+    String(String),
+}
+
+/// The API is inconsistent about quoting numbers; accept `1` and `"1"` alike.
+fn deserialize_number_or_string<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: std::fmt::Display,
+{
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Like [`deserialize_number_or_string`], but a missing, `null` or empty value is `None`.
+fn deserialize_optional_number<'de, D, T>(
+    deserializer: D,
+) -> std::result::Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: std::fmt::Display,
+{
+    match Option::<NumberOrString<T>>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NumberOrString::Number(n)) => Ok(Some(n)),
+        Some(NumberOrString::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(NumberOrString::String(s)) => {
+            s.trim().parse().map(Some).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Tag lists arrive as `{"tag": [...]}`, `{"tag": {...}}`, or an empty string.
+fn deserialize_tags<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Tag {
+        name: String,
+    }
+    #[derive(Deserialize)]
+    struct Tags {
+        #[serde(default, deserialize_with = "deserialize_one_or_many")]
+        tag: Vec<Tag>,
+    }
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum TagsOrEmpty {
+        Tags(Tags),
+        Empty(IgnoredAny),
+    }
+    Ok(match TagsOrEmpty::deserialize(deserializer)? {
+        TagsOrEmpty::Tags(tags) => tags.tag.into_iter().map(|t| t.name).collect(),
+        TagsOrEmpty::Empty(_) => Vec::new(),
+    })
+}
+
+/// The API reports unknown MBIDs and text fields as empty strings.
+fn non_empty(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// A nested `{"name": ..., "mbid": ...}` object, as used for artists in read responses.
+#[derive(Deserialize)]
+struct ApiNamed {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+}
+
+#[derive(Deserialize)]
+struct ApiRankAttr {
+    #[serde(deserialize_with = "deserialize_number_or_string")]
+    rank: u32,
+}
+
+/// A paginated listing; the item field is named after what it lists.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct ApiListing<T> {
+    #[serde(
+        default,
+        alias = "artist",
+        alias = "track",
+        alias = "album",
+        deserialize_with = "deserialize_one_or_many"
+    )]
+    items: Vec<T>,
+    #[serde(rename = "@attr")]
+    attr: ApiPaginationAttr,
+}
+
+impl<T> ApiListing<T> {
+    fn into_page<U>(self, convert: impl FnMut(T) -> U) -> ApiPage<U> {
+        let page_number: u32 = self.attr.page.parse().unwrap_or(1);
+        let total_pages: u32 = self.attr.total_pages.parse().unwrap_or(1);
+        ApiPage {
+            items: self.items.into_iter().map(convert).collect(),
+            page_number,
+            has_next_page: page_number < total_pages,
+            total_pages: Some(total_pages),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiUserInfoResponse {
+    user: ApiRawUserInfo,
+}
+
+#[derive(Deserialize)]
+struct ApiRawUserInfo {
+    name: String,
+    #[serde(default)]
+    realname: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    country: String,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    playcount: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    artist_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    album_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    track_count: Option<u64>,
+    registered: Option<ApiRegistered>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    subscriber: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ApiRegistered {
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    unixtime: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ApiTopArtistsResponse {
+    topartists: ApiListing<ApiRawTopArtist>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawTopArtist {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
+    playcount: u64,
+    #[serde(rename = "@attr")]
+    attr: ApiRankAttr,
+}
+
+#[derive(Deserialize)]
+struct ApiTopTracksResponse {
+    toptracks: ApiListing<ApiRawTopTrack>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawTopTrack {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    artist: ApiNamed,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
+    playcount: u64,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    duration: Option<u32>,
+    #[serde(rename = "@attr")]
+    attr: ApiRankAttr,
+}
+
+#[derive(Deserialize)]
+struct ApiTopAlbumsResponse {
+    topalbums: ApiListing<ApiRawTopAlbum>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawTopAlbum {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    artist: ApiNamed,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
+    playcount: u64,
+    #[serde(rename = "@attr")]
+    attr: ApiRankAttr,
+}
+
+#[derive(Deserialize)]
+struct ApiLovedTracksResponse {
+    lovedtracks: ApiListing<ApiRawLovedTrack>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawLovedTrack {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    artist: ApiNamed,
+    date: Option<ApiDate>,
+}
+
+#[derive(Deserialize)]
+struct ApiTrackInfoResponse {
+    track: ApiRawTrackInfo,
+}
+
+#[derive(Deserialize)]
+struct ApiRawTrackInfo {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    artist: ApiNamed,
+    album: Option<ApiRawTrackAlbum>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    duration: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    listeners: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    playcount: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    userplaycount: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    userloved: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    toptags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawTrackAlbum {
+    title: String,
+    #[serde(default)]
+    mbid: String,
+}
+
+#[derive(Deserialize)]
+struct ApiAlbumInfoResponse {
+    album: ApiRawAlbumInfo,
+}
+
+#[derive(Deserialize)]
+struct ApiRawAlbumInfo {
+    name: String,
+    artist: String,
+    #[serde(default)]
+    mbid: String,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    listeners: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    playcount: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    userplaycount: Option<u64>,
+    tracks: Option<ApiRawAlbumTracks>,
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawAlbumTracks {
+    #[serde(default, deserialize_with = "deserialize_one_or_many")]
+    track: Vec<ApiRawAlbumTrack>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawAlbumTrack {
+    name: String,
+    artist: ApiNamed,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    duration: Option<u32>,
+    #[serde(rename = "@attr")]
+    attr: Option<ApiRankAttr>,
+}
+
+#[derive(Deserialize)]
+struct ApiArtistInfoResponse {
+    artist: ApiRawArtistInfo,
+}
+
+#[derive(Deserialize)]
+struct ApiRawArtistInfo {
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    stats: Option<ApiRawArtistStats>,
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiRawArtistStats {
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    listeners: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    playcount: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number")]
+    userplaycount: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ApiCorrectionResponse {
+    corrections: ApiRawCorrections,
+}
+
+/// Without a correction the API sends `"corrections"` as a whitespace string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ApiRawCorrections {
+    Found {
+        #[serde(deserialize_with = "deserialize_one_or_many")]
+        correction: Vec<ApiRawCorrection>,
+    },
+    Empty(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct ApiRawCorrection {
+    artist: ApiNamed,
+}
+
+/// Parse a `track.scrobble` response for `batch`, pairing each result with the scrobble
+/// that was submitted at the same index.
+pub fn parse_scrobble_response(json: &str, batch: &[ApiScrobble]) -> Result<ScrobbleBatchResult> {
+    let response: ApiScrobblesResponse =
+        serde_json::from_str(json).map_err(|e| LastFmError::Parse(e.to_string()))?;
+
+    let ignored = response
+        .scrobbles
+        .scrobble
+        .into_iter()
+        .zip(batch)
+        .filter(|(result, _)| result.ignored_message.code != 0)
+        .map(|(result, scrobble)| IgnoredScrobble {
+            scrobble: scrobble.clone(),
+            code: result.ignored_message.code,
+            message: result.ignored_message.text,
+        })
+        .collect();
+
+    Ok(ScrobbleBatchResult {
+        accepted: response.scrobbles.attr.accepted,
+        ignored,
+        failed: None,
+    })
+}
+
+fn parse_user_info_response(json: &str) -> Result<ApiUserInfo> {
+    let user = parse_api_body::<ApiUserInfoResponse>(json)?.user;
+    Ok(ApiUserInfo {
+        name: user.name,
+        real_name: non_empty(user.realname),
+        url: user.url,
+        country: non_empty(user.country).filter(|c| c != "None"),
+        playcount: user.playcount.unwrap_or_default(),
+        artist_count: user.artist_count,
+        album_count: user.album_count,
+        track_count: user.track_count,
+        registered: user.registered.and_then(|r| r.unixtime),
+        subscriber: user.subscriber.unwrap_or_default() != 0,
+    })
+}
+
+fn parse_top_artists_response(json: &str) -> Result<ApiPage<ApiTopArtist>> {
+    let response: ApiTopArtistsResponse = parse_api_body(json)?;
+    Ok(response.topartists.into_page(|a| ApiTopArtist {
+        rank: a.attr.rank,
+        name: a.name,
+        mbid: non_empty(a.mbid),
+        playcount: a.playcount,
+    }))
+}
+
+fn parse_top_tracks_response(json: &str) -> Result<ApiPage<ApiTopTrack>> {
+    let response: ApiTopTracksResponse = parse_api_body(json)?;
+    Ok(response.toptracks.into_page(|t| ApiTopTrack {
+        rank: t.attr.rank,
+        name: t.name,
+        artist: t.artist.name,
+        mbid: non_empty(t.mbid),
+        playcount: t.playcount,
+        duration: t.duration.filter(|&d| d > 0),
+    }))
+}
+
+fn parse_top_albums_response(json: &str) -> Result<ApiPage<ApiTopAlbum>> {
+    let response: ApiTopAlbumsResponse = parse_api_body(json)?;
+    Ok(response.topalbums.into_page(|a| ApiTopAlbum {
+        rank: a.attr.rank,
+        name: a.name,
+        artist: a.artist.name,
+        mbid: non_empty(a.mbid),
+        playcount: a.playcount,
+    }))
+}
+
+fn parse_loved_tracks_response(json: &str) -> Result<ApiPage<ApiLovedTrack>> {
+    let response: ApiLovedTracksResponse = parse_api_body(json)?;
+    Ok(response.lovedtracks.into_page(|t| ApiLovedTrack {
+        name: t.name,
+        artist: t.artist.name,
+        mbid: non_empty(t.mbid),
+        loved_at: t.date.and_then(|d| d.uts.parse().ok()),
+    }))
+}
+
+fn parse_track_info_response(json: &str) -> Result<ApiTrackInfo> {
+    let track = parse_api_body::<ApiTrackInfoResponse>(json)?.track;
+    let (album, album_mbid) = match track.album {
+        Some(album) => (non_empty(album.title), non_empty(album.mbid)),
+        None => (None, None),
+    };
+    Ok(ApiTrackInfo {
+        name: track.name,
+        mbid: non_empty(track.mbid),
+        artist: track.artist.name,
+        artist_mbid: non_empty(track.artist.mbid),
+        album,
+        album_mbid,
+        duration_ms: track.duration.filter(|&d| d > 0),
+        listeners: track.listeners.unwrap_or_default(),
+        playcount: track.playcount.unwrap_or_default(),
+        user_playcount: track.userplaycount,
+        user_loved: track.userloved.map(|loved| loved != 0),
+        tags: track.toptags,
+    })
+}
+
+fn parse_album_info_response(json: &str) -> Result<ApiAlbumInfo> {
+    let album = parse_api_body::<ApiAlbumInfoResponse>(json)?.album;
+    let tracks = album
+        .tracks
+        .map(|tracks| tracks.track)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| ApiAlbumTrack {
+            rank: t.attr.map(|attr| attr.rank),
+            name: t.name,
+            artist: t.artist.name,
+            duration: t.duration.filter(|&d| d > 0),
+        })
+        .collect();
+    Ok(ApiAlbumInfo {
+        name: album.name,
+        artist: album.artist,
+        mbid: non_empty(album.mbid),
+        listeners: album.listeners.unwrap_or_default(),
+        playcount: album.playcount.unwrap_or_default(),
+        user_playcount: album.userplaycount,
+        tracks,
+        tags: album.tags,
+    })
+}
+
+fn parse_artist_info_response(json: &str) -> Result<ApiArtistInfo> {
+    let artist = parse_api_body::<ApiArtistInfoResponse>(json)?.artist;
+    let (listeners, playcount, user_playcount) = match artist.stats {
+        Some(stats) => (
+            stats.listeners.unwrap_or_default(),
+            stats.playcount.unwrap_or_default(),
+            stats.userplaycount,
+        ),
+        None => (0, 0, None),
+    };
+    Ok(ApiArtistInfo {
+        name: artist.name,
+        mbid: non_empty(artist.mbid),
+        listeners,
+        playcount,
+        user_playcount,
+        tags: artist.tags,
+    })
+}
+
+fn parse_artist_correction_response(json: &str) -> Result<Option<ApiArtistCorrection>> {
+    let response: ApiCorrectionResponse = parse_api_body(json)?;
+    Ok(match response.corrections {
+        ApiRawCorrections::Found { correction } => {
+            correction.into_iter().next().map(|c| ApiArtistCorrection {
+                name: c.artist.name,
+                mbid: non_empty(c.artist.mbid),
+            })
+        }
+        ApiRawCorrections::Empty(_) => None,
+    })
+}
+
 #[derive(Deserialize)]
 pub struct ApiTrack {
     pub name: String,
+    #[serde(default)]
+    pub mbid: String,
     pub artist: ApiTextField,
     pub album: ApiTextField,
     pub date: Option<ApiDate>,
+    /// `"1"` or `"0"`; only present on `extended=1` responses.
+    pub loved: Option<String>,
+    /// Cover images, smallest first.
+    #[serde(default)]
+    pub image: Vec<ApiImage>,
     #[serde(rename = "@attr")]
     pub attr: Option<ApiTrackAttr>,
 }
 
+/// A named entity with an optional MBID.
+///
+/// Plain responses send `{"#text": ..., "mbid": ...}`; `extended=1` responses send the
+/// artist as `{"name": ..., "mbid": ...}` instead.
 #[derive(Deserialize)]
 pub struct ApiTextField {
-    #[serde(rename = "#text")]
+    #[serde(rename = "#text", alias = "name")]
     pub text: String,
+    #[serde(default)]
+    pub mbid: String,
+}
+
+#[derive(Deserialize)]
+pub struct ApiImage {
+    #[serde(default)]
+    pub size: String,
+    #[serde(rename = "#text", default)]
+    pub url: String,
 }
 
 #[derive(Deserialize)]
@@ -279,17 +1605,7 @@ pub struct ApiPaginationAttr {
 }
 
 pub fn parse_api_recent_tracks_response(json: &str) -> Result<TrackPage> {
-    let response: ApiRecentTracksResponse = serde_json::from_str(json).map_err(|e| {
-        // Prefer surfacing the API's own error message when the body is an error payload.
-        if let Ok(api_error) = serde_json::from_str::<ApiErrorResponse>(json) {
-            crate::types::LastFmError::Http(format!(
-                "last.fm API error {}: {}",
-                api_error.error, api_error.message
-            ))
-        } else {
-            crate::types::LastFmError::Parse(e.to_string())
-        }
-    })?;
+    let response: ApiRecentTracksResponse = parse_api_body(json)?;
 
     let current_page: u32 = response.recenttracks.attr.page.parse().unwrap_or(1);
     let total_pages: u32 = response.recenttracks.attr.total_pages.parse().unwrap_or(1);
@@ -309,6 +1625,11 @@ pub fn parse_api_recent_tracks_response(json: &str) -> Result<TrackPage> {
         })
         .filter_map(|t| {
             let timestamp: u64 = t.date.as_ref()?.uts.parse().ok()?;
+            let image_url = t
+                .image
+                .into_iter()
+                .rev()
+                .find_map(|image| non_empty(image.url));
             Some(Track {
                 name: t.name,
                 artist: t.artist.text,
@@ -319,6 +1640,11 @@ pub fn parse_api_recent_tracks_response(json: &str) -> Result<TrackPage> {
                 // honestly instead of guessing. Scraped edit-form values are the authoritative
                 // way to obtain it.
                 album_artist: None,
+                loved: t.loved.map(|loved| loved == "1"),
+                track_mbid: non_empty(t.mbid),
+                artist_mbid: non_empty(t.artist.mbid),
+                album_mbid: non_empty(t.album.mbid),
+                image_url,
             })
         })
         .collect();
@@ -376,6 +1702,47 @@ mod tests {
         assert_eq!(page.total_pages, Some(5));
     }
 
+    #[test]
+    fn test_parse_extended_track_fields() {
+        let json = r##"{
+            "recenttracks": {
+                "track": [
+                    {
+                        "name": "Roygbiv",
+                        "mbid": "8b4b0f6a-0d0c-4b9e-9d3b-5b0f6c1b1e7a",
+                        "loved": "1",
+                        "artist": {"name": "Boards of Canada", "mbid": "69158f97-4c07-4c4e-baf8-4e4ab1ed666e"},
+                        "album": {"#text": "Music Has the Right to Children", "mbid": ""},
+                        "image": [
+                            {"size": "small", "#text": "https://img/34s/cover.jpg"},
+                            {"size": "extralarge", "#text": "https://img/300x300/cover.jpg"}
+                        ],
+                        "date": {"uts": "1700000000"}
+                    }
+                ],
+                "@attr": {"page": "1", "totalPages": "1"}
+            }
+        }"##;
+
+        let track = &parse_api_recent_tracks_response(json).unwrap().tracks[0];
+        assert_eq!(track.artist, "Boards of Canada");
+        assert_eq!(track.loved, Some(true));
+        assert_eq!(
+            track.track_mbid.as_deref(),
+            Some("8b4b0f6a-0d0c-4b9e-9d3b-5b0f6c1b1e7a")
+        );
+        assert_eq!(
+            track.artist_mbid.as_deref(),
+            Some("69158f97-4c07-4c4e-baf8-4e4ab1ed666e")
+        );
+        // Unknown MBIDs arrive as empty strings.
+        assert_eq!(track.album_mbid, None);
+        assert_eq!(
+            track.image_url.as_deref(),
+            Some("https://img/300x300/cover.jpg")
+        );
+    }
+
     #[test]
     fn test_parse_single_track_as_object() {
         // The API returns a bare object (not a one-element array) for single-track pages.
@@ -437,12 +1804,49 @@ mod tests {
         assert_eq!(page.page_number, 3);
     }
 
+    #[test]
+    fn test_sign_api_params_sorts_and_skips_format() {
+        let params = vec![
+            ("username".to_string(), "alice".to_string()),
+            ("password".to_string(), "hunter2".to_string()),
+            ("method".to_string(), "auth.getMobileSession".to_string()),
+            ("api_key".to_string(), "xxx".to_string()),
+            ("format".to_string(), "json".to_string()),
+        ];
+        assert_eq!(
+            sign_api_params(&params, "secret"),
+            "043a294e5d145c30dde0c16adec8e6f3"
+        );
+    }
+
+    #[test]
+    fn test_parse_scrobble_response_single_ignored_object() {
+        // Single-scrobble responses use a bare object and quoted counts.
+        let json = r##"{
+            "scrobbles": {
+                "scrobble": {
+                    "artist": {"corrected": "0", "#text": "Artist"},
+                    "track": {"corrected": "0", "#text": "Track"},
+                    "ignoredMessage": {"code": "3", "#text": "Timestamp too old"}
+                },
+                "@attr": {"accepted": "0", "ignored": "1"}
+            }
+        }"##;
+        let batch = [ApiScrobble::new("Artist", "Track", 1)];
+        let result = parse_scrobble_response(json, &batch).unwrap();
+        assert_eq!(result.accepted, 0);
+        assert_eq!(result.ignored.len(), 1);
+        assert_eq!(result.ignored[0].code, 3);
+        assert_eq!(result.ignored[0].message, "Timestamp too old");
+        assert_eq!(result.ignored[0].scrobble, batch[0]);
+    }
+
     #[test]
     fn test_build_recent_tracks_url_without_range() {
         let url = build_recent_tracks_url("someuser", "apikey123", 2, None, None);
         assert_eq!(
             url,
-            "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user=someuser&api_key=apikey123&format=json&page=2&limit=200"
+            "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user=someuser&api_key=apikey123&format=json&extended=1&page=2&limit=200"
         );
         assert!(!url.contains("&from="));
         assert!(!url.contains("&to="));
@@ -459,7 +1863,7 @@ mod tests {
         );
         assert_eq!(
             url,
-            "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user=someuser&api_key=apikey123&format=json&page=1&limit=200&from=1700000000&to=1700086400"
+            "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user=someuser&api_key=apikey123&format=json&extended=1&page=1&limit=200&from=1700000000&to=1700086400"
         );
     }
 
@@ -482,4 +1886,158 @@ mod tests {
         let url = build_recent_tracks_url("some user&x", "key", 1, None, None);
         assert!(url.contains("user=some%20user%26x"));
     }
+
+    #[test]
+    fn test_build_api_url_encodes_params() {
+        let url = build_api_url(
+            "track.getInfo",
+            "key",
+            &[
+                ("artist", "AC/DC".to_string()),
+                ("track", "T.N.T.".to_string()),
+            ],
+        );
+        assert_eq!(
+            url,
+            "https://ws.audioscrobbler.com/2.0/?method=track.getInfo&api_key=key&format=json&artist=AC%2FDC&track=T.N.T."
+        );
+    }
+
+    #[test]
+    fn test_parse_user_info() {
+        let json = r##"{
+            "user": {
+                "name": "RJ",
+                "realname": "",
+                "subscriber": "1",
+                "playcount": "150316",
+                "artist_count": "12749",
+                "track_count": "57310",
+                "album_count": "26520",
+                "registered": {"unixtime": "1037793040", "#text": 1037793040},
+                "country": "None",
+                "url": "https://www.last.fm/user/RJ"
+            }
+        }"##;
+        let info = parse_user_info_response(json).unwrap();
+        assert_eq!(info.name, "RJ");
+        assert_eq!(info.real_name, None);
+        assert_eq!(info.country, None);
+        assert_eq!(info.playcount, 150316);
+        assert_eq!(info.track_count, Some(57310));
+        assert_eq!(info.registered, Some(1037793040));
+        assert!(info.subscriber);
+    }
+
+    #[test]
+    fn test_parse_top_tracks_single_object() {
+        let json = r##"{
+            "toptracks": {
+                "track": {
+                    "name": "Creep",
+                    "mbid": "",
+                    "artist": {"name": "Radiohead", "mbid": "a74b1b7f-71a5-4011-9441-d0b5e4122711"},
+                    "duration": "0",
+                    "playcount": "55",
+                    "@attr": {"rank": "1"}
+                },
+                "@attr": {"page": "1", "totalPages": "2", "perPage": "1", "total": "2"}
+            }
+        }"##;
+        let page = parse_top_tracks_response(json).unwrap();
+        assert_eq!(
+            page.items,
+            vec![ApiTopTrack {
+                rank: 1,
+                name: "Creep".to_string(),
+                artist: "Radiohead".to_string(),
+                mbid: None,
+                playcount: 55,
+                duration: None,
+            }]
+        );
+        assert!(page.has_next_page);
+        assert_eq!(page.total_pages, Some(2));
+    }
+
+    #[test]
+    fn test_parse_track_info() {
+        let json = r##"{
+            "track": {
+                "name": "Believe",
+                "mbid": "32ca187e-ee25-4f18-b7d0-3b6713f24635",
+                "duration": "240000",
+                "listeners": "1234",
+                "playcount": "5678",
+                "artist": {"name": "Cher", "mbid": "bfcc6d75-a6a5-4bc6-8282-47aec8531818"},
+                "album": {"artist": "Cher", "title": "Believe", "mbid": ""},
+                "userplaycount": "5",
+                "userloved": "1",
+                "toptags": {"tag": [{"name": "pop"}, {"name": "dance"}]}
+            }
+        }"##;
+        let info = parse_track_info_response(json).unwrap();
+        assert_eq!(info.artist, "Cher");
+        assert_eq!(
+            info.artist_mbid.as_deref(),
+            Some("bfcc6d75-a6a5-4bc6-8282-47aec8531818")
+        );
+        assert_eq!(info.album.as_deref(), Some("Believe"));
+        assert_eq!(info.album_mbid, None);
+        assert_eq!(info.duration_ms, Some(240000));
+        assert_eq!(info.user_playcount, Some(5));
+        assert_eq!(info.user_loved, Some(true));
+        assert_eq!(info.tags, vec!["pop", "dance"]);
+    }
+
+    #[test]
+    fn test_parse_album_info_with_empty_tags() {
+        let json = r##"{
+            "album": {
+                "name": "Believe",
+                "artist": "Cher",
+                "listeners": "10",
+                "playcount": "20",
+                "tags": "",
+                "tracks": {
+                    "track": {
+                        "name": "Believe",
+                        "duration": 239,
+                        "artist": {"name": "Cher"},
+                        "@attr": {"rank": 1}
+                    }
+                }
+            }
+        }"##;
+        let info = parse_album_info_response(json).unwrap();
+        assert!(info.tags.is_empty());
+        assert_eq!(info.user_playcount, None);
+        assert_eq!(info.tracks.len(), 1);
+        assert_eq!(info.tracks[0].rank, Some(1));
+        assert_eq!(info.tracks[0].duration, Some(239));
+    }
+
+    #[test]
+    fn test_parse_artist_correction() {
+        let json = r##"{
+            "corrections": {
+                "correction": {
+                    "artist": {"name": "Guns N' Roses", "mbid": "eeb1195b-f213-4ce1-b28c-8565211f8e43"},
+                    "@attr": {"index": "0"}
+                }
+            }
+        }"##;
+        let correction = parse_artist_correction_response(json).unwrap().unwrap();
+        assert_eq!(correction.name, "Guns N' Roses");
+
+        let none = r##"{"corrections": "\n            "}"##;
+        assert_eq!(parse_artist_correction_response(none).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_api_error_body_is_surfaced() {
+        let json = r##"{"error":6,"message":"The artist you supplied could not be found"}"##;
+        let err = parse_artist_info_response(json).unwrap_err();
+        assert!(err.to_string().contains("could not be found"), "{err}");
+    }
 }
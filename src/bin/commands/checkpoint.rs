@@ -0,0 +1,72 @@
+use lastfm_edit::{AsyncPaginatedIterator, IteratorCursor};
+use std::path::PathBuf;
+
+type Iter<T> = Box<dyn AsyncPaginatedIterator<T>>;
+
+/// The `--resume` file of a list command, holding the cursor of an unfinished listing
+#[derive(Default)]
+pub struct Checkpoint {
+    path: Option<PathBuf>,
+}
+
+impl Checkpoint {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+
+    /// Continue `iterator` from the saved cursor, if there is one
+    ///
+    /// A checkpoint written by a different listing (another command or other arguments)
+    /// is an error rather than being silently ignored or overwritten.
+    pub fn resume<T>(
+        &self,
+        iterator: Iter<T>,
+        rebuild: impl FnOnce(&IteratorCursor) -> lastfm_edit::Result<Iter<T>>,
+    ) -> Result<Iter<T>, Box<dyn std::error::Error>> {
+        let Some(path) = self.path.as_ref().filter(|path| path.exists()) else {
+            return Ok(iterator);
+        };
+
+        let cursor: IteratorCursor = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if iterator.cursor().map(|fresh| fresh.kind) != Some(cursor.kind.clone()) {
+            return Err(format!(
+                "{} is a checkpoint of a different listing ({:?})",
+                path.display(),
+                cursor.kind
+            )
+            .into());
+        }
+
+        log::info!(
+            "Resuming from {} at page {}, item {}",
+            path.display(),
+            cursor.page,
+            cursor.offset + 1
+        );
+        Ok(rebuild(&cursor)?)
+    }
+
+    /// Record the position after the item just output
+    pub fn save(&self, cursor: Option<IteratorCursor>) -> Result<(), Box<dyn std::error::Error>> {
+        let (Some(path), Some(cursor)) = (&self.path, cursor) else {
+            return Ok(());
+        };
+
+        // Write next to the checkpoint and rename, so an interrupted write never
+        // leaves a truncated file behind.
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, serde_json::to_string(&cursor)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Remove the checkpoint once the listing has been output completely
+    pub fn finish(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(path) = self.path.as_ref().filter(|path| path.exists()) {
+            std::fs::remove_file(path)?;
+            log::info!("Listing complete; removed checkpoint {}", path.display());
+        }
+        Ok(())
+    }
+}
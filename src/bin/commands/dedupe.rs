@@ -0,0 +1,114 @@
+use super::delete::handle_delete_manifest;
+use lastfm_edit::delete_manifest::{write_manifest, DeleteManifestSource};
+use lastfm_edit::duplicates::{duplicate_targets, scan_recent_pages, DuplicateFinderConfig};
+use lastfm_edit::LastFmEditClientImpl;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Events emitted by the dedupe command (JSON output to stdout)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DedupeEvent {
+    /// A play that was submitted more than once
+    DuplicateFound {
+        artist: String,
+        track: String,
+        album: Option<String>,
+        kept_offset: Option<u64>,
+        kept_timestamp: u64,
+        duplicate_offsets: Vec<Option<u64>>,
+        duplicate_timestamps: Vec<u64>,
+    },
+    /// Summary of the scan, before any deletion
+    Summary {
+        groups: usize,
+        duplicates: usize,
+        manifest: Option<String>,
+        dry_run: bool,
+    },
+}
+
+/// Output a dedupe event as JSON to stdout
+fn output_event(event: &DedupeEvent) {
+    if let Ok(json) = serde_json::to_string(event) {
+        println!("{json}");
+    } else {
+        log::error!("Failed to serialize event to JSON");
+    }
+}
+
+pub async fn handle_dedupe(
+    client: &LastFmEditClientImpl,
+    recent_pages: u32,
+    window_secs: u64,
+    manifest_output: Option<&Path>,
+    dry_run: bool,
+    delete_delay_ms: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Scanning {recent_pages} page(s) of recent scrobbles for duplicates");
+    let config = DuplicateFinderConfig { window_secs };
+    let groups = scan_recent_pages(client, recent_pages, &config).await?;
+
+    for group in &groups {
+        output_event(&DedupeEvent::DuplicateFound {
+            artist: group.kept.artist.clone(),
+            track: group.kept.track.clone(),
+            album: group.kept.album.clone(),
+            kept_offset: group.kept.offset,
+            kept_timestamp: group.kept.timestamp,
+            duplicate_offsets: group.duplicates.iter().map(|d| d.offset).collect(),
+            duplicate_timestamps: group.duplicates.iter().map(|d| d.timestamp).collect(),
+        });
+    }
+
+    let targets = duplicate_targets(&groups);
+    if targets.is_empty() {
+        log::info!("No duplicate scrobbles found");
+        output_event(&DedupeEvent::Summary {
+            groups: 0,
+            duplicates: 0,
+            manifest: None,
+            dry_run,
+        });
+        return Ok(());
+    }
+
+    // Always write a manifest before deleting so the run can be audited and restored.
+    let manifest_path = match manifest_output {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            PathBuf::from(format!("dedupe-{now}.json"))
+        }
+    };
+    write_manifest(
+        &manifest_path,
+        DeleteManifestSource {
+            kind: "dedupe".to_string(),
+            range: Some(format!("1-{recent_pages}")),
+        },
+        &targets,
+    )?;
+
+    output_event(&DedupeEvent::Summary {
+        groups: groups.len(),
+        duplicates: targets.len(),
+        manifest: Some(manifest_path.display().to_string()),
+        dry_run,
+    });
+
+    if dry_run {
+        log::info!("DRY RUN - No duplicates deleted");
+        log::info!(
+            "Review '{}' and use --apply to delete the duplicates",
+            manifest_path.display()
+        );
+        return Ok(());
+    }
+
+    handle_delete_manifest(client, &manifest_path, false, delete_delay_ms).await
+}
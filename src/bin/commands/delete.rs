@@ -1,7 +1,7 @@
 use super::utils::parse_range;
 use lastfm_edit::delete_manifest::{
-    execute_delete_targets, read_manifest, target_from_track, write_manifest, DeleteManifestSource,
-    DeleteTarget,
+    execute_delete_targets, read_manifest, save_manifest, target_from_track, write_manifest,
+    DeleteAttemptResult, DeleteManifestSource, DeleteTarget,
 };
 use lastfm_edit::{LastFmEditClientImpl, Track};
 use serde::{Deserialize, Serialize};
@@ -50,7 +50,7 @@ fn output_event(event: &DeleteEvent) {
 }
 
 /// Utility function to ask for user confirmation (goes to stderr)
-fn ask_for_confirmation(message: &str) -> Result<bool, Box<dyn std::error::Error>> {
+pub fn ask_for_confirmation(message: &str) -> Result<bool, Box<dyn std::error::Error>> {
     eprint!("{message} (y/N): ");
     io::stderr().flush()?;
 
@@ -312,7 +312,7 @@ pub async fn handle_delete_manifest(
     dry_run: bool,
     delete_delay_ms: u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let manifest = read_manifest(manifest_path)?;
+    let mut manifest = read_manifest(manifest_path)?;
     let scrobbles_to_delete = manifest.targets();
 
     for (i, scrobble) in scrobbles_to_delete.iter().enumerate() {
@@ -325,7 +325,24 @@ pub async fn handle_delete_manifest(
         });
     }
 
-    execute_deletions(client, scrobbles_to_delete, dry_run, delete_delay_ms).await
+    // Record each outcome as it happens so `lastfm-edit restore` knows exactly what was
+    // removed, even if this run is interrupted.
+    execute_deletions(
+        client,
+        scrobbles_to_delete,
+        dry_run,
+        delete_delay_ms,
+        |index, result| {
+            manifest.record_delete_result(index, result.clone());
+            if let Err(e) = save_manifest(manifest_path, &manifest) {
+                log::warn!(
+                    "Failed to record delete result in '{}': {e}",
+                    manifest_path.display()
+                );
+            }
+        },
+    )
+    .await
 }
 
 async fn handle_collected_scrobbles(
@@ -347,15 +364,18 @@ async fn handle_collected_scrobbles(
         return Ok(());
     }
 
-    execute_deletions(client, scrobbles, dry_run, delete_delay_ms).await
+    execute_deletions(client, scrobbles, dry_run, delete_delay_ms, |_, _| {}).await
 }
 
 /// Common deletion execution logic
+///
+/// `on_result` receives the 1-based index and outcome of every attempted deletion.
 async fn execute_deletions(
     client: &LastFmEditClientImpl,
     scrobbles: Vec<DeleteTarget>,
     dry_run: bool,
     delete_delay_ms: u64,
+    mut on_result: impl FnMut(usize, &DeleteAttemptResult),
 ) -> Result<(), Box<dyn std::error::Error>> {
     if scrobbles.is_empty() {
         log::info!("No scrobbles with timestamps found to delete");
@@ -428,6 +448,7 @@ async fn execute_deletions(
                 success: result.success(),
                 message: result.message().map(str::to_string),
             });
+            on_result(index, result);
         },
     )
     .await?;
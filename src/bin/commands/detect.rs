@@ -0,0 +1,91 @@
+use lastfm_edit::fake_scrobbles::{
+    scan_recent_tracks, write_blocks_manifest, FakeScrobbleDetectorConfig, SuspicionReason,
+};
+use lastfm_edit::LastFmEditClientImpl;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Events emitted by the detect-fakes command (JSON output to stdout)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DetectEvent {
+    /// A block of recent scrobbles that looks like fake playback
+    SuspiciousBlock {
+        rank: usize,
+        /// Offset range in `delete --recent-offset` syntax
+        offsets: String,
+        plays: usize,
+        score: usize,
+        first: String,
+        last: String,
+        reasons: Vec<SuspicionReason>,
+    },
+    /// Summary of the scan
+    Summary {
+        blocks: usize,
+        flagged_plays: usize,
+        manifest: Option<String>,
+    },
+}
+
+/// Output a detect event as JSON to stdout
+fn output_event(event: &DetectEvent) {
+    if let Ok(json) = serde_json::to_string(event) {
+        println!("{json}");
+    } else {
+        log::error!("Failed to serialize event to JSON");
+    }
+}
+
+pub async fn handle_detect_fakes(
+    client: &LastFmEditClientImpl,
+    scan: usize,
+    utc_offset_minutes: i64,
+    manifest_output: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config =
+        FakeScrobbleDetectorConfig::default().with_utc_offset_secs(utc_offset_minutes * 60);
+    let blocks = scan_recent_tracks(client, scan, &config).await?;
+
+    for (i, block) in blocks.iter().enumerate() {
+        let describe = |offset: usize| {
+            block
+                .plays
+                .get(offset)
+                .map(|p| format!("'{}' by '{}'", p.track, p.artist))
+                .unwrap_or_default()
+        };
+        output_event(&DetectEvent::SuspiciousBlock {
+            rank: i + 1,
+            offsets: block.offset_range(),
+            plays: block.plays.len(),
+            score: block.score,
+            first: describe(0),
+            last: describe(block.plays.len().saturating_sub(1)),
+            reasons: block.reasons.clone(),
+        });
+    }
+
+    if let Some(path) = manifest_output {
+        if blocks.is_empty() {
+            log::info!("Nothing flagged; no manifest written");
+        } else {
+            write_blocks_manifest(path, &blocks)?;
+            log::info!(
+                "Review '{}', then run: lastfm-edit delete --manifest {} --apply",
+                path.display(),
+                path.display()
+            );
+        }
+    }
+
+    output_event(&DetectEvent::Summary {
+        blocks: blocks.len(),
+        flagged_plays: blocks.iter().map(|b| b.plays.len()).sum(),
+        manifest: manifest_output
+            .filter(|_| !blocks.is_empty())
+            .map(|p| p.display().to_string()),
+    });
+
+    Ok(())
+}
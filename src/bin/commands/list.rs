@@ -1,14 +1,21 @@
+use super::checkpoint::Checkpoint;
 use super::list_output::{log_started, log_summary, output_event, ListEvent};
-use lastfm_edit::{LastFmEditClient, LastFmEditClientImpl};
+use lastfm_edit::{
+    AsyncPaginatedIterator, LastFmEditClient, LastFmEditClientImpl, LibraryDateRange, LibrarySort,
+};
 
 /// Handle the list artists command
 pub async fn handle_list_artists(
     client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
     limit: usize,
+    range: LibraryDateRange,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log_started("artists", None, None);
 
-    let mut artists_iterator = client.artists();
+    let mut artists_iterator = checkpoint.resume(client.artists_in_range(range), |cursor| {
+        cursor.artist_iterator(client.clone())
+    })?;
     let mut count = 0;
 
     while let Some(artist) = artists_iterator.next().await? {
@@ -18,12 +25,17 @@ pub async fn handle_list_artists(
             index: count,
             artist,
         });
+        checkpoint.save(artists_iterator.cursor())?;
 
         if limit > 0 && count >= limit {
             break;
         }
     }
 
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
     log_summary("artists", count, None);
 
     Ok(())
@@ -32,12 +44,17 @@ pub async fn handle_list_artists(
 /// Handle the list albums command
 pub async fn handle_list_albums(
     client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
     artist: &str,
     limit: usize,
+    range: LibraryDateRange,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log_started("albums", Some(artist), None);
 
-    let mut albums_iterator = client.artist_albums(artist);
+    let mut albums_iterator = checkpoint
+        .resume(client.artist_albums_in_range(artist, range), |cursor| {
+            cursor.album_iterator(client.clone())
+        })?;
     let mut count = 0;
 
     while let Some(album) = albums_iterator.next().await? {
@@ -47,12 +64,17 @@ pub async fn handle_list_albums(
             index: count,
             album,
         });
+        checkpoint.save(albums_iterator.cursor())?;
 
         if limit > 0 && count >= limit {
             break;
         }
     }
 
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
     log_summary("albums", count, Some(artist));
 
     Ok(())
@@ -61,12 +83,15 @@ pub async fn handle_list_albums(
 /// Handle the list tracks by album command
 pub async fn handle_list_tracks_by_album(
     client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
     artist: &str,
     limit: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log_started("tracks-by-album", Some(artist), None);
 
-    let mut albums_iterator = client.artist_albums(artist);
+    let mut albums_iterator = checkpoint.resume(client.artist_albums(artist), |cursor| {
+        cursor.album_iterator(client.clone())
+    })?;
     let mut album_count = 0;
 
     while let Some(album) = albums_iterator.next().await? {
@@ -98,6 +123,8 @@ pub async fn handle_list_tracks_by_album(
             }
         }
 
+        checkpoint.save(albums_iterator.cursor())?;
+
         if track_idx == 0 {
             log::warn!("No tracks found in your library for album '{}'", album.name);
         }
@@ -107,6 +134,10 @@ pub async fn handle_list_tracks_by_album(
         }
     }
 
+    if limit == 0 || album_count < limit {
+        checkpoint.finish()?;
+    }
+
     log_summary("tracks-by-album", album_count, Some(artist));
 
     Ok(())
@@ -115,12 +146,15 @@ pub async fn handle_list_tracks_by_album(
 /// Handle the list tracks command
 pub async fn handle_list_tracks(
     client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
     artist: &str,
     limit: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log_started("tracks", Some(artist), None);
 
-    let mut tracks_iterator = client.artist_tracks(artist);
+    let mut tracks_iterator = checkpoint.resume(client.artist_tracks(artist), |cursor| {
+        cursor.track_iterator(client.clone())
+    })?;
     let mut count = 0;
 
     while let Some(track) = tracks_iterator.next().await? {
@@ -130,12 +164,17 @@ pub async fn handle_list_tracks(
             index: count,
             track,
         });
+        checkpoint.save(tracks_iterator.cursor())?;
 
         if limit > 0 && count >= limit {
             break;
         }
     }
 
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
     log_summary("tracks", count, Some(artist));
 
     Ok(())
@@ -144,12 +183,17 @@ pub async fn handle_list_tracks(
 /// Handle the list tracks direct command
 pub async fn handle_list_tracks_direct(
     client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
     artist: &str,
     limit: usize,
+    range: LibraryDateRange,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log_started("tracks-direct", Some(artist), None);
 
-    let mut tracks_iterator = client.artist_tracks_direct(artist);
+    let mut tracks_iterator = checkpoint
+        .resume(client.artist_tracks_in_range(artist, range), |cursor| {
+            cursor.track_iterator(client.clone())
+        })?;
     let mut count = 0;
 
     while let Some(track) = tracks_iterator.next().await? {
@@ -159,12 +203,17 @@ pub async fn handle_list_tracks_direct(
             index: count,
             track,
         });
+        checkpoint.save(tracks_iterator.cursor())?;
 
         if limit > 0 && count >= limit {
             break;
         }
     }
 
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
     log_summary("tracks-direct", count, Some(artist));
 
     Ok(())
@@ -173,12 +222,15 @@ pub async fn handle_list_tracks_direct(
 /// Handle the list album tracks command
 pub async fn handle_list_album_tracks(
     client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
     album: &str,
     artist: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log_started("album-tracks", Some(artist), Some(album));
 
-    let mut tracks_iterator = client.album_tracks(album, artist);
+    let mut tracks_iterator = checkpoint.resume(client.album_tracks(album, artist), |cursor| {
+        cursor.track_iterator(client.clone())
+    })?;
     let mut count = 0;
 
     while let Some(track) = tracks_iterator.next().await? {
@@ -188,9 +240,175 @@ pub async fn handle_list_album_tracks(
             index: count,
             track,
         });
+        checkpoint.save(tracks_iterator.cursor())?;
     }
 
+    checkpoint.finish()?;
+
     log_summary("album-tracks", count, Some(artist));
 
     Ok(())
 }
+
+/// Handle the list library-tracks command
+pub async fn handle_list_library_tracks(
+    client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
+    limit: usize,
+    range: LibraryDateRange,
+    sort: LibrarySort,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Listing tracks in your library ({range})");
+
+    let mut tracks_iterator = checkpoint.resume(client.library_tracks(range, sort), |cursor| {
+        cursor.track_iterator(client.clone())
+    })?;
+    let mut count = 0;
+
+    while let Some(track) = tracks_iterator.next().await? {
+        count += 1;
+
+        output_event(&ListEvent::TrackFound {
+            index: count,
+            track,
+        });
+        checkpoint.save(tracks_iterator.cursor())?;
+
+        if limit > 0 && count >= limit {
+            break;
+        }
+    }
+
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
+    log::info!(
+        "Found {count} track{} in your library",
+        if count == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+/// Handle the list library-albums command
+pub async fn handle_list_library_albums(
+    client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
+    limit: usize,
+    range: LibraryDateRange,
+    sort: LibrarySort,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Listing albums in your library ({range})");
+
+    let mut albums_iterator = checkpoint.resume(client.library_albums(range, sort), |cursor| {
+        cursor.album_iterator(client.clone())
+    })?;
+    let mut count = 0;
+
+    while let Some(album) = albums_iterator.next().await? {
+        count += 1;
+
+        output_event(&ListEvent::AlbumFound {
+            index: count,
+            album,
+        });
+        checkpoint.save(albums_iterator.cursor())?;
+
+        if limit > 0 && count >= limit {
+            break;
+        }
+    }
+
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
+    log::info!(
+        "Found {count} album{} in your library",
+        if count == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+/// Handle the list scrobbles command
+pub async fn handle_list_scrobbles(
+    client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
+    artist: &str,
+    track: &str,
+    limit: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Listing scrobbles of '{track}' by '{artist}'");
+
+    let mut scrobbles_iterator = checkpoint
+        .resume(client.track_scrobbles(artist, track), |cursor| {
+            cursor.track_iterator(client.clone())
+        })?;
+    let mut count = 0;
+
+    while let Some(scrobble) = scrobbles_iterator.next().await? {
+        count += 1;
+
+        output_event(&ListEvent::TrackFound {
+            index: count,
+            track: scrobble,
+        });
+        checkpoint.save(scrobbles_iterator.cursor())?;
+
+        if limit > 0 && count >= limit {
+            break;
+        }
+    }
+
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
+    log::info!(
+        "Found {count} scrobble{} of '{track}' by '{artist}'",
+        if count == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+/// Handle the list loved command
+pub async fn handle_list_loved(
+    client: &LastFmEditClientImpl,
+    checkpoint: &Checkpoint,
+    limit: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Listing your loved tracks");
+
+    let mut loved_iterator = checkpoint.resume(client.loved_tracks(), |cursor| {
+        cursor.track_iterator(client.clone())
+    })?;
+    let mut count = 0;
+
+    while let Some(track) = loved_iterator.next().await? {
+        count += 1;
+
+        output_event(&ListEvent::TrackFound {
+            index: count,
+            track,
+        });
+        checkpoint.save(loved_iterator.cursor())?;
+
+        if limit > 0 && count >= limit {
+            break;
+        }
+    }
+
+    if limit == 0 || count < limit {
+        checkpoint.finish()?;
+    }
+
+    log::info!(
+        "Found {count} loved track{}",
+        if count == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
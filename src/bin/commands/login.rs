@@ -0,0 +1,37 @@
+use lastfm_edit::SessionManager;
+use std::path::Path;
+
+/// Handle importing and saving the session of a logged-in browser profile
+#[cfg(feature = "browser-import")]
+pub async fn handle_login_from_browser(
+    manager: &SessionManager,
+    profile: &Path,
+    username: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let http_client = http_client::native::NativeClient::new();
+
+    log::info!("Importing session from {}", profile.display());
+    lastfm_edit::browser_session::import_browser_session(
+        Box::new(http_client),
+        profile,
+        username,
+        manager,
+    )
+    .await?;
+
+    log::info!(
+        "Logged in as {username}; session saved to {}",
+        manager.get_session_path(username)?.display()
+    );
+    Ok(())
+}
+
+/// Handle importing and saving the session of a logged-in browser profile
+#[cfg(not(feature = "browser-import"))]
+pub async fn handle_login_from_browser(
+    _manager: &SessionManager,
+    _profile: &Path,
+    _username: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err("lastfm-edit was built without the browser-import feature".into())
+}
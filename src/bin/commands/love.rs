@@ -0,0 +1,52 @@
+use lastfm_edit::LastFmEditClientImpl;
+use serde::{Deserialize, Serialize};
+
+/// Events emitted by the love command (JSON output to stdout)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum LoveEvent {
+    /// A track was loved or unloved
+    LoveChanged {
+        artist: String,
+        track: String,
+        loved: bool,
+        success: bool,
+    },
+}
+
+/// Output a love event as JSON to stdout
+fn output_event(event: &LoveEvent) {
+    if let Ok(json) = serde_json::to_string(event) {
+        println!("{json}");
+    } else {
+        log::error!("Failed to serialize event to JSON");
+    }
+}
+
+/// Handle loving or unloving a track
+pub async fn handle_love(
+    client: &LastFmEditClientImpl,
+    artist: &str,
+    track: &str,
+    loved: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let success = if loved {
+        log::info!("Loving '{track}' by '{artist}'");
+        client.love_track(artist, track).await?
+    } else {
+        log::info!("Unloving '{track}' by '{artist}'");
+        client.unlove_track(artist, track).await?
+    };
+
+    output_event(&LoveEvent::LoveChanged {
+        artist: artist.to_string(),
+        track: track.to_string(),
+        loved,
+        success,
+    });
+
+    if !success {
+        return Err(format!("Last.fm did not accept the change to '{track}' by '{artist}'").into());
+    }
+    Ok(())
+}
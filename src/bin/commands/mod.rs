@@ -1,15 +1,23 @@
+pub mod checkpoint;
+pub mod dedupe;
 pub mod delete;
+pub mod detect;
 pub mod edit;
 pub mod list;
 pub mod list_output;
+pub mod login;
+pub mod love;
+pub mod restore;
 pub mod search;
 pub mod search_output;
+pub mod selfcheck;
 pub mod show;
 pub mod show_output;
 pub mod utils;
 
-use clap::{Subcommand, ValueEnum};
-use lastfm_edit::LastFmEditClientImpl;
+use checkpoint::Checkpoint;
+use clap::{Args, Subcommand, ValueEnum};
+use lastfm_edit::{LastFmEditClientImpl, LibraryDateRange, LibrarySort};
 use std::path::PathBuf;
 
 #[derive(ValueEnum, Clone)]
@@ -22,6 +30,48 @@ pub enum SearchType {
     Artists,
 }
 
+#[derive(ValueEnum, Clone, Copy, Default)]
+pub enum SortOrder {
+    /// Most scrobbled first
+    #[default]
+    Scrobbles,
+    /// Alphabetical by name
+    Name,
+}
+
+impl From<SortOrder> for LibrarySort {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Scrobbles => LibrarySort::Scrobbles,
+            SortOrder::Name => LibrarySort::Name,
+        }
+    }
+}
+
+/// Date window for the library listings (`--from`/`--to`)
+#[derive(Args, Clone, Default)]
+pub struct DateRangeArgs {
+    /// Only count scrobbles on or after this day (YYYY-MM-DD)
+    #[arg(long)]
+    pub from: Option<chrono::NaiveDate>,
+
+    /// Only count scrobbles on or before this day (YYYY-MM-DD, defaults to today)
+    #[arg(long, requires = "from")]
+    pub to: Option<chrono::NaiveDate>,
+}
+
+impl DateRangeArgs {
+    pub fn range(&self) -> LibraryDateRange {
+        match self.from {
+            Some(from) => LibraryDateRange::between(
+                from,
+                self.to.unwrap_or_else(|| chrono::Utc::now().date_naive()),
+            ),
+            None => LibraryDateRange::AllTime,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum ListCommands {
     /// List all artists in your library
@@ -35,10 +85,16 @@ pub enum ListCommands {
     ///
     /// # List first 20 artists
     /// lastfm-edit list artists --limit 20
+    ///
+    /// # Artists you scrobbled in 2019, by their 2019 play count
+    /// lastfm-edit list artists --from 2019-01-01 --to 2019-12-31
     Artists {
         /// Maximum number of artists to show (0 for no limit)
         #[arg(long, default_value = "0")]
         limit: usize,
+
+        #[command(flatten)]
+        dates: DateRangeArgs,
     },
 
     /// List albums for an artist
@@ -52,6 +108,9 @@ pub enum ListCommands {
     ///
     /// # List first 10 albums
     /// lastfm-edit list albums "Radiohead" --limit 10
+    ///
+    /// # Albums scrobbled since March 2024
+    /// lastfm-edit list albums "Radiohead" --from 2024-03-01
     Albums {
         /// Artist name
         artist: String,
@@ -59,6 +118,9 @@ pub enum ListCommands {
         /// Maximum number of albums to show (0 for no limit)
         #[arg(long, default_value = "0")]
         limit: usize,
+
+        #[command(flatten)]
+        dates: DateRangeArgs,
     },
 
     /// List all tracks for an artist with album information (album-based iteration)
@@ -96,6 +158,9 @@ pub enum ListCommands {
     ///
     /// # Compare with regular tracks command to find missing tracks
     /// lastfm-edit list tracks-direct "The Beatles" --limit 20
+    ///
+    /// # Tracks scrobbled in 2019
+    /// lastfm-edit list tracks-direct "The Beatles" --from 2019-01-01 --to 2019-12-31
     TracksDirect {
         /// Artist name
         artist: String,
@@ -103,6 +168,9 @@ pub enum ListCommands {
         /// Maximum number of tracks to show (0 for no limit)
         #[arg(long, default_value = "0")]
         limit: usize,
+
+        #[command(flatten)]
+        dates: DateRangeArgs,
     },
 
     /// List tracks organized by album for an artist
@@ -140,6 +208,91 @@ pub enum ListCommands {
         /// Artist name
         artist: String,
     },
+
+    /// List every track in your library, across all artists
+    ///
+    /// This command walks the whole-library track listing, which needs far fewer
+    /// requests than listing every artist's tracks. Outputs JSON.
+    ///
+    /// Usage examples:
+    /// # List your 50 most scrobbled tracks
+    /// lastfm-edit list library-tracks --limit 50
+    ///
+    /// # Tracks scrobbled in 2019, alphabetically
+    /// lastfm-edit list library-tracks --from 2019-01-01 --to 2019-12-31 --sort name
+    LibraryTracks {
+        /// Maximum number of tracks to show (0 for no limit)
+        #[arg(long, default_value = "0")]
+        limit: usize,
+
+        /// Order of the listing
+        #[arg(long, value_enum, default_value_t)]
+        sort: SortOrder,
+
+        #[command(flatten)]
+        dates: DateRangeArgs,
+    },
+
+    /// List every album in your library, across all artists
+    ///
+    /// Usage examples:
+    /// # List your 20 most scrobbled albums
+    /// lastfm-edit list library-albums --limit 20
+    ///
+    /// # Albums scrobbled since March 2024
+    /// lastfm-edit list library-albums --from 2024-03-01
+    LibraryAlbums {
+        /// Maximum number of albums to show (0 for no limit)
+        #[arg(long, default_value = "0")]
+        limit: usize,
+
+        /// Order of the listing
+        #[arg(long, value_enum, default_value_t)]
+        sort: SortOrder,
+
+        #[command(flatten)]
+        dates: DateRangeArgs,
+    },
+
+    /// List every scrobble of one track, newest first
+    ///
+    /// This command lists the individual scrobbles of a track from its library page,
+    /// each with its timestamp and album information. Outputs JSON.
+    ///
+    /// Usage examples:
+    /// # List every play of "Creep"
+    /// lastfm-edit list scrobbles "Radiohead" "Creep"
+    ///
+    /// # List the 10 most recent plays
+    /// lastfm-edit list scrobbles "Radiohead" "Creep" --limit 10
+    Scrobbles {
+        /// Artist name
+        artist: String,
+
+        /// Track name
+        track: String,
+
+        /// Maximum number of scrobbles to show (0 for no limit)
+        #[arg(long, default_value = "0")]
+        limit: usize,
+    },
+
+    /// List your loved tracks
+    ///
+    /// This command lists the tracks you have loved, most recently loved first.
+    /// Outputs JSON.
+    ///
+    /// Usage examples:
+    /// # List all loved tracks
+    /// lastfm-edit list loved
+    ///
+    /// # List the 20 most recently loved tracks
+    /// lastfm-edit list loved --limit 20
+    Loved {
+        /// Maximum number of tracks to show (0 for no limit)
+        #[arg(long, default_value = "0")]
+        limit: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -230,7 +383,7 @@ pub enum Commands {
     /// # Write a deletion manifest for later execution
     /// lastfm-edit delete --recent-offset 0-4 --write-manifest delete.json
     ///
-    /// # Execute a deletion manifest
+    /// # Execute a deletion manifest (outcomes are recorded in it for `restore`)
     /// lastfm-edit delete --manifest delete.json --apply --delete-delay-ms 60000
     Delete {
         /// Delete scrobbles from recent pages (format: start-end, 0-indexed)
@@ -265,6 +418,117 @@ pub enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Delete double-submitted scrobbles
+    ///
+    /// This command finds identical scrobbles (same artist, track and album) submitted
+    /// within a few seconds of each other in your recent pages, keeps the earliest copy
+    /// and deletes the rest. The duplicates are always written to a deletion manifest
+    /// first, so the run can be audited and undone with `restore`.
+    ///
+    /// Usage examples:
+    /// # Show duplicates in the first 5 pages (dry run)
+    /// lastfm-edit dedupe --recent-pages 5
+    ///
+    /// # Delete them, recording the run in dedupe.json
+    /// lastfm-edit dedupe --recent-pages 5 --write-manifest dedupe.json --apply
+    Dedupe {
+        /// Number of recent pages to scan, starting from the most recent
+        #[arg(long)]
+        recent_pages: u32,
+
+        /// Identical scrobbles within this many seconds of each other are duplicates
+        #[arg(long, default_value = "30")]
+        window_secs: u64,
+
+        /// Manifest path (defaults to dedupe-<unix time>.json in the current directory)
+        #[arg(long)]
+        write_manifest: Option<PathBuf>,
+
+        /// Milliseconds to wait between actual delete requests
+        #[arg(long, default_value = "1000")]
+        delete_delay_ms: u64,
+
+        /// Actually delete the duplicates (default is dry-run mode)
+        #[arg(long)]
+        apply: bool,
+
+        /// Perform a dry run without deleting (default behavior)
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Detect runaway or unattended playback in recent scrobbles
+    ///
+    /// This command scans your most recent scrobbles for suspicious blocks: the same album
+    /// looping, plays spaced closer than any plausible track length, and long overnight
+    /// streaks. Blocks are ranked and printed as JSON with their offset ranges. Nothing is
+    /// deleted; write a manifest, review it, and execute it with `delete --manifest`.
+    ///
+    /// Usage examples:
+    /// # Scan the last 500 scrobbles
+    /// lastfm-edit detect-fakes
+    ///
+    /// # Scan 1000 scrobbles in UTC-5 and write a deletion manifest for review
+    /// lastfm-edit detect-fakes --scan 1000 --utc-offset-minutes=-300 --write-manifest fakes.json
+    DetectFakes {
+        /// Number of recent scrobbles to scan
+        #[arg(long, default_value = "500")]
+        scan: usize,
+
+        /// Your local time's offset from UTC in minutes, used to find overnight streaks
+        #[arg(long, default_value = "0", allow_hyphen_values = true)]
+        utc_offset_minutes: i64,
+
+        /// Write the flagged scrobbles to a deletion manifest
+        #[arg(long)]
+        write_manifest: Option<PathBuf>,
+    },
+
+    /// Restore scrobbles removed by a deletion manifest
+    ///
+    /// This command re-submits the scrobbles that `delete --manifest --apply` recorded as
+    /// deleted, with their original timestamps, through the Last.fm API. Each entry's
+    /// restore outcome is written back to the manifest, so an interrupted restore can be
+    /// resumed by running the command again. Last.fm ignores scrobbles older than two weeks.
+    ///
+    /// Requires an API key and secret (--api-key/--api-secret or LASTFM_EDIT_API_KEY/
+    /// LASTFM_EDIT_API_SECRET) and either an API session key or LASTFM_EDIT_PASSWORD.
+    ///
+    /// Usage examples:
+    /// # Show which scrobbles would be restored (dry run)
+    /// lastfm-edit restore --manifest delete.json
+    ///
+    /// # Restore them
+    /// lastfm-edit restore --manifest delete.json --apply
+    Restore {
+        /// Deletion manifest previously executed with `delete --manifest`
+        #[arg(long)]
+        manifest: PathBuf,
+
+        /// Last.fm API key (overrides LASTFM_EDIT_API_KEY environment variable)
+        #[arg(long)]
+        api_key: Option<String>,
+
+        /// Last.fm API shared secret (overrides LASTFM_EDIT_API_SECRET environment variable)
+        #[arg(long)]
+        api_secret: Option<String>,
+
+        /// Last.fm API session key (overrides LASTFM_EDIT_API_SESSION_KEY environment variable)
+        #[arg(long)]
+        api_session_key: Option<String>,
+
+        /// Also restore entries without a recorded delete outcome
+        #[arg(long)]
+        include_unrecorded: bool,
+
+        /// Actually submit the scrobbles (default is dry-run mode)
+        #[arg(long)]
+        apply: bool,
+
+        /// Perform a dry run without submitting anything (default behavior)
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Search tracks, albums, and artists in your library
     ///
     /// This command allows you to search through your Last.fm library for tracks, albums,
@@ -336,10 +600,92 @@ pub enum Commands {
     ///
     /// # List tracks organized by album
     /// lastfm-edit list tracks-by-album "Pink Floyd" --limit 5 --details
+    ///
+    /// # Checkpoint a long listing and pick it up again after an interruption
+    /// lastfm-edit list library-tracks --resume library-tracks.cursor
     List {
+        /// Save the position to this file after every item, and continue from it when
+        /// it exists. The file is removed once the listing completes.
+        #[arg(long, global = true)]
+        resume: Option<PathBuf>,
+
         #[command(subcommand)]
         command: ListCommands,
     },
+
+    /// Love or unlove a track
+    ///
+    /// This command marks a track as loved (or, with --unlove, removes the love) on
+    /// your Last.fm profile. Outputs JSON.
+    ///
+    /// Usage examples:
+    /// # Love a track
+    /// lastfm-edit love "Radiohead" "Creep"
+    ///
+    /// # Remove the love again
+    /// lastfm-edit love "Radiohead" "Creep" --unlove
+    Love {
+        /// Artist name
+        artist: String,
+
+        /// Track name
+        track: String,
+
+        /// Unlove the track instead of loving it
+        #[arg(long)]
+        unlove: bool,
+    },
+
+    /// Log in and save the session for later runs
+    ///
+    /// This command logs in with your username and password, or imports the session of a
+    /// browser that is already logged in to last.fm, and saves it so later commands can
+    /// run without credentials. Only Firefox and unencrypted Chromium cookie databases
+    /// can be imported.
+    ///
+    /// Usage examples:
+    /// # Log in with a password and save the session
+    /// lastfm-edit login --username myuser --password mypass
+    ///
+    /// # Import the session of a Firefox profile
+    /// lastfm-edit login --username myuser --from-browser ~/.mozilla/firefox/abcd1234.default-release
+    Login {
+        /// Browser profile directory or cookie database to import the session from
+        /// (requires --username)
+        #[arg(long)]
+        from_browser: Option<PathBuf>,
+    },
+
+    /// Check that the HTML parsers still match Last.fm's markup
+    ///
+    /// This command fetches one page of every kind the client parses (recent scrobbles,
+    /// library listings and searches) and prints, as JSON, how many elements each parser
+    /// selector matched and any anomalies. It exits with an error when a page drifted or
+    /// could not be fetched.
+    ///
+    /// Usage examples:
+    /// # Check against live pages, using the artist of your latest scrobble
+    /// lastfm-edit selfcheck
+    ///
+    /// # Check specific artist pages and a search query
+    /// lastfm-edit selfcheck --artist "The Beatles" --query moon
+    ///
+    /// # Check against pages recorded in a VCR cassette (no login needed)
+    /// lastfm-edit selfcheck --cassette tests/vcr/fixtures/recent_tracks_dir --username IvanMalison
+    Selfcheck {
+        /// Artist whose track and album listings to check (default: artist of your latest scrobble)
+        #[arg(long)]
+        artist: Option<String>,
+
+        /// Query for the track, album and artist searches (default: the artist)
+        #[arg(long)]
+        query: Option<String>,
+
+        /// Replay pages from a recorded VCR cassette directory instead of fetching them
+        /// (requires --username: the account the cassette was recorded with)
+        #[arg(long)]
+        cassette: Option<PathBuf>,
+    },
 }
 
 /// Execute the appropriate command handler based on the parsed command
@@ -432,6 +778,60 @@ pub async fn execute_command(
             }
         }
 
+        Commands::Dedupe {
+            recent_pages,
+            window_secs,
+            write_manifest,
+            delete_delay_ms,
+            apply,
+            dry_run,
+        } => {
+            let is_dry_run = dry_run || !apply;
+            dedupe::handle_dedupe(
+                client,
+                recent_pages,
+                window_secs,
+                write_manifest.as_deref(),
+                is_dry_run,
+                delete_delay_ms,
+            )
+            .await
+        }
+
+        Commands::DetectFakes {
+            scan,
+            utc_offset_minutes,
+            write_manifest,
+        } => {
+            detect::handle_detect_fakes(client, scan, utc_offset_minutes, write_manifest.as_deref())
+                .await
+        }
+
+        Commands::Restore {
+            manifest,
+            api_key,
+            api_secret,
+            api_session_key,
+            include_unrecorded,
+            apply,
+            dry_run,
+        } => {
+            let is_dry_run = dry_run || !apply;
+            let credentials = restore::ApiCredentials {
+                api_key,
+                api_secret,
+                session_key: api_session_key,
+            };
+            restore::handle_restore_manifest(
+                client,
+                &manifest,
+                credentials,
+                include_unrecorded,
+                is_dry_run,
+            )
+            .await
+        }
+
         Commands::Search {
             search_type,
             query,
@@ -447,23 +847,89 @@ pub async fn execute_command(
             show::handle_show_scrobbles(client, &offsets).await
         }
 
-        Commands::List { command } => match command {
-            ListCommands::Artists { limit } => list::handle_list_artists(client, limit).await,
-            ListCommands::Albums { artist, limit } => {
-                list::handle_list_albums(client, &artist, limit).await
-            }
-            ListCommands::Tracks { artist, limit } => {
-                list::handle_list_tracks(client, &artist, limit).await
-            }
-            ListCommands::TracksDirect { artist, limit } => {
-                list::handle_list_tracks_direct(client, &artist, limit).await
-            }
-            ListCommands::TracksByAlbum { artist, limit } => {
-                list::handle_list_tracks_by_album(client, &artist, limit).await
-            }
-            ListCommands::AlbumTracks { album, artist } => {
-                list::handle_list_album_tracks(client, &album, &artist).await
+        Commands::List { resume, command } => {
+            let checkpoint = Checkpoint::new(resume);
+            match command {
+                ListCommands::Artists { limit, dates } => {
+                    list::handle_list_artists(client, &checkpoint, limit, dates.range()).await
+                }
+                ListCommands::Albums {
+                    artist,
+                    limit,
+                    dates,
+                } => {
+                    list::handle_list_albums(client, &checkpoint, &artist, limit, dates.range())
+                        .await
+                }
+                ListCommands::Tracks { artist, limit } => {
+                    list::handle_list_tracks(client, &checkpoint, &artist, limit).await
+                }
+                ListCommands::TracksDirect {
+                    artist,
+                    limit,
+                    dates,
+                } => {
+                    list::handle_list_tracks_direct(
+                        client,
+                        &checkpoint,
+                        &artist,
+                        limit,
+                        dates.range(),
+                    )
+                    .await
+                }
+                ListCommands::TracksByAlbum { artist, limit } => {
+                    list::handle_list_tracks_by_album(client, &checkpoint, &artist, limit).await
+                }
+                ListCommands::AlbumTracks { album, artist } => {
+                    list::handle_list_album_tracks(client, &checkpoint, &album, &artist).await
+                }
+                ListCommands::LibraryTracks { limit, sort, dates } => {
+                    list::handle_list_library_tracks(
+                        client,
+                        &checkpoint,
+                        limit,
+                        dates.range(),
+                        sort.into(),
+                    )
+                    .await
+                }
+                ListCommands::LibraryAlbums { limit, sort, dates } => {
+                    list::handle_list_library_albums(
+                        client,
+                        &checkpoint,
+                        limit,
+                        dates.range(),
+                        sort.into(),
+                    )
+                    .await
+                }
+                ListCommands::Scrobbles {
+                    artist,
+                    track,
+                    limit,
+                } => list::handle_list_scrobbles(client, &checkpoint, &artist, &track, limit).await,
+                ListCommands::Loved { limit } => {
+                    list::handle_list_loved(client, &checkpoint, limit).await
+                }
             }
-        },
+        }
+
+        Commands::Love {
+            artist,
+            track,
+            unlove,
+        } => love::handle_love(client, &artist, &track, !unlove).await,
+
+        Commands::Login { .. } => {
+            log::info!("Logged in as {}", client.username());
+            Ok(())
+        }
+
+        Commands::Selfcheck {
+            artist,
+            query,
+            cassette: _,
+        } => selfcheck::handle_selfcheck(client, artist.as_deref(), query.as_deref(), false).await,
     }
 }
@@ -0,0 +1,184 @@
+use super::delete::ask_for_confirmation;
+use lastfm_edit::delete_manifest::{read_manifest, restore_manifest};
+use lastfm_edit::{LastFmApiClientImpl, LastFmEditClientImpl};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::Path;
+
+/// Events emitted by the restore command (JSON output to stdout)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RestoreEvent {
+    /// Found a deleted scrobble that would be restored (dry run)
+    ScrobbleFound {
+        index: usize,
+        artist: String,
+        track: String,
+        timestamp: u64,
+    },
+    /// A scrobble was re-submitted
+    ScrobbleRestored {
+        index: usize,
+        artist: String,
+        track: String,
+        timestamp: u64,
+        success: bool,
+        message: Option<String>,
+    },
+    /// Summary of restore operation
+    Summary {
+        total_candidates: usize,
+        restored: usize,
+        ignored: usize,
+        dry_run: bool,
+    },
+}
+
+/// Output a restore event as JSON to stdout
+fn output_event(event: &RestoreEvent) {
+    if let Ok(json) = serde_json::to_string(event) {
+        println!("{json}");
+    } else {
+        log::error!("Failed to serialize event to JSON");
+    }
+}
+
+/// API credentials for the restore command; each falls back to an environment variable.
+pub struct ApiCredentials {
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub session_key: Option<String>,
+}
+
+impl ApiCredentials {
+    fn api_client(
+        self,
+        client: &LastFmEditClientImpl,
+    ) -> Result<(LastFmApiClientImpl, bool), Box<dyn std::error::Error>> {
+        let api_key = self
+            .api_key
+            .or_else(|| env::var("LASTFM_EDIT_API_KEY").ok())
+            .ok_or("--api-key or LASTFM_EDIT_API_KEY is required to restore scrobbles")?;
+        let api_secret = self
+            .api_secret
+            .or_else(|| env::var("LASTFM_EDIT_API_SECRET").ok())
+            .ok_or("--api-secret or LASTFM_EDIT_API_SECRET is required to restore scrobbles")?;
+        let session_key = self
+            .session_key
+            .or_else(|| env::var("LASTFM_EDIT_API_SESSION_KEY").ok());
+
+        let http_client = http_client::native::NativeClient::new();
+        let api_client =
+            LastFmApiClientImpl::new(Box::new(http_client), client.username(), api_key)
+                .with_api_secret(api_secret);
+        Ok(match session_key {
+            Some(key) => (api_client.with_session_key(key), true),
+            None => (api_client, false),
+        })
+    }
+}
+
+pub async fn handle_restore_manifest(
+    client: &LastFmEditClientImpl,
+    manifest_path: &Path,
+    credentials: ApiCredentials,
+    include_unrecorded: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = read_manifest(manifest_path)?;
+    let candidates = manifest.restore_candidates(include_unrecorded);
+
+    if candidates.is_empty() {
+        log::info!("No deleted scrobbles left to restore in this manifest");
+        if manifest.scrobbles.iter().all(|e| e.delete_result.is_none()) {
+            log::info!("The manifest has no recorded deletions; use --include-unrecorded if it was executed before outcomes were recorded");
+        }
+        output_event(&RestoreEvent::Summary {
+            total_candidates: 0,
+            restored: 0,
+            ignored: 0,
+            dry_run,
+        });
+        return Ok(());
+    }
+
+    for entry in &candidates {
+        output_event(&RestoreEvent::ScrobbleFound {
+            index: entry.index,
+            artist: entry.artist.clone(),
+            track: entry.track.clone(),
+            timestamp: entry.timestamp,
+        });
+    }
+
+    log::info!("Found {} scrobbles to restore", candidates.len());
+
+    if dry_run {
+        log::info!("DRY RUN - No scrobbles submitted");
+        log::info!("Use --apply to restore these scrobbles");
+        output_event(&RestoreEvent::Summary {
+            total_candidates: candidates.len(),
+            restored: 0,
+            ignored: 0,
+            dry_run: true,
+        });
+        return Ok(());
+    }
+
+    let (api_client, has_session_key) = credentials.api_client(client)?;
+    if !has_session_key {
+        let password = env::var("LASTFM_EDIT_PASSWORD").map_err(
+            |_| "--api-session-key, LASTFM_EDIT_API_SESSION_KEY or LASTFM_EDIT_PASSWORD is required to restore scrobbles",
+        )?;
+        log::info!("Requesting an API session key...");
+        api_client.get_mobile_session(&password).await?;
+    }
+
+    eprintln!();
+    eprintln!("About to re-scrobble {} scrobble(s).", candidates.len());
+    if !ask_for_confirmation("\nDo you want to proceed with restoring these scrobbles?")? {
+        log::info!("Restore cancelled by user");
+        output_event(&RestoreEvent::Summary {
+            total_candidates: candidates.len(),
+            restored: 0,
+            ignored: 0,
+            dry_run: false,
+        });
+        return Ok(());
+    }
+
+    log::info!("Restoring scrobbles...");
+
+    let summary = restore_manifest(
+        &api_client,
+        manifest_path,
+        include_unrecorded,
+        |entry, result| {
+            output_event(&RestoreEvent::ScrobbleRestored {
+                index: entry.index,
+                artist: entry.artist.clone(),
+                track: entry.track.clone(),
+                timestamp: entry.timestamp,
+                success: result.success(),
+                message: result.message().map(str::to_string),
+            });
+        },
+    )
+    .await?;
+
+    output_event(&RestoreEvent::Summary {
+        total_candidates: summary.total_candidates,
+        restored: summary.restored,
+        ignored: summary.ignored,
+        dry_run: false,
+    });
+
+    log::info!(
+        "Restore complete: {} restored, {} ignored by Last.fm out of {} total",
+        summary.restored,
+        summary.ignored,
+        summary.total_candidates
+    );
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use http_client_vcr::{CassetteFormat, NoOpClient, VcrClient, VcrMode};
+use lastfm_edit::parse_health::{self_check, PageKind, ParseAnomaly};
+use lastfm_edit::vcr_matcher::LastFmEditVcrMatcher;
+use lastfm_edit::{ClientConfig, LastFmEditClientImpl, LastFmEditSession};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Events emitted by the selfcheck command (JSON output to stdout)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SelfCheckEvent {
+    /// How many elements one parser selector matched on a page
+    Selector {
+        page: PageKind,
+        selector: String,
+        required: bool,
+        matches: usize,
+    },
+    /// Result of parsing one page
+    Page {
+        page: PageKind,
+        healthy: bool,
+        rows: usize,
+        parsed: usize,
+        anomalies: Vec<ParseAnomaly>,
+        error: Option<String>,
+    },
+    /// Summary of the check
+    Summary {
+        pages: usize,
+        healthy: usize,
+        drifted: usize,
+        failed: usize,
+        /// Pages missing from the replayed cassette
+        skipped: usize,
+    },
+}
+
+/// Output a selfcheck event as JSON to stdout
+fn output_event(event: &SelfCheckEvent) {
+    if let Ok(json) = serde_json::to_string(event) {
+        println!("{json}");
+    } else {
+        log::error!("Failed to serialize event to JSON");
+    }
+}
+
+/// Run every page parser against live pages and report per-selector matches.
+///
+/// Fails when any page drifted or could not be fetched, so the command can gate CI.
+/// With `skip_unavailable`, pages that could not be fetched are reported as skipped
+/// instead (a cassette only holds the pages it was recorded with).
+pub async fn handle_selfcheck(
+    client: &LastFmEditClientImpl,
+    artist: Option<&str>,
+    query: Option<&str>,
+    skip_unavailable: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = self_check(client, artist, query).await;
+    let (mut healthy, mut drifted, mut failed, mut skipped) = (0, 0, 0, 0);
+    for entry in &entries {
+        match &entry.report {
+            Some(report) => {
+                for selector in &report.selectors {
+                    output_event(&SelfCheckEvent::Selector {
+                        page: entry.page,
+                        selector: selector.selector.clone(),
+                        required: selector.required,
+                        matches: selector.matches,
+                    });
+                }
+                if report.is_healthy() {
+                    healthy += 1;
+                } else {
+                    drifted += 1;
+                }
+                output_event(&SelfCheckEvent::Page {
+                    page: entry.page,
+                    healthy: report.is_healthy(),
+                    rows: report.rows,
+                    parsed: report.parsed,
+                    anomalies: report.anomalies.clone(),
+                    error: None,
+                });
+            }
+            None => {
+                if skip_unavailable {
+                    skipped += 1;
+                } else {
+                    failed += 1;
+                }
+                output_event(&SelfCheckEvent::Page {
+                    page: entry.page,
+                    healthy: false,
+                    rows: 0,
+                    parsed: 0,
+                    anomalies: Vec::new(),
+                    error: entry.error.clone(),
+                });
+            }
+        }
+    }
+
+    output_event(&SelfCheckEvent::Summary {
+        pages: entries.len(),
+        healthy,
+        drifted,
+        failed,
+        skipped,
+    });
+
+    if drifted + failed > 0 {
+        return Err(format!("{drifted} page(s) drifted, {failed} page(s) failed").into());
+    }
+    Ok(())
+}
+
+/// Run the self-check against pages replayed from a recorded VCR cassette directory.
+pub async fn handle_selfcheck_cassette(
+    cassette: &Path,
+    username: &str,
+    artist: Option<&str>,
+    query: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let vcr_client = VcrClient::builder(cassette)
+        .inner_client(Box::new(NoOpClient::new()))
+        .mode(VcrMode::Replay)
+        .format(CassetteFormat::Directory)
+        .matcher(Box::new(LastFmEditVcrMatcher::new()))
+        .build()
+        .await?;
+    let session = LastFmEditSession::new(
+        username.to_string(),
+        Vec::new(),
+        None,
+        "https://www.last.fm".to_string(),
+    );
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(vcr_client),
+        session,
+        ClientConfig::for_testing(),
+    );
+    handle_selfcheck(&client, artist, query, true).await
+}
@@ -65,7 +65,7 @@ pub async fn handle_show_scrobbles(
             let scrobble = &all_scrobbles[offset as usize];
             output_event(&ShowEvent::ScrobbleDetails {
                 offset,
-                scrobble: scrobble.clone(),
+                scrobble: Box::new(scrobble.clone()),
             });
             shown_count += 1;
         } else {
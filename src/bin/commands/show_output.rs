@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 #[serde(tag = "type")]
 pub enum ShowEvent {
     /// Showing details for a specific scrobble
-    ScrobbleDetails { offset: u64, scrobble: Track },
+    ScrobbleDetails { offset: u64, scrobble: Box<Track> },
     /// Requested offset is not available (beyond available scrobbles)
     OffsetUnavailable { offset: u64, total_available: usize },
 }
@@ -1,7 +1,42 @@
-use lastfm_edit::{LastFmEditClientImpl, SessionPersistence};
+use lastfm_edit::{LastFmEditClientImpl, LastFmError, SessionManager};
 use std::env;
 use std::io::{self, Write};
 
+/// Application name under which the CLI saves sessions
+const APP_NAME: &str = "lastfm-edit";
+
+const PASSPHRASE_HINT: &str =
+    "Set LASTFM_EDIT_SESSION_PASSPHRASE or pass --encrypt-session to unlock encrypted sessions";
+
+/// Build the session manager, encrypting sessions when a passphrase is available.
+///
+/// The passphrase comes from the LASTFM_EDIT_SESSION_PASSPHRASE environment variable,
+/// or is prompted for when `prompt` is set (the --encrypt-session flag).
+#[cfg(feature = "session-encryption")]
+pub fn session_manager(prompt: bool) -> Result<SessionManager, Box<dyn std::error::Error>> {
+    let manager = SessionManager::new(APP_NAME);
+    let passphrase = match env::var("LASTFM_EDIT_SESSION_PASSPHRASE") {
+        Ok(passphrase) => Some(passphrase),
+        Err(_) if prompt => Some(rpassword::prompt_password("Session passphrase: ")?),
+        Err(_) => None,
+    };
+
+    match passphrase {
+        Some(passphrase) if passphrase.is_empty() => Err("Session passphrase is empty".into()),
+        Some(passphrase) => Ok(manager.with_passphrase(passphrase)),
+        None => Ok(manager),
+    }
+}
+
+/// Build the session manager; this build saves sessions as plaintext only.
+#[cfg(not(feature = "session-encryption"))]
+pub fn session_manager(prompt: bool) -> Result<SessionManager, Box<dyn std::error::Error>> {
+    if prompt || env::var("LASTFM_EDIT_SESSION_PASSPHRASE").is_ok() {
+        return Err("lastfm-edit was built without the session-encryption feature".into());
+    }
+    Ok(SessionManager::new(APP_NAME))
+}
+
 /// Load existing session or create a new client with fresh login.
 ///
 /// This function implements the session management logic:
@@ -10,14 +45,15 @@ use std::io::{self, Write};
 /// 3. If session is invalid or doesn't exist, perform fresh login
 /// 4. Save the new session for future use
 pub async fn load_or_create_client(
+    manager: &SessionManager,
     username: &str,
     password: &str,
 ) -> Result<LastFmEditClientImpl, Box<dyn std::error::Error>> {
     // Check if we have a saved session
-    if SessionPersistence::session_exists(username) {
+    if manager.session_exists(username) {
         log::info!("Found existing session for user '{username}', attempting to restore...");
 
-        match SessionPersistence::load_session(username) {
+        match manager.load_session(username) {
             Ok(session) => {
                 log::info!("Session loaded successfully");
 
@@ -33,13 +69,18 @@ pub async fn load_or_create_client(
                 } else {
                     log::warn!("Session is invalid or expired");
                     // Remove invalid session file
-                    let _ = SessionPersistence::remove_session(username);
+                    let _ = manager.remove_session(username);
                 }
             }
+            Err(e @ LastFmError::Auth(_)) => {
+                // Encrypted with another passphrase (or none given): keep the file rather
+                // than logging in again and replacing it with a plaintext session.
+                return Err(format!("Failed to load session: {e}\n{PASSPHRASE_HINT}").into());
+            }
             Err(e) => {
                 log::warn!("Failed to load session: {e}");
                 // Remove corrupted session file
-                let _ = SessionPersistence::remove_session(username);
+                let _ = manager.remove_session(username);
             }
         }
     }
@@ -54,7 +95,7 @@ pub async fn load_or_create_client(
     // Save the new session
     log::info!("Saving session for future use...");
     let session = client.get_session();
-    if let Err(e) = SessionPersistence::save_session(&session) {
+    if let Err(e) = manager.save_session(&session) {
         log::warn!("Failed to save session: {e}");
         log::warn!("You'll need to login again next time");
     } else {
@@ -106,24 +147,25 @@ pub fn parse_range(
 /// Try to restore the most recent session from available saved sessions.
 ///
 /// This function looks for all saved sessions and attempts to restore the most recent valid one.
-/// Returns Some(client) if a valid session was found and restored, None otherwise.
-pub async fn try_restore_most_recent_session() -> Option<LastFmEditClientImpl> {
+/// Returns Some(client) if a valid session was found and restored, None otherwise, and an
+/// error when the only sessions left are encrypted ones this manager cannot open.
+pub async fn try_restore_most_recent_session(
+    manager: &SessionManager,
+) -> Result<Option<LastFmEditClientImpl>, Box<dyn std::error::Error>> {
     // Get list of all saved users
-    let saved_users = match SessionPersistence::list_saved_users() {
+    let saved_users = match manager.list_saved_users() {
         Ok(users) => users,
-        Err(_) => return None,
+        Err(_) => return Ok(None),
     };
 
-    if saved_users.is_empty() {
-        return None;
-    }
+    let mut locked = None;
 
     // Try each saved user session, starting with the first one found
     // In a more sophisticated implementation, we could sort by last modified time
     for username in saved_users {
         log::info!("Attempting to restore session for user '{username}'...");
 
-        match SessionPersistence::load_session(&username) {
+        match manager.load_session(&username) {
             Ok(session) => {
                 log::info!("Session loaded successfully");
 
@@ -135,22 +177,30 @@ pub async fn try_restore_most_recent_session() -> Option<LastFmEditClientImpl> {
                 log::info!("Validating session...");
                 if client.validate_session().await {
                     log::info!("Session is valid for user '{username}'");
-                    return Some(client);
+                    return Ok(Some(client));
                 } else {
                     log::warn!("Session is invalid or expired for user '{username}'");
                     // Remove invalid session file
-                    let _ = SessionPersistence::remove_session(&username);
+                    let _ = manager.remove_session(&username);
                 }
             }
+            Err(e @ LastFmError::Auth(_)) => {
+                // Encrypted with another passphrase (or none given): keep the file
+                log::warn!("Failed to load session for user '{username}': {e}");
+                locked = Some(e);
+            }
             Err(e) => {
                 log::warn!("Failed to load session for user '{username}': {e}");
                 // Remove corrupted session file
-                let _ = SessionPersistence::remove_session(&username);
+                let _ = manager.remove_session(&username);
             }
         }
     }
 
-    None
+    match locked {
+        Some(e) => Err(format!("Failed to load session: {e}\n{PASSPHRASE_HINT}").into()),
+        None => Ok(None),
+    }
 }
 
 /// Prompt the user for their Last.fm credentials interactively.
@@ -3,8 +3,9 @@ use log::LevelFilter;
 
 mod commands;
 use commands::{
-    execute_command, utils::get_credentials, utils::load_or_create_client,
-    utils::prompt_for_credentials, utils::try_restore_most_recent_session, Commands,
+    execute_command, login, selfcheck, utils::get_credentials, utils::load_or_create_client,
+    utils::prompt_for_credentials, utils::session_manager, utils::try_restore_most_recent_session,
+    Commands,
 };
 
 /// Last.fm scrobble metadata editor
@@ -34,6 +35,16 @@ struct Cli {
     #[arg(short, long, global = true)]
     password: Option<String>,
 
+    /// Share one rate-limit budget with other processes using the same account
+    /// (other lastfm-edit runs, scrobble-store, scrobble-scrubber)
+    #[arg(long, global = true)]
+    shared_rate_limit: bool,
+
+    /// Encrypt saved sessions with a passphrase, prompting for it unless
+    /// LASTFM_EDIT_SESSION_PASSPHRASE is set (setting that variable alone also enables it)
+    #[arg(long, global = true)]
+    encrypt_session: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -96,6 +107,57 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     builder.init();
 
+    // Replaying a cassette needs no login
+    if let Commands::Selfcheck {
+        artist,
+        query,
+        cassette: Some(cassette),
+    } = &args.command
+    {
+        let Some(username) = &args.username else {
+            log::error!(
+                "--cassette requires --username (the account the cassette was recorded with)"
+            );
+            std::process::exit(1);
+        };
+        if let Err(e) = selfcheck::handle_selfcheck_cassette(
+            cassette,
+            username,
+            artist.as_deref(),
+            query.as_deref(),
+        )
+        .await
+        {
+            log::error!("Command failed: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let manager = match session_manager(args.encrypt_session) {
+        Ok(manager) => manager,
+        Err(e) => {
+            log::error!("Failed to set up session storage: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    // Importing a browser session needs no password
+    if let Commands::Login {
+        from_browser: Some(profile),
+    } = &args.command
+    {
+        let Some(username) = &args.username else {
+            log::error!("--from-browser requires --username (the account logged in there)");
+            std::process::exit(1);
+        };
+        if let Err(e) = login::handle_login_from_browser(&manager, profile, username).await {
+            log::error!("Command failed: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Try to get credentials from command line args or environment first
     let (username, password) = if let (Some(u), Some(p)) = (&args.username, &args.password) {
         (Some(u.clone()), Some(p.clone()))
@@ -112,18 +174,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // First, try to restore the most recent session if no credentials were provided
     let client = if username.is_none() && password.is_none() {
-        match try_restore_most_recent_session().await {
-            Some(client) => {
+        match try_restore_most_recent_session(&manager).await {
+            Ok(Some(client)) => {
                 log::info!("Restored most recent session");
                 client
             }
-            None => {
+            Err(e) => {
+                log::error!("{e}");
+                std::process::exit(1);
+            }
+            Ok(None) => {
                 // No valid session found, prompt for credentials
                 log::info!("No valid saved session found. Please provide credentials:");
                 let (prompted_username, prompted_password) = prompt_for_credentials();
                 log::info!("Using username: {prompted_username}");
 
-                match load_or_create_client(&prompted_username, &prompted_password).await {
+                match load_or_create_client(&manager, &prompted_username, &prompted_password).await
+                {
                     Ok(client) => client,
                     Err(e) => {
                         log::error!("Failed to create client: {e}");
@@ -138,7 +205,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let password = password.unwrap();
         log::info!("Using username: {username}");
 
-        match load_or_create_client(&username, &password).await {
+        match load_or_create_client(&manager, &username, &password).await {
             Ok(client) => client,
             Err(e) => {
                 log::error!("Failed to create client: {e}");
@@ -147,6 +214,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    let client = if args.shared_rate_limit {
+        let username = client.username();
+        match lastfm_edit::SharedRateLimiter::for_lastfm_edit_user(&username) {
+            Ok(limiter) => {
+                log::info!("Sharing rate limit budget via {}", limiter.path().display());
+                client.with_shared_rate_limiter(std::sync::Arc::new(limiter))
+            }
+            Err(e) => {
+                log::error!("Failed to set up shared rate limit: {e}");
+                std::process::exit(1);
+            }
+        }
+    } else {
+        client
+    };
+
     log::info!("Client ready");
 
     // Execute the command
@@ -0,0 +1,234 @@
+//! Import a Last.fm session from a local browser profile.
+//!
+//! Reads the `sessionid` and `csrftoken` cookies for last.fm from a Firefox
+//! `cookies.sqlite` or an unencrypted Chromium `Cookies` database, for accounts that
+//! sign in only through a browser or whose password logins get blocked.
+
+use crate::client::LastFmEditClientImpl;
+use crate::session_persistence::SessionManager;
+use crate::types::{LastFmEditSession, LastFmError};
+use crate::Result;
+use http_client::HttpClient;
+use rusqlite::{Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEFAULT_BASE_URL: &str = "https://www.last.fm";
+
+/// The cookie stores this module can read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserKind {
+    /// `cookies.sqlite` (`moz_cookies` table)
+    Firefox,
+    /// `Cookies` (`cookies` table), without OS-level value encryption
+    Chromium,
+}
+
+/// The last.fm cookies found in a browser profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserCookies {
+    pub browser: BrowserKind,
+    pub session_id: String,
+    pub csrf_token: Option<String>,
+}
+
+impl LastFmEditSession {
+    /// Build a session for `username` from the last.fm cookies of a browser profile.
+    ///
+    /// `profile_path` is either the cookie database itself or a profile directory
+    /// containing `cookies.sqlite` (Firefox), `Cookies` or `Network/Cookies` (Chromium).
+    /// The browser may keep running: the database is copied before it is read.
+    ///
+    /// The session is not checked against Last.fm; see [`import_browser_session`].
+    pub fn from_browser_profile(profile_path: impl AsRef<Path>, username: &str) -> Result<Self> {
+        let cookies = read_browser_cookies(profile_path.as_ref())?;
+
+        let mut session_cookies = vec![format!("sessionid={}", cookies.session_id)];
+        if let Some(csrf_token) = &cookies.csrf_token {
+            session_cookies.push(format!("csrftoken={csrf_token}"));
+        }
+
+        Ok(LastFmEditSession::new(
+            username.to_string(),
+            session_cookies,
+            cookies.csrf_token,
+            DEFAULT_BASE_URL.to_string(),
+        ))
+    }
+}
+
+/// Import a browser session, check that Last.fm accepts it, and save it with `manager`.
+///
+/// Fails with [`LastFmError::Auth`] when the browser is not logged in to Last.fm.
+pub async fn import_browser_session(
+    client: Box<dyn HttpClient + Send + Sync>,
+    profile_path: impl AsRef<Path>,
+    username: &str,
+    manager: &SessionManager,
+) -> Result<LastFmEditClientImpl> {
+    let session = LastFmEditSession::from_browser_profile(profile_path, username)?;
+    let client = LastFmEditClientImpl::from_session(client, session.clone());
+
+    if !client.validate_session().await {
+        return Err(LastFmError::Auth(
+            "Last.fm rejected the browser session; log in to last.fm in the browser first"
+                .to_string(),
+        ));
+    }
+
+    manager.save_session(&session)?;
+    Ok(client)
+}
+
+/// Read the last.fm cookies from a Firefox or Chromium cookie database.
+pub fn read_browser_cookies(profile_path: &Path) -> Result<BrowserCookies> {
+    let database = find_cookie_database(profile_path)?;
+
+    // Browsers hold their cookie database open (and Firefox keeps recent writes in the
+    // WAL file), so read a copy of both instead of the live files.
+    let scratch = create_scratch_dir()?;
+    let result = (|| {
+        let copy = scratch.join("cookies.db");
+        std::fs::copy(&database, &copy)?;
+        let wal = sidecar(&database, "-wal");
+        if wal.exists() {
+            std::fs::copy(&wal, sidecar(&copy, "-wal"))?;
+        }
+        read_cookie_copy(&copy)
+    })();
+    // Remove the copies, which hold live session cookies, even when copying failed.
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Create a fresh directory, private to the current user, to hold the copied database.
+///
+/// The copies carry live session cookies, so the directory must not be one another user
+/// could have created or can read: creation fails instead of reusing an existing path.
+fn create_scratch_dir() -> Result<PathBuf> {
+    static SCRATCH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    let mut attempts = 0;
+    loop {
+        let scratch = std::env::temp_dir().join(format!(
+            "lastfm-edit-browser-cookies-{}-{nanos:x}-{}",
+            std::process::id(),
+            SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        match builder.create(&scratch) {
+            Ok(()) => return Ok(scratch),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempts < 16 => {
+                attempts += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn read_cookie_copy(database: &Path) -> Result<BrowserCookies> {
+    let conn = Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .map_err(sqlite_error)?;
+
+    let browser = if has_table(&conn, "moz_cookies")? {
+        BrowserKind::Firefox
+    } else if has_table(&conn, "cookies")? {
+        BrowserKind::Chromium
+    } else {
+        return Err(LastFmError::Parse(format!(
+            "{} is not a Firefox or Chromium cookie database",
+            database.display()
+        )));
+    };
+
+    let session_id = read_cookie(&conn, browser, "sessionid")?.ok_or_else(|| {
+        LastFmError::Auth("No last.fm sessionid cookie in the browser profile".to_string())
+    })?;
+    let csrf_token = read_cookie(&conn, browser, "csrftoken")?;
+
+    Ok(BrowserCookies {
+        browser,
+        session_id,
+        csrf_token,
+    })
+}
+
+/// Resolve a profile directory to the cookie database inside it.
+fn find_cookie_database(profile_path: &Path) -> Result<PathBuf> {
+    if profile_path.is_file() {
+        return Ok(profile_path.to_path_buf());
+    }
+
+    ["cookies.sqlite", "Cookies", "Network/Cookies"]
+        .iter()
+        .map(|name| profile_path.join(name))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| {
+            LastFmError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No cookie database found in {}", profile_path.display()),
+            ))
+        })
+}
+
+fn sidecar(database: &Path, suffix: &str) -> PathBuf {
+    let mut name = database.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn has_table(conn: &Connection, table: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+    .map_err(sqlite_error)
+}
+
+/// The value of the last.fm cookie `name`, preferring the one that expires last.
+fn read_cookie(conn: &Connection, browser: BrowserKind, name: &str) -> Result<Option<String>> {
+    let sql = match browser {
+        BrowserKind::Firefox => {
+            "SELECT value, NULL FROM moz_cookies
+             WHERE name = ?1 AND (host = 'last.fm' OR host LIKE '%.last.fm')
+             ORDER BY expiry DESC"
+        }
+        BrowserKind::Chromium => {
+            "SELECT value, encrypted_value FROM cookies
+             WHERE name = ?1 AND (host_key = 'last.fm' OR host_key LIKE '%.last.fm')
+             ORDER BY expires_utc DESC"
+        }
+    };
+
+    let mut statement = conn.prepare(sql).map_err(sqlite_error)?;
+    let mut rows = statement.query([name]).map_err(sqlite_error)?;
+    let mut encrypted = false;
+    while let Some(row) = rows.next().map_err(sqlite_error)? {
+        let value: String = row.get(0).map_err(sqlite_error)?;
+        if !value.is_empty() {
+            return Ok(Some(value));
+        }
+        let encrypted_value: Option<Vec<u8>> = row.get(1).map_err(sqlite_error)?;
+        encrypted |= encrypted_value.is_some_and(|v| !v.is_empty());
+    }
+
+    if encrypted {
+        return Err(LastFmError::Auth(format!(
+            "The browser stores the last.fm {name} cookie encrypted, which is not supported"
+        )));
+    }
+    Ok(None)
+}
+
+fn sqlite_error(e: rusqlite::Error) -> LastFmError {
+    LastFmError::Io(std::io::Error::other(format!("cookie database: {e}")))
+}
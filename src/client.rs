@@ -1,14 +1,21 @@
 use crate::edit_analysis;
+use crate::edit_journal::{self, EditJournal};
 use crate::headers;
-use crate::login::extract_cookies_from_response;
+use crate::login::{extract_cookies_from_response, CredentialProvider, LoginManager};
+use crate::pacing::{self, AdaptivePacer};
+use crate::parse_health::{PageKind, ParseReport};
 use crate::parsing::LastFmParser;
 use crate::r#trait::{LastFmBaseClient, LastFmEditClient};
+use crate::response_cache::{CachePolicy, CachedPage, LibraryChange, PageScope, ResponseCache};
 use crate::retry;
+use crate::session_persistence::SessionManager;
+use crate::shared_rate_limit::SharedRateLimiter;
 use crate::types::{
     AlbumPage, ArtistPage, ClientConfig, ClientEvent, ClientEventReceiver, DelayReason,
-    EditResponse, ExactScrobbleEdit, LastFmEditSession, LastFmError, RateLimitBehavior,
-    RateLimitConfig, RateLimitType, RequestInfo, RetryConfig, ScrobbleEdit, SharedEventBroadcaster,
-    SingleEditResponse, Track, TrackPage,
+    EditFailureKind, EditResponse, EditVerificationStatus, ExactScrobbleEdit, LastFmEditSession,
+    LastFmError, LibraryDateRange, LibrarySort, RateLimitBehavior, RateLimitConfig, RateLimitType,
+    RequestInfo, RetryConfig, ScrobbleEdit, SharedEventBroadcaster, SingleEditResponse, Track,
+    TrackPage, TrafficClass,
 };
 use crate::Result;
 use crate::{cancel, CancellationState};
@@ -16,6 +23,7 @@ use async_trait::async_trait;
 use http_client::{HttpClient, Request, Response};
 use http_types::{Method, Url};
 use scraper::{Html, Selector};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
@@ -27,6 +35,18 @@ pub struct LastFmEditClientImpl {
     config: ClientConfig,
     cancel: CancellationState,
     api_key: Option<String>,
+    edit_journal: Option<Arc<dyn EditJournal>>,
+    pacer: Option<Arc<AdaptivePacer>>,
+    shared_limiter: Option<Arc<SharedRateLimiter>>,
+    parse_reports: Arc<Mutex<HashMap<PageKind, ParseReport>>>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+    session_manager: Option<SessionManager>,
+    /// Held while logging in again, so clones that hit the same expiry log in once.
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Held during the fixed GET delay, so concurrent page fetches are spaced out.
+    get_delay_lock: Arc<tokio::sync::Mutex<()>>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    cache_policy: CachePolicy,
 }
 
 /// Converts panics inside the wrapped client's `send` into `http_types::Error`s.
@@ -77,12 +97,98 @@ fn page_indicates_logged_out(html: &str) -> bool {
     html.contains("/login?next=")
 }
 
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// The `&from=...&to=...` (or `&date_preset=...`) suffix for a library page URL.
+fn range_suffix(range: &LibraryDateRange) -> String {
+    if range.is_all_time() {
+        String::new()
+    } else {
+        format!("&{}", range.query_params())
+    }
+}
+
+/// The `&order=...` suffix for a library page URL (empty for Last.fm's default order).
+fn sort_suffix(sort: LibrarySort) -> String {
+    if sort == LibrarySort::default() {
+        String::new()
+    } else {
+        format!("&order={}", sort.as_param())
+    }
+}
+
+/// Compare the scrobbles shown at an edit's timestamp with the edit.
+///
+/// Album fields are only compared when the page exposes them (via the edit form inputs).
+fn judge_edit(exact_edit: &ExactScrobbleEdit, rows: &[Track]) -> EditVerificationStatus {
+    let shows = |track: &Track, name: &str, artist: &str, album: &str, album_artist: &str| {
+        track.name == name
+            && track.artist == artist
+            && track.album.as_deref().is_none_or(|a| a == album)
+            && track
+                .album_artist
+                .as_deref()
+                .is_none_or(|a| a == album_artist)
+    };
+    let shows_new = rows.iter().any(|t| {
+        shows(
+            t,
+            &exact_edit.track_name,
+            &exact_edit.artist_name,
+            &exact_edit.album_name,
+            &exact_edit.album_artist_name,
+        )
+    });
+    let shows_original = rows.iter().any(|t| {
+        shows(
+            t,
+            &exact_edit.track_name_original,
+            &exact_edit.artist_name_original,
+            &exact_edit.album_name_original,
+            &exact_edit.album_artist_name_original,
+        )
+    });
+    let is_noop = exact_edit.track_name == exact_edit.track_name_original
+        && exact_edit.artist_name == exact_edit.artist_name_original
+        && exact_edit.album_name == exact_edit.album_name_original
+        && exact_edit.album_artist_name == exact_edit.album_artist_name_original;
+
+    match (shows_new, shows_original) {
+        (true, false) => EditVerificationStatus::Verified,
+        (true, true) if is_noop => EditVerificationStatus::Verified,
+        (true, true) => EditVerificationStatus::Contradicted {
+            reason: "Both the new and the original metadata are still scrobbled".to_string(),
+        },
+        (false, true) => EditVerificationStatus::Contradicted {
+            reason: "Scrobble still shows the original metadata".to_string(),
+        },
+        (false, false) => EditVerificationStatus::Contradicted {
+            reason: format!(
+                "Scrobble shows '{}' by '{}' instead",
+                rows[0].name, rows[0].artist
+            ),
+        },
+    }
+}
+
 impl LastFmEditClientImpl {
     /// Custom URL encoding for Last.fm paths
     fn lastfm_encode(&self, input: &str) -> String {
         urlencoding::encode(input).to_string()
     }
 
+    /// Whether `url` is a page of this session's own library.
+    fn is_own_library_url(&self, url: &str) -> bool {
+        let session = self.session.lock().unwrap();
+        let prefix = format!("{}/user/{}/library", session.base_url, session.username);
+        url.starts_with(&prefix)
+    }
+
     pub fn from_session(
         client: Box<dyn HttpClient + Send + Sync>,
         session: LastFmEditSession,
@@ -234,6 +340,12 @@ impl LastFmEditClientImpl {
         broadcaster: Arc<SharedEventBroadcaster>,
     ) -> Self {
         let api_key = config.api_key.clone();
+        let pacer = config.adaptive_pacing.enabled.then(|| {
+            Arc::new(AdaptivePacer::new(
+                config.adaptive_pacing.clone(),
+                &config.operational_delays,
+            ))
+        });
         Self {
             client,
             session: Arc::new(Mutex::new(session)),
@@ -242,6 +354,16 @@ impl LastFmEditClientImpl {
             config,
             cancel: CancellationState::new(),
             api_key,
+            edit_journal: None,
+            pacer,
+            shared_limiter: None,
+            parse_reports: Arc::new(Mutex::new(HashMap::new())),
+            credential_provider: None,
+            session_manager: None,
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+            get_delay_lock: Arc::new(tokio::sync::Mutex::new(())),
+            response_cache: None,
+            cache_policy: CachePolicy::default(),
         }
     }
 
@@ -265,6 +387,127 @@ impl LastFmEditClientImpl {
         self.cancel.subscribe()
     }
 
+    /// Current adaptive pacing delay for `traffic`, if adaptive pacing is enabled.
+    pub fn pacing_delay_ms(&self, traffic: TrafficClass) -> Option<u64> {
+        self.pacer.as_ref().map(|pacer| pacer.delay_ms(traffic))
+    }
+
+    /// Wait for the next send slot of `traffic`.
+    ///
+    /// With adaptive pacing this waits for the pacer's slot; otherwise GET requests wait
+    /// the fixed `get_delay_ms`, one after another, and POSTs are not delayed here.
+    async fn pace(&self, traffic: TrafficClass, request: &RequestInfo) -> Result<()> {
+        if let Some(limiter) = &self.shared_limiter {
+            self.wait_for_shared_budget(limiter, request).await?;
+        }
+
+        let Some(pacer) = &self.pacer else {
+            if traffic == TrafficClass::Get && self.config.operational_delays.get_delay_ms > 0 {
+                // Optional throttle for heavy library scanning. Kept cancelable so callers can stop.
+                let _slot = self.get_delay_lock.lock().await;
+                self.sleep_ms(self.config.operational_delays.get_delay_ms)
+                    .await?;
+            }
+            return Ok(());
+        };
+
+        let wait_ms = pacer.reserve(traffic).as_millis() as u64;
+        if wait_ms > 0 {
+            self.broadcast_event(ClientEvent::Delaying {
+                delay_ms: wait_ms,
+                reason: DelayReason::AdaptivePacing(traffic),
+                request: Some(request.clone()),
+                delay_timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            });
+        }
+        self.sleep_ms(wait_ms).await
+    }
+
+    /// Wait for this process's slot in the cross-process budget.
+    async fn wait_for_shared_budget(
+        &self,
+        limiter: &SharedRateLimiter,
+        request: &RequestInfo,
+    ) -> Result<()> {
+        if self.config.rate_limit_behavior == RateLimitBehavior::ReturnError {
+            match limiter.cooldown_remaining() {
+                Ok(Some(remaining)) => {
+                    return Err(LastFmError::RateLimit {
+                        retry_after: remaining.as_secs().max(1),
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to read shared rate limit state: {e}"),
+            }
+        }
+
+        let wait_ms = match limiter.reserve() {
+            Ok(wait) => wait.as_millis() as u64,
+            Err(e) => {
+                log::warn!("Failed to reserve a slot in the shared rate limit budget: {e}");
+                0
+            }
+        };
+        if wait_ms > 0 {
+            self.broadcast_event(ClientEvent::Delaying {
+                delay_ms: wait_ms,
+                reason: DelayReason::SharedRateLimit,
+                request: Some(request.clone()),
+                delay_timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            });
+        }
+        self.sleep_ms(wait_ms).await
+    }
+
+    /// Feed request outcomes to the adaptive pacer and report delay changes.
+    fn adapt_pacing(&self, pacer: &AdaptivePacer, event: &ClientEvent) {
+        let traffic_of = |request: &RequestInfo| {
+            if request.method == "GET" {
+                TrafficClass::Get
+            } else if request.path.ends_with("/library/delete") {
+                TrafficClass::Delete
+            } else {
+                TrafficClass::Edit
+            }
+        };
+
+        let (traffic, backoff) = match event {
+            ClientEvent::RequestCompleted {
+                request,
+                status_code,
+                ..
+            } if (200..300).contains(status_code) => (traffic_of(request), false),
+            // Retry loops re-report detected rate limits without the request; the detection
+            // itself always carries it, so only that one counts.
+            ClientEvent::RateLimited {
+                request: Some(request),
+                ..
+            } => (traffic_of(request), true),
+            _ => return,
+        };
+
+        let changed = if backoff {
+            pacer.record_rate_limit(traffic)
+        } else {
+            pacer.record_success(traffic)
+        };
+        if let Some(delay_ms) = changed {
+            self.broadcaster
+                .broadcast_event(ClientEvent::PacingAdjusted {
+                    traffic,
+                    delay_ms,
+                    requests_per_minute: pacing::requests_per_minute(delay_ms),
+                    backoff,
+                });
+        }
+    }
+
     async fn sleep_ms(&self, delay_ms: u64) -> Result<()> {
         if delay_ms == 0 {
             return Ok(());
@@ -304,6 +547,213 @@ impl LastFmEditClientImpl {
         clone
     }
 
+    /// Record every successfully applied edit into `journal`.
+    ///
+    /// Clones of the returned client share the journal. Journal write failures are logged
+    /// and do not fail the edit, which Last.fm has already applied.
+    pub fn with_edit_journal(mut self, journal: Arc<dyn EditJournal>) -> Self {
+        self.edit_journal = Some(journal);
+        self
+    }
+
+    /// The edit journal attached with [`with_edit_journal`](Self::with_edit_journal), if any.
+    pub fn edit_journal(&self) -> Option<Arc<dyn EditJournal>> {
+        self.edit_journal.clone()
+    }
+
+    /// Consult `limiter` before every request and report detected rate limits to it.
+    ///
+    /// Use one limiter file per account (see [`SharedRateLimiter::for_user`]) so processes
+    /// working on the same account share one budget and honour each other's cool-downs.
+    /// A non-blocking client fails with [`LastFmError::RateLimit`] during a cool-down
+    /// instead of waiting it out. Errors reading the limiter file are logged and the
+    /// request proceeds unpaced.
+    pub fn with_shared_rate_limiter(mut self, limiter: Arc<SharedRateLimiter>) -> Self {
+        self.shared_limiter = Some(limiter);
+        self
+    }
+
+    /// The limiter attached with [`with_shared_rate_limiter`](Self::with_shared_rate_limiter), if any.
+    pub fn shared_rate_limiter(&self) -> Option<Arc<SharedRateLimiter>> {
+        self.shared_limiter.clone()
+    }
+
+    /// Log in again with credentials from `provider` when the session expires.
+    ///
+    /// When a page fetch finds the session logged out, the client logs in through
+    /// [`LoginManager`], swaps the new cookies and CSRF token into the session shared by
+    /// its clones, saves it with the manager set by
+    /// [`with_session_manager`](Self::with_session_manager), emits
+    /// [`ClientEvent::SessionRefreshed`] and retries the request once. A failed re-login
+    /// is returned as the request's error.
+    pub fn with_credential_provider(mut self, provider: Arc<dyn CredentialProvider>) -> Self {
+        self.credential_provider = Some(provider);
+        self
+    }
+
+    /// Save sessions refreshed after an expiry with `manager`.
+    ///
+    /// Save failures are logged; the refreshed session is used either way.
+    pub fn with_session_manager(mut self, manager: SessionManager) -> Self {
+        self.session_manager = Some(manager);
+        self
+    }
+
+    /// Serve library listing pages from `cache` while they are fresh under `policy`.
+    ///
+    /// Artist track and album listings, album track listings, per-track scrobble pages
+    /// (including those read by [`get_scrobble_edit_variations`](Self::get_scrobble_edit_variations))
+    /// and the library-wide artist, track and album listings are cached; recent
+    /// scrobbles, loved tracks and searches are always fetched. Successful edits and
+    /// deletes drop the pages they affect. Cache read and write failures are logged and
+    /// fall back to fetching.
+    pub fn with_response_cache(
+        mut self,
+        cache: Arc<dyn ResponseCache>,
+        policy: CachePolicy,
+    ) -> Self {
+        self.response_cache = Some(cache);
+        self.cache_policy = policy;
+        self
+    }
+
+    /// The cache attached with [`with_response_cache`](Self::with_response_cache), if any.
+    pub fn response_cache(&self) -> Option<Arc<dyn ResponseCache>> {
+        self.response_cache.clone()
+    }
+
+    /// Fetch a library page's status and body, serving it from the response cache while
+    /// it is fresh. Only successful responses are cached.
+    async fn get_cacheable(
+        &self,
+        url: &str,
+        scope: PageScope,
+    ) -> Result<(http_types::StatusCode, String)> {
+        let ttl = self.cache_policy.ttl_for(scope.kind);
+        let cache = self.response_cache.as_ref().filter(|_| !ttl.is_zero());
+
+        if let Some(cache) = cache {
+            let now_ms = now_millis();
+            match cache.get(url) {
+                Ok(Some(page)) if page.age(now_ms) < ttl => {
+                    log::debug!("Serving {url} from the response cache");
+                    self.broadcast_event(ClientEvent::CacheHit {
+                        url: url.to_string(),
+                        kind: scope.kind,
+                        age_ms: page.age(now_ms).as_millis() as u64,
+                    });
+                    return Ok((http_types::StatusCode::Ok, page.body));
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to read {url} from the response cache: {e}"),
+            }
+            self.broadcast_event(ClientEvent::CacheMiss {
+                url: url.to_string(),
+                kind: scope.kind,
+            });
+        }
+
+        let mut response = self.get(url).await?;
+        let status = response.status();
+        let body = response
+            .body_string()
+            .await
+            .map_err(|e| LastFmError::Http(e.to_string()))?;
+
+        if let Some(cache) = cache.filter(|_| status.is_success()) {
+            let page = CachedPage {
+                url: url.to_string(),
+                scope,
+                body: body.clone(),
+                stored_at_ms: now_millis(),
+            };
+            if let Err(e) = cache.put(page) {
+                log::warn!("Failed to store {url} in the response cache: {e}");
+            }
+        }
+        Ok((status, body))
+    }
+
+    /// Drop cached pages that may show scrobbles touched by `changes`.
+    fn invalidate_cached_pages(&self, changes: &[LibraryChange]) {
+        let Some(cache) = &self.response_cache else {
+            return;
+        };
+        let mut seen = Vec::new();
+        for change in changes {
+            if seen.contains(&change) {
+                continue;
+            }
+            seen.push(change);
+            match cache.invalidate(change) {
+                Ok(dropped) => self.broadcast_event(ClientEvent::CacheInvalidated {
+                    change: change.clone(),
+                    dropped,
+                }),
+                Err(e) => log::warn!(
+                    "Failed to invalidate cached pages for '{}': {e}",
+                    change.artist
+                ),
+            }
+        }
+    }
+
+    /// Log in again and swap in the new session.
+    ///
+    /// `stale_cookies` are the cookies the failed request was sent with. If another clone
+    /// has replaced them in the meantime, its session is used without logging in again.
+    async fn refresh_session(&self, stale_cookies: &[String], reason: &str) -> Result<()> {
+        let Some(provider) = &self.credential_provider else {
+            return Err(LastFmError::Auth(reason.to_string()));
+        };
+
+        let _refreshing = self.refresh_lock.lock().await;
+        let base_url = {
+            let session = self.session.lock().unwrap();
+            if session.cookies != stale_cookies {
+                log::debug!("Session was already refreshed by another request");
+                return Ok(());
+            }
+            session.base_url.clone()
+        };
+
+        log::info!("Session expired ({reason}), logging in again");
+        let (username, password) = provider.credentials().await?;
+        let session = LoginManager::new(self.client.clone(), base_url)
+            .login(&username, &password)
+            .await?;
+
+        *self.session.lock().unwrap() = session.clone();
+
+        if let Some(manager) = &self.session_manager {
+            if let Err(e) = manager.save_session(&session) {
+                log::warn!("Failed to save refreshed session: {e}");
+            }
+        }
+
+        self.broadcast_event(ClientEvent::SessionRefreshed {
+            username: session.username,
+            reason: reason.to_string(),
+            refresh_timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        });
+        Ok(())
+    }
+
+    /// Undo a journaled edit by applying its inverse (original and new fields swapped,
+    /// same timestamp). See [`edit_journal::revert_edit`].
+    ///
+    /// Fails with [`LastFmError::EditFailed`] when no journal is attached or the entry
+    /// does not exist.
+    pub async fn revert_edit(&self, journal_id: u64) -> Result<EditResponse> {
+        let journal = self.edit_journal.clone().ok_or_else(|| {
+            LastFmError::EditFailed("No edit journal attached to this client".to_string())
+        })?;
+        edit_journal::revert_edit(self, journal.as_ref(), journal_id).await
+    }
+
     pub fn username(&self) -> String {
         self.session.lock().unwrap().username.clone()
     }
@@ -471,6 +921,7 @@ impl LastFmEditClientImpl {
         );
 
         let request_info = RequestInfo::from_url_and_method(&delete_url, "POST");
+        self.pace(TrafficClass::Delete, &request_info).await?;
         let request_start = std::time::Instant::now();
 
         self.broadcast_event(ClientEvent::RequestStarted {
@@ -504,6 +955,11 @@ impl LastFmEditClientImpl {
 
         if success {
             log::debug!("Successfully deleted scrobble");
+            self.invalidate_cached_pages(&[LibraryChange::new(
+                artist_name,
+                None,
+                Some(track_name),
+            )]);
         } else {
             log::debug!("Delete failed with response: {response_text}");
         }
@@ -511,6 +967,179 @@ impl LastFmEditClientImpl {
         Ok(success)
     }
 
+    /// Love a track, the same as clicking its heart on Last.fm.
+    ///
+    /// Returns whether Last.fm accepted the request. Paced, retried and rate-limit checked
+    /// like a scrobble edit.
+    pub async fn love_track(&self, artist_name: &str, track_name: &str) -> Result<bool> {
+        self.set_track_loved(artist_name, track_name, true).await
+    }
+
+    /// Remove a track from the loved tracks.
+    ///
+    /// Returns whether Last.fm accepted the request.
+    pub async fn unlove_track(&self, artist_name: &str, track_name: &str) -> Result<bool> {
+        self.set_track_loved(artist_name, track_name, false).await
+    }
+
+    async fn set_track_loved(
+        &self,
+        artist_name: &str,
+        track_name: &str,
+        loved: bool,
+    ) -> Result<bool> {
+        // Same retry policy as deletes: non-blocking mode and disabled retries make a single
+        // attempt and propagate rate limits.
+        if self.config.rate_limit_behavior == RateLimitBehavior::ReturnError
+            || !self.config.retry.enabled
+        {
+            return self
+                .set_track_loved_impl(artist_name, track_name, loved)
+                .await;
+        }
+
+        let config = self.config.retry.clone();
+        let operation = if loved { "Love track" } else { "Unlove track" };
+
+        let artist_name = artist_name.to_string();
+        let track_name = track_name.to_string();
+        let client = self.clone();
+
+        match retry::retry_with_backoff_cancelable(
+            config,
+            operation,
+            || client.set_track_loved_impl(&artist_name, &track_name, loved),
+            |delay, rate_limit_timestamp, operation_name| {
+                self.broadcast_event(ClientEvent::RateLimited {
+                    delay_seconds: delay,
+                    request: None,
+                    rate_limit_type: RateLimitType::ResponsePattern,
+                    rate_limit_timestamp,
+                });
+                self.broadcast_event(ClientEvent::Delaying {
+                    delay_ms: delay * 1000,
+                    reason: DelayReason::RetryBackoff,
+                    request: None,
+                    delay_timestamp: rate_limit_timestamp,
+                });
+                log::debug!("{operation_name} rate limited, waiting {delay} seconds");
+            },
+            |total_duration, _operation_name| {
+                self.broadcast_event(ClientEvent::RateLimitEnded {
+                    request: crate::types::RequestInfo::from_url_and_method(
+                        &format!("loved/{artist_name}/{track_name}"),
+                        "POST",
+                    ),
+                    rate_limit_type: RateLimitType::ResponsePattern,
+                    total_rate_limit_duration_seconds: total_duration,
+                });
+            },
+            Some(self.cancel_rx()),
+        )
+        .await
+        {
+            Ok(retry_result) => Ok(retry_result.result),
+            Err(_) => Ok(false),
+        }
+    }
+
+    async fn set_track_loved_impl(
+        &self,
+        artist_name: &str,
+        track_name: &str,
+        loved: bool,
+    ) -> Result<bool> {
+        let (loved_url, session_csrf_token) = {
+            let session = self.session.lock().unwrap();
+            (
+                format!("{}/user/{}/loved", session.base_url, session.username),
+                session.csrf_token.clone(),
+            )
+        };
+
+        let csrf_token = match session_csrf_token {
+            Some(token) => token,
+            None => {
+                log::debug!("No CSRF token in session, fetching one for love/unlove");
+                let library_url = {
+                    let session = self.session.lock().unwrap();
+                    format!("{}/user/{}/library", session.base_url, session.username)
+                };
+                let mut response = self.get(&library_url).await?;
+                let content = response
+                    .body_string()
+                    .await
+                    .map_err(|e| LastFmError::Http(e.to_string()))?;
+                self.extract_csrf_token(&Html::parse_document(&content))?
+            }
+        };
+
+        let mut request = Request::new(Method::Post, loved_url.parse::<Url>().unwrap());
+
+        let referer_url = {
+            let session = self.session.lock().unwrap();
+            headers::add_cookies(&mut request, &session.cookies);
+            format!("{}/user/{}/library", session.base_url, session.username)
+        };
+
+        headers::add_edit_headers(&mut request, &referer_url);
+
+        let form_data = [
+            ("csrfmiddlewaretoken", csrf_token.as_str()),
+            ("action", if loved { "love" } else { "unlove" }),
+            ("track", track_name),
+            ("artist", artist_name),
+            ("ajax", "1"),
+        ];
+
+        let form_string: String = form_data
+            .iter()
+            .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        request.set_body(form_string);
+
+        log::debug!(
+            "{} track: '{track_name}' by '{artist_name}'",
+            if loved { "Loving" } else { "Unloving" }
+        );
+
+        let request_info = RequestInfo::from_url_and_method(&loved_url, "POST");
+        self.pace(TrafficClass::Edit, &request_info).await?;
+        let request_start = std::time::Instant::now();
+
+        self.broadcast_event(ClientEvent::RequestStarted {
+            request: request_info.clone(),
+        });
+
+        let mut response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| LastFmError::Http(e.to_string()))?;
+
+        self.broadcast_event(ClientEvent::RequestCompleted {
+            request: request_info.clone(),
+            status_code: response.status().into(),
+            duration_ms: request_start.elapsed().as_millis() as u64,
+        });
+
+        let response_text = response
+            .body_string()
+            .await
+            .map_err(|e| LastFmError::Http(e.to_string()))?;
+
+        self.check_post_response_for_rate_limit(&loved_url, response.status(), &response_text)?;
+
+        let success = response.status().is_success();
+        if !success {
+            log::debug!("Love/unlove failed with response: {response_text}");
+        }
+
+        Ok(success)
+    }
+
     pub fn subscribe(&self) -> ClientEventReceiver {
         self.broadcaster.subscribe()
     }
@@ -534,7 +1163,30 @@ impl LastFmEditClientImpl {
     }
 
     fn broadcast_event(&self, event: ClientEvent) {
-        self.broadcaster.broadcast_event(event);
+        if self.pacer.is_none() && self.shared_limiter.is_none() {
+            self.broadcaster.broadcast_event(event);
+            return;
+        }
+
+        self.broadcaster.broadcast_event(event.clone());
+        if let Some(pacer) = &self.pacer {
+            self.adapt_pacing(pacer, &event);
+        }
+        // Share detected rate limits (the events that carry their request) with other processes.
+        if let (
+            Some(limiter),
+            ClientEvent::RateLimited {
+                delay_seconds,
+                request: Some(_),
+                rate_limit_type,
+                ..
+            },
+        ) = (&self.shared_limiter, &event)
+        {
+            if let Err(e) = limiter.record_rate_limit(*delay_seconds, rate_limit_type.clone()) {
+                log::warn!("Failed to record rate limit in shared state: {e}");
+            }
+        }
     }
 
     pub async fn get_recent_scrobbles(&self, page: u32) -> Result<Vec<Track>> {
@@ -560,6 +1212,7 @@ impl LastFmEditClientImpl {
         );
 
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::RecentScrobbles, &document, page);
         self.parser.parse_recent_scrobbles(&document)
     }
 
@@ -586,6 +1239,7 @@ impl LastFmEditClientImpl {
         );
 
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::RecentScrobbles, &document, page);
         let tracks = self.parser.parse_recent_scrobbles(&document)?;
         let (has_next_page, total_pages) = self.parser.parse_pagination(&document, page)?;
 
@@ -630,18 +1284,7 @@ impl LastFmEditClientImpl {
         let discovered_edits = self.discover_scrobble_edit_variations(edit).await?;
 
         if discovered_edits.is_empty() {
-            let context = match (&edit.track_name_original, &edit.album_name_original) {
-                (Some(track_name), _) => {
-                    format!("track '{}' by '{}'", track_name, edit.artist_name_original)
-                }
-                (None, Some(album_name)) => {
-                    format!("album '{}' by '{}'", album_name, edit.artist_name_original)
-                }
-                (None, None) => format!("artist '{}'", edit.artist_name_original),
-            };
-            return Err(LastFmError::Parse(format!(
-                "No scrobbles found for {context}. Make sure the names are correct and that you have scrobbled recently."
-            )));
+            return Err(edit.no_scrobbles_error());
         }
 
         let mut all_results = Vec::new();
@@ -655,19 +1298,7 @@ impl LastFmEditClientImpl {
                 discovered_edit.album_name_original
             );
 
-            let mut modified_exact_edit = discovered_edit.clone();
-
-            if let Some(new_track_name) = &edit.track_name {
-                modified_exact_edit.track_name = new_track_name.clone();
-            }
-            if let Some(new_album_name) = &edit.album_name {
-                modified_exact_edit.album_name = new_album_name.clone();
-            }
-            modified_exact_edit.artist_name = edit.artist_name.clone();
-            if let Some(new_album_artist_name) = &edit.album_artist_name {
-                modified_exact_edit.album_artist_name = new_album_artist_name.clone();
-            }
-            modified_exact_edit.edit_all = edit.edit_all;
+            let modified_exact_edit = edit.apply_to(discovered_edit);
 
             let album_info = format!(
                 "{} by {}",
@@ -678,15 +1309,25 @@ impl LastFmEditClientImpl {
             let single_response = self.edit_scrobble_single(&modified_exact_edit, 3).await?;
             let success = single_response.success();
             let message = single_response.message();
+            let (verification, failure_kind) = single_response
+                .individual_results
+                .into_iter()
+                .next()
+                .map(|result| (result.verification, result.failure_kind))
+                .unwrap_or_default();
 
             all_results.push(SingleEditResponse {
                 success,
                 message,
                 album_info: Some(album_info),
                 exact_scrobble_edit: modified_exact_edit.clone(),
+                verification,
+                failure_kind,
             });
 
+            // Adaptive pacing spaces the edit POSTs itself.
             if index < discovered_edits.len() - 1
+                && self.pacer.is_none()
                 && self.config.operational_delays.edit_delay_ms > 0
             {
                 log::info!(
@@ -708,10 +1349,174 @@ impl LastFmEditClientImpl {
             }
         }
 
-        Ok(EditResponse::from_results(all_results))
+        Ok(EditResponse::from_results(all_results))
+    }
+
+    pub async fn edit_scrobble_single(
+        &self,
+        exact_edit: &ExactScrobbleEdit,
+        max_retries: u32,
+    ) -> Result<EditResponse> {
+        let mut response = self
+            .edit_scrobble_single_unverified(exact_edit, max_retries)
+            .await?;
+
+        if self.config.edit_verification.enabled {
+            for result in response.individual_results.iter_mut().filter(|r| r.success) {
+                result.verification = Some(self.verify_edit(&result.exact_scrobble_edit).await);
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Re-fetch an edited scrobble and check that it shows the new metadata.
+    ///
+    /// The scrobble is looked up by timestamp in the most recent pages first; older
+    /// scrobbles are looked up on the library pages of the new and the original track.
+    /// Fetch failures make the result [`EditVerificationStatus::Unverified`] rather than
+    /// an error, since the edit itself has already been submitted.
+    pub async fn verify_edit(&self, exact_edit: &ExactScrobbleEdit) -> EditVerificationStatus {
+        let start_time = std::time::Instant::now();
+        let max_pages = self.config.edit_verification.max_pages;
+
+        let status = match self.find_scrobbles_at(exact_edit, max_pages).await {
+            Ok(rows) if rows.is_empty() => EditVerificationStatus::Unverified {
+                reason: format!(
+                    "No scrobble at timestamp {} in the first {max_pages} recent or track pages",
+                    exact_edit.timestamp
+                ),
+            },
+            Ok(rows) => judge_edit(exact_edit, &rows),
+            Err(error) => EditVerificationStatus::Unverified {
+                reason: format!("Failed to re-fetch scrobble: {error}"),
+            },
+        };
+
+        log::debug!(
+            "Verification of edit at {}: {status:?}",
+            exact_edit.timestamp
+        );
+        self.broadcast_event(ClientEvent::EditVerified {
+            edit: exact_edit.clone(),
+            status: status.clone(),
+            duration_ms: start_time.elapsed().as_millis() as u64,
+        });
+
+        status
+    }
+
+    /// Scrobbles shown at the edit's timestamp, searching at most `max_pages` pages per listing.
+    async fn find_scrobbles_at(
+        &self,
+        exact_edit: &ExactScrobbleEdit,
+        max_pages: u32,
+    ) -> Result<Vec<Track>> {
+        let timestamp = exact_edit.timestamp;
+        let at_timestamp = |tracks: &[Track]| -> Vec<Track> {
+            tracks
+                .iter()
+                .filter(|t| t.timestamp == Some(timestamp))
+                .cloned()
+                .collect()
+        };
+
+        for page in 1..=max_pages {
+            let track_page = self.get_recent_tracks_page(page).await?;
+            let rows = at_timestamp(&track_page.tracks);
+            if !rows.is_empty() {
+                return Ok(rows);
+            }
+            // Pages are newest first: once a page reaches past the timestamp, stop.
+            let passed = track_page
+                .tracks
+                .iter()
+                .filter_map(|t| t.timestamp)
+                .min()
+                .is_some_and(|oldest| oldest < timestamp);
+            if passed || !track_page.has_next_page {
+                break;
+            }
+        }
+
+        let mut track_pages = vec![(&exact_edit.artist_name, &exact_edit.track_name)];
+        if (
+            &exact_edit.artist_name_original,
+            &exact_edit.track_name_original,
+        ) != track_pages[0]
+        {
+            track_pages.push((
+                &exact_edit.artist_name_original,
+                &exact_edit.track_name_original,
+            ));
+        }
+
+        let mut rows = Vec::new();
+        for (artist, track) in track_pages {
+            for page in 1..=max_pages {
+                let track_page = self.get_track_scrobbles_page(artist, track, page).await?;
+                rows.extend(at_timestamp(&track_page.tracks));
+                if !track_page.has_next_page {
+                    break;
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Fetch a page of the individual scrobbles of one track, newest first.
+    ///
+    /// Scrapes `/user/{user}/library/music/{artist}/_/{track}`; every row carries its
+    /// timestamp and the album details from its edit form. A track the user never
+    /// scrobbled yields an empty page.
+    pub async fn get_track_scrobbles_page(
+        &self,
+        artist: &str,
+        track: &str,
+        page: u32,
+    ) -> Result<TrackPage> {
+        let url = {
+            let session = self.session.lock().unwrap();
+            format!(
+                "{}/user/{}/library/music/+noredirect/{}/_/{}?page={}",
+                session.base_url,
+                session.username,
+                urlencoding::encode(artist),
+                urlencoding::encode(track),
+                page
+            )
+        };
+
+        log::debug!("Fetching track scrobbles page {page} for '{track}' by '{artist}'");
+        let (status, content) = self
+            .get_cacheable(
+                &url,
+                PageScope::track(PageKind::RecentScrobbles, artist, track),
+            )
+            .await?;
+        if status == http_types::StatusCode::NotFound {
+            return Ok(TrackPage {
+                tracks: Vec::new(),
+                page_number: page,
+                has_next_page: false,
+                total_pages: Some(0),
+            });
+        }
+
+        let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::RecentScrobbles, &document, page);
+        let tracks = self.parser.parse_recent_scrobbles(&document)?;
+        let (has_next_page, total_pages) = self.parser.parse_pagination(&document, page)?;
+
+        Ok(TrackPage {
+            tracks,
+            page_number: page,
+            has_next_page,
+            total_pages,
+        })
     }
 
-    pub async fn edit_scrobble_single(
+    async fn edit_scrobble_single_unverified(
         &self,
         exact_edit: &ExactScrobbleEdit,
         max_retries: u32,
@@ -729,12 +1534,7 @@ impl LastFmEditClientImpl {
                     exact_edit.clone(),
                 )),
                 Err(rate_limit @ LastFmError::RateLimit { .. }) => Err(rate_limit),
-                Err(error) => Ok(EditResponse::single(
-                    false,
-                    Some(error.to_string()),
-                    None,
-                    exact_edit.clone(),
-                )),
+                Err(error) => Ok(EditResponse::from_error(&error, exact_edit.clone())),
             };
         }
 
@@ -747,12 +1547,7 @@ impl LastFmEditClientImpl {
                     None,
                     exact_edit.clone(),
                 )),
-                Err(error) => Ok(EditResponse::single(
-                    false,
-                    Some(error.to_string()),
-                    None,
-                    exact_edit.clone(),
-                )),
+                Err(error) => Ok(EditResponse::from_error(&error, exact_edit.clone())),
             };
         }
 
@@ -804,18 +1599,17 @@ impl LastFmEditClientImpl {
                 None,
                 exact_edit.clone(),
             )),
-            Err(LastFmError::RateLimit { .. }) => Ok(EditResponse::single(
-                false,
-                Some(format!("Rate limit exceeded after {max_retries} retries")),
-                None,
-                exact_edit.clone(),
-            )),
-            Err(other_error) => Ok(EditResponse::single(
-                false,
-                Some(other_error.to_string()),
-                None,
-                exact_edit.clone(),
-            )),
+            Err(LastFmError::RateLimit { .. }) => {
+                let mut response = EditResponse::single(
+                    false,
+                    Some(format!("Rate limit exceeded after {max_retries} retries")),
+                    None,
+                    exact_edit.clone(),
+                );
+                response.individual_results[0].failure_kind = Some(EditFailureKind::RateLimited);
+                Ok(response)
+            }
+            Err(other_error) => Ok(EditResponse::from_error(&other_error, exact_edit.clone())),
         }
     }
 
@@ -824,6 +1618,41 @@ impl LastFmEditClientImpl {
         let result = self.edit_scrobble_impl_internal(exact_edit).await;
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
+        if let Ok(true) = result {
+            self.invalidate_cached_pages(&[
+                LibraryChange::new(
+                    &exact_edit.artist_name_original,
+                    Some(&exact_edit.album_name_original),
+                    Some(&exact_edit.track_name_original),
+                ),
+                LibraryChange::new(
+                    &exact_edit.album_artist_name_original,
+                    Some(&exact_edit.album_name_original),
+                    Some(&exact_edit.track_name_original),
+                ),
+                LibraryChange::new(
+                    &exact_edit.artist_name,
+                    Some(&exact_edit.album_name),
+                    Some(&exact_edit.track_name),
+                ),
+                LibraryChange::new(
+                    &exact_edit.album_artist_name,
+                    Some(&exact_edit.album_name),
+                    Some(&exact_edit.track_name),
+                ),
+            ]);
+        }
+
+        if let (Ok(true), Some(journal)) = (&result, &self.edit_journal) {
+            if let Err(e) = journal.record(exact_edit) {
+                log::warn!(
+                    "Failed to record edit of '{}' by '{}' in edit journal: {e}",
+                    exact_edit.track_name_original,
+                    exact_edit.artist_name_original
+                );
+            }
+        }
+
         match &result {
             Ok(success) => {
                 self.broadcast_event(ClientEvent::EditAttempted {
@@ -894,6 +1723,7 @@ impl LastFmEditClientImpl {
         request.set_body(form_string);
 
         let request_info = RequestInfo::from_url_and_method(&edit_url, "POST");
+        self.pace(TrafficClass::Edit, &request_info).await?;
         let request_start = std::time::Instant::now();
 
         self.broadcast_event(ClientEvent::RequestStarted {
@@ -925,7 +1755,17 @@ impl LastFmEditClientImpl {
 
         let analysis = edit_analysis::analyze_edit_response(&response_text, response.status());
 
-        Ok(analysis.success)
+        match analysis.failure_kind {
+            None => Ok(true),
+            // An alert like "too many edits" is a rate limit the page patterns did not catch.
+            Some(EditFailureKind::RateLimited) => Err(self.post_rate_limited(&edit_url)),
+            Some(kind) => Err(LastFmError::EditRejected {
+                kind,
+                message: analysis
+                    .error_text
+                    .unwrap_or_else(|| format!("HTTP {}", response.status())),
+            }),
+        }
     }
 
     async fn get_edit_form_html(&self, edit_url: &str) -> Result<String> {
@@ -986,6 +1826,7 @@ impl LastFmEditClientImpl {
             )
         };
 
+        let track_scope = PageScope::track(PageKind::RecentScrobbles, artist_name, track_name);
         let mut all_scrobble_edits = Vec::new();
         let max_pages = 5;
 
@@ -1029,11 +1870,7 @@ impl LastFmEditClientImpl {
             let url = build_track_page_url(root, 1, ajax_param);
             log::debug!("Fetching track page: {url}");
 
-            let mut response = self.get(&url).await?;
-            let html = response
-                .body_string()
-                .await
-                .map_err(|e| crate::LastFmError::Http(e.to_string()))?;
+            let (_, html) = self.get_cacheable(&url, track_scope.clone()).await?;
             last_tried_url = Some(url.clone());
             last_tried_html = Some(html.clone());
             let parsed = Html::parse_document(&html);
@@ -1109,11 +1946,7 @@ impl LastFmEditClientImpl {
 
             log::debug!("Fetching page {page} for additional album variations");
 
-            let mut response = self.get(&page_url).await?;
-            let html = response
-                .body_string()
-                .await
-                .map_err(|e| crate::LastFmError::Http(e.to_string()))?;
+            let (_, html) = self.get_cacheable(&page_url, track_scope.clone()).await?;
 
             let document = Html::parse_document(&html);
 
@@ -1285,35 +2118,62 @@ impl LastFmEditClientImpl {
     }
 
     pub async fn get_artist_tracks_page(&self, artist: &str, page: u32) -> Result<TrackPage> {
+        self.get_artist_tracks_page_in_range(artist, LibraryDateRange::AllTime, page)
+            .await
+    }
+
+    /// Fetch a page of an artist's tracks, counting only scrobbles within `range`.
+    pub async fn get_artist_tracks_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<TrackPage> {
         let url = {
             let session = self.session.lock().unwrap();
             format!(
-                "{}/user/{}/library/music/{}/+tracks?page={}&ajax=true",
+                "{}/user/{}/library/music/{}/+tracks?page={}&ajax=true{}",
                 session.base_url,
                 session.username,
                 urlencoding::encode(artist),
-                page
+                page,
+                range_suffix(&range)
             )
         };
 
-        log::debug!("Fetching tracks page {page} for artist: {artist}");
-        let mut response = self.get(&url).await?;
-        let content = response
-            .body_string()
-            .await
-            .map_err(|e| LastFmError::Http(e.to_string()))?;
+        log::debug!("Fetching tracks page {page} for artist: {artist} ({range})");
+        let (status, content) = self
+            .get_cacheable(&url, PageScope::artist(PageKind::ArtistTracks, artist))
+            .await?;
 
-        log::debug!(
-            "AJAX response: {} status, {} chars",
-            response.status(),
-            content.len()
-        );
+        log::debug!("AJAX response: {status} status, {} chars", content.len());
 
         log::debug!("Parsing HTML response from AJAX endpoint");
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::ArtistTracks, &document, page);
         self.parser.parse_tracks_page(&document, page, artist, None)
     }
 
+    /// The parse report of the most recently fetched page of `kind`, if any.
+    pub fn last_parse_report(&self, kind: PageKind) -> Option<ParseReport> {
+        self.parse_reports.lock().unwrap().get(&kind).cloned()
+    }
+
+    /// Check a fetched page against its parser and report drift.
+    fn check_parse_health(&self, kind: PageKind, document: &Html, page: u32) {
+        let report = self.parser.check_page(kind, document, page);
+        if !report.is_healthy() {
+            log::warn!(
+                "Possible Last.fm markup change on {kind} page {page}: {:?}",
+                report.anomalies
+            );
+            self.broadcast_event(ClientEvent::ParseDrift {
+                report: report.clone(),
+            });
+        }
+        self.parse_reports.lock().unwrap().insert(kind, report);
+    }
+
     pub fn extract_tracks_from_document(
         &self,
         document: &Html,
@@ -1347,6 +2207,17 @@ impl LastFmEditClientImpl {
     }
 
     pub async fn get(&self, url: &str) -> Result<Response> {
+        let sent_cookies = self.session.lock().unwrap().cookies.clone();
+        match self.get_once(url).await {
+            Err(LastFmError::Auth(reason)) if self.credential_provider.is_some() => {
+                self.refresh_session(&sent_cookies, &reason).await?;
+                self.get_once(url).await
+            }
+            result => result,
+        }
+    }
+
+    async fn get_once(&self, url: &str) -> Result<Response> {
         // In non-blocking mode we never sleep/retry internally on rate limits, regardless of
         // the retry configuration. Detection still broadcasts RateLimited events and updates
         // the shared rate-limit state inside `get_without_retry`.
@@ -1366,6 +2237,18 @@ impl LastFmEditClientImpl {
         let status = response.status();
         let body = self.extract_response_body(url, &mut response).await?;
 
+        // With a credential provider attached, a logged-out rendering of our own library is
+        // reported as an expired session so `get` can log in again and retry.
+        if self.credential_provider.is_some()
+            && status.is_success()
+            && self.is_own_library_url(url)
+            && page_indicates_logged_out(&body)
+        {
+            return Err(LastFmError::Auth(format!(
+                "session expired: {url} was rendered logged out"
+            )));
+        }
+
         // Only scan the body for rate-limit patterns on non-success responses. Last.fm
         // serves its "Rate Limited" interstitial with a non-success status (e.g. 503), so
         // scanning 2xx bodies is unnecessary and false-positives on ordinary pages whose
@@ -1402,11 +2285,25 @@ impl LastFmEditClientImpl {
 
         let url_string = url.to_string();
         let client = self.clone();
+        // Page loads are safe to repeat, so a 5xx is retried too. If it persists, callers get
+        // the last response and handle the status as they would without retries.
+        let server_error: Mutex<Option<Response>> = Mutex::new(None);
 
-        let retry_result = retry::retry_with_backoff_cancelable(
+        let retry_result = retry::retry_idempotent_with_backoff_cancelable(
             config,
             &format!("GET {url}"),
-            || client.get_without_retry(&url_string),
+            || async {
+                let response = client.get_without_retry(&url_string).await?;
+                let status = response.status();
+                if !status.is_server_error() {
+                    return Ok(response);
+                }
+                *server_error.lock().unwrap() = Some(response);
+                Err(LastFmError::EditRejected {
+                    kind: EditFailureKind::ServerError,
+                    message: format!("HTTP {status}"),
+                })
+            },
             |delay, rate_limit_timestamp, operation_name| {
                 self.broadcast_event(ClientEvent::RateLimited {
                     delay_seconds: delay,
@@ -1431,9 +2328,19 @@ impl LastFmEditClientImpl {
             },
             Some(self.cancel_rx()),
         )
-        .await?;
+        .await;
 
-        Ok(retry_result.result)
+        match retry_result {
+            Ok(retry_result) => Ok(retry_result.result),
+            Err(LastFmError::EditRejected {
+                kind: EditFailureKind::ServerError,
+                ..
+            }) => Ok(server_error
+                .into_inner()
+                .unwrap()
+                .expect("a server error stores its response")),
+            Err(error) => Err(error),
+        }
     }
 
     async fn get_with_redirects(&self, url: &str, redirect_count: u32) -> Result<Response> {
@@ -1467,11 +2374,7 @@ impl LastFmEditClientImpl {
             request: request_info.clone(),
         });
 
-        // Optional throttle for heavy library scanning. Kept cancelable so callers can stop.
-        if self.config.operational_delays.get_delay_ms > 0 {
-            self.sleep_ms(self.config.operational_delays.get_delay_ms)
-                .await?;
-        }
+        self.pace(TrafficClass::Get, &request_info).await?;
 
         let mut response = self
             .client
@@ -1491,14 +2394,14 @@ impl LastFmEditClientImpl {
             if let Some(location) = response.header("location") {
                 if let Some(redirect_url) = location.get(0) {
                     let redirect_url_str = redirect_url.as_str();
-                    if url.contains("page=") {
+                    if url.contains("page=") || self.credential_provider.is_some() {
                         log::debug!("Following redirect from {url} to {redirect_url_str}");
 
                         if redirect_url_str.contains("/login") {
-                            log::debug!("Redirect to login page - authentication failed for paginated request");
-                            return Err(LastFmError::Auth(
-                                "Session expired or invalid for paginated request".to_string(),
-                            ));
+                            log::debug!("Redirect to login page - authentication failed for {url}");
+                            return Err(LastFmError::Auth(format!(
+                                "Session expired or invalid: {url} redirected to login"
+                            )));
                         }
                     }
 
@@ -1642,20 +2545,25 @@ impl LastFmEditClientImpl {
     ) -> Result<()> {
         if self.response_indicates_rate_limit(status, body) {
             log::debug!("POST response body contains rate limit patterns (status {status})");
-            self.broadcast_event(ClientEvent::RateLimited {
-                delay_seconds: 60,
-                request: Some(RequestInfo::from_url_and_method(url, "POST")),
-                rate_limit_type: RateLimitType::ResponsePattern,
-                rate_limit_timestamp: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs(),
-            });
-            return Err(LastFmError::RateLimit { retry_after: 60 });
+            return Err(self.post_rate_limited(url));
         }
         Ok(())
     }
 
+    /// Broadcast a pattern-detected rate limit on a POST and build the matching error.
+    fn post_rate_limited(&self, url: &str) -> LastFmError {
+        self.broadcast_event(ClientEvent::RateLimited {
+            delay_seconds: 60,
+            request: Some(RequestInfo::from_url_and_method(url, "POST")),
+            rate_limit_type: RateLimitType::ResponsePattern,
+            rate_limit_timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        });
+        LastFmError::RateLimit { retry_after: 60 }
+    }
+
     fn is_rate_limit_response(&self, response_body: &str) -> bool {
         let rate_limit_config = &self.config.rate_limit;
 
@@ -1700,60 +2608,159 @@ impl LastFmEditClientImpl {
     }
 
     pub async fn get_artists_page(&self, page: u32) -> Result<crate::ArtistPage> {
+        self.get_artists_page_in_range(LibraryDateRange::AllTime, page)
+            .await
+    }
+
+    /// Fetch a page of the library's artists, counting only scrobbles within `range`.
+    pub async fn get_artists_page_in_range(
+        &self,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<crate::ArtistPage> {
         let url = {
             let session = self.session.lock().unwrap();
             format!(
-                "{}/user/{}/library/artists?page={}",
-                session.base_url, session.username, page
+                "{}/user/{}/library/artists?page={}{}",
+                session.base_url,
+                session.username,
+                page,
+                range_suffix(&range)
             )
         };
 
-        log::debug!("Fetching artists page {page}");
-        let mut response = self.get(&url).await?;
-        let content = response
-            .body_string()
-            .await
-            .map_err(|e| LastFmError::Http(e.to_string()))?;
+        log::debug!("Fetching artists page {page} ({range})");
+        let (status, content) = self
+            .get_cacheable(&url, PageScope::library(PageKind::Artists))
+            .await?;
 
         log::debug!(
-            "Artist library response: {} status, {} chars",
-            response.status(),
+            "Artist library response: {status} status, {} chars",
             content.len()
         );
 
         log::debug!("Parsing HTML response from artist library endpoint");
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::Artists, &document, page);
         self.parser.parse_artists_page(&document, page)
     }
 
     pub async fn get_artist_albums_page(&self, artist: &str, page: u32) -> Result<AlbumPage> {
+        self.get_artist_albums_page_in_range(artist, LibraryDateRange::AllTime, page)
+            .await
+    }
+
+    /// Fetch a page of an artist's albums, counting only scrobbles within `range`.
+    pub async fn get_artist_albums_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<AlbumPage> {
         let url = {
             let session = self.session.lock().unwrap();
             format!(
-                "{}/user/{}/library/music/{}/+albums?page={}&ajax=true",
+                "{}/user/{}/library/music/{}/+albums?page={}&ajax=true{}",
                 session.base_url,
                 session.username,
                 urlencoding::encode(artist),
-                page
+                page,
+                range_suffix(&range)
+            )
+        };
+
+        log::debug!("Fetching albums page {page} for artist: {artist} ({range})");
+        let (status, content) = self
+            .get_cacheable(&url, PageScope::artist(PageKind::ArtistAlbums, artist))
+            .await?;
+
+        log::debug!("AJAX response: {status} status, {} chars", content.len());
+
+        log::debug!("Parsing HTML response from AJAX endpoint");
+        let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::ArtistAlbums, &document, page);
+        self.parser.parse_albums_page(&document, page, artist)
+    }
+
+    /// Fetch a page of the user's loved tracks (`/user/{user}/loved`), most recently loved
+    /// first.
+    pub async fn get_loved_tracks_page(&self, page: u32) -> Result<TrackPage> {
+        let url = {
+            let session = self.session.lock().unwrap();
+            format!(
+                "{}/user/{}/loved?page={}",
+                session.base_url, session.username, page
             )
         };
 
-        log::debug!("Fetching albums page {page} for artist: {artist}");
+        log::debug!("Fetching loved tracks page {page}");
         let mut response = self.get(&url).await?;
         let content = response
             .body_string()
             .await
             .map_err(|e| LastFmError::Http(e.to_string()))?;
 
-        log::debug!(
-            "AJAX response: {} status, {} chars",
-            response.status(),
-            content.len()
-        );
+        let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::LovedTracks, &document, page);
+        self.parser.parse_loved_tracks_page(&document, page)
+    }
+
+    /// Fetch a page of every track in the library (`/user/{user}/library/tracks`).
+    pub async fn get_library_tracks_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<TrackPage> {
+        let url = {
+            let session = self.session.lock().unwrap();
+            format!(
+                "{}/user/{}/library/tracks?page={}{}{}",
+                session.base_url,
+                session.username,
+                page,
+                range_suffix(&range),
+                sort_suffix(sort)
+            )
+        };
+
+        log::debug!("Fetching library tracks page {page} ({range}, by {sort:?})");
+        let (_, content) = self
+            .get_cacheable(&url, PageScope::library(PageKind::LibraryTracks))
+            .await?;
 
-        log::debug!("Parsing HTML response from AJAX endpoint");
         let document = Html::parse_document(&content);
-        self.parser.parse_albums_page(&document, page, artist)
+        self.check_parse_health(PageKind::LibraryTracks, &document, page);
+        self.parser.parse_library_tracks_page(&document, page)
+    }
+
+    /// Fetch a page of every album in the library (`/user/{user}/library/albums`).
+    pub async fn get_library_albums_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<AlbumPage> {
+        let url = {
+            let session = self.session.lock().unwrap();
+            format!(
+                "{}/user/{}/library/albums?page={}{}{}",
+                session.base_url,
+                session.username,
+                page,
+                range_suffix(&range),
+                sort_suffix(sort)
+            )
+        };
+
+        log::debug!("Fetching library albums page {page} ({range}, by {sort:?})");
+        let (_, content) = self
+            .get_cacheable(&url, PageScope::library(PageKind::LibraryAlbums))
+            .await?;
+
+        let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::LibraryAlbums, &document, page);
+        self.parser.parse_library_albums_page(&document, page)
     }
 
     pub async fn get_album_tracks_page(
@@ -1777,20 +2784,18 @@ impl LastFmEditClientImpl {
         log::debug!("Fetching tracks page {page} for album '{album_name}' by '{artist_name}'");
         log::debug!("🔗 Album URL: {url}");
 
-        let mut response = self.get(&url).await?;
-        let content = response
-            .body_string()
-            .await
-            .map_err(|e| LastFmError::Http(e.to_string()))?;
+        let (status, content) = self
+            .get_cacheable(
+                &url,
+                PageScope::album(PageKind::ArtistTracks, artist_name, album_name),
+            )
+            .await?;
 
-        log::debug!(
-            "AJAX response: {} status, {} chars",
-            response.status(),
-            content.len()
-        );
+        log::debug!("AJAX response: {status} status, {} chars", content.len());
 
         log::debug!("Parsing HTML response from AJAX endpoint");
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::ArtistTracks, &document, page);
         let result =
             self.parser
                 .parse_tracks_page(&document, page, artist_name, Some(album_name))?;
@@ -1847,6 +2852,7 @@ impl LastFmEditClientImpl {
         );
 
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::TrackSearch, &document, page);
         let tracks = self.parser.parse_track_search_results(&document)?;
 
         // For search results, we need to determine pagination differently
@@ -1887,6 +2893,7 @@ impl LastFmEditClientImpl {
         );
 
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::AlbumSearch, &document, page);
         let albums = self.parser.parse_album_search_results(&document)?;
 
         // For search results, we need to determine pagination differently
@@ -1926,6 +2933,7 @@ impl LastFmEditClientImpl {
         );
 
         let document = Html::parse_document(&content);
+        self.check_parse_health(PageKind::ArtistSearch, &document, page);
         let artists = self.parser.parse_artist_search_results(&document)?;
 
         // For search results, we need to determine pagination differently
@@ -1949,6 +2957,7 @@ impl LastFmEditClientImpl {
                 self.username(),
                 key.clone(),
             )
+            .with_client_config(self.config.clone())
         })
     }
 
@@ -1972,6 +2981,65 @@ impl LastFmBaseClient for LastFmEditClientImpl {
         self.get_artist_albums_page(artist, page).await
     }
 
+    async fn get_track_scrobbles_page(
+        &self,
+        artist: &str,
+        track: &str,
+        page: u32,
+    ) -> Result<TrackPage> {
+        self.get_track_scrobbles_page(artist, track, page).await
+    }
+
+    async fn get_loved_tracks_page(&self, page: u32) -> Result<TrackPage> {
+        self.get_loved_tracks_page(page).await
+    }
+
+    async fn get_library_tracks_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<TrackPage> {
+        self.get_library_tracks_page(range, sort, page).await
+    }
+
+    async fn get_library_albums_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<AlbumPage> {
+        self.get_library_albums_page(range, sort, page).await
+    }
+
+    async fn get_artists_page_in_range(
+        &self,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<crate::ArtistPage> {
+        self.get_artists_page_in_range(range, page).await
+    }
+
+    async fn get_artist_tracks_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<TrackPage> {
+        self.get_artist_tracks_page_in_range(artist, range, page)
+            .await
+    }
+
+    async fn get_artist_albums_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<AlbumPage> {
+        self.get_artist_albums_page_in_range(artist, range, page)
+            .await
+    }
+
     async fn get_album_tracks_page(
         &self,
         album_name: &str,
@@ -2073,6 +3141,14 @@ impl LastFmEditClient for LastFmEditClientImpl {
             .await
     }
 
+    async fn love_track(&self, artist_name: &str, track_name: &str) -> Result<bool> {
+        self.love_track(artist_name, track_name).await
+    }
+
+    async fn unlove_track(&self, artist_name: &str, track_name: &str) -> Result<bool> {
+        self.unlove_track(artist_name, track_name).await
+    }
+
     async fn get_scrobble_edit_variations(
         &self,
         track_name: &str,
@@ -2138,6 +3214,72 @@ impl LastFmEditClient for LastFmEditClientImpl {
         ))
     }
 
+    fn track_scrobbles(
+        &self,
+        artist: &str,
+        track: &str,
+    ) -> Box<dyn crate::AsyncPaginatedIterator<Track>> {
+        Box::new(crate::TrackScrobblesIterator::new(
+            self.clone(),
+            artist.to_string(),
+            track.to_string(),
+        ))
+    }
+
+    fn loved_tracks(&self) -> Box<dyn crate::AsyncPaginatedIterator<Track>> {
+        Box::new(crate::LovedTracksIterator::new(self.clone()))
+    }
+
+    fn library_tracks(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    ) -> Box<dyn crate::AsyncPaginatedIterator<Track>> {
+        Box::new(crate::LibraryTracksIterator::new(self.clone(), range, sort))
+    }
+
+    fn library_albums(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    ) -> Box<dyn crate::AsyncPaginatedIterator<crate::Album>> {
+        Box::new(crate::LibraryAlbumsIterator::new(self.clone(), range, sort))
+    }
+
+    fn artists_in_range(
+        &self,
+        range: LibraryDateRange,
+    ) -> Box<dyn crate::AsyncPaginatedIterator<crate::Artist>> {
+        Box::new(crate::iterator::ArtistsIterator::with_range(
+            self.clone(),
+            range,
+        ))
+    }
+
+    fn artist_tracks_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+    ) -> Box<dyn crate::AsyncPaginatedIterator<Track>> {
+        Box::new(crate::iterator::ArtistTracksDirectIterator::with_range(
+            self.clone(),
+            artist.to_string(),
+            range,
+        ))
+    }
+
+    fn artist_albums_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+    ) -> Box<dyn crate::AsyncPaginatedIterator<crate::Album>> {
+        Box::new(crate::ArtistAlbumsIterator::with_range(
+            self.clone(),
+            artist.to_string(),
+            range,
+        ))
+    }
+
     fn album_tracks(
         &self,
         album_name: &str,
@@ -1,4 +1,5 @@
-use crate::{LastFmEditClient, Track};
+use crate::api::MAX_SCROBBLES_PER_REQUEST;
+use crate::{ApiScrobble, LastFmApiClientImpl, LastFmEditClient, Track};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -26,6 +27,41 @@ pub struct DeleteManifestEntry {
     pub track: String,
     pub album: Option<String>,
     pub timestamp: u64,
+    /// Outcome of deleting this entry, once the manifest has been executed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_result: Option<DeleteAttemptResult>,
+    /// Outcome of restoring this entry with [`restore_manifest`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restore_result: Option<RestoreAttemptResult>,
+}
+
+impl DeleteManifestEntry {
+    /// Whether [`restore_manifest`] should (re-)submit this entry.
+    ///
+    /// Only entries recorded as deleted qualify, unless `include_unrecorded` also admits
+    /// entries with no recorded delete outcome (manifests executed before outcomes were
+    /// recorded). Entries already restored or permanently ignored by Last.fm are skipped,
+    /// so an interrupted restore can simply be run again.
+    pub fn is_restorable(&self, include_unrecorded: bool) -> bool {
+        let deleted = match &self.delete_result {
+            Some(result) => result.success(),
+            None => include_unrecorded,
+        };
+        deleted
+            && !matches!(
+                self.restore_result,
+                Some(RestoreAttemptResult::Restored { .. } | RestoreAttemptResult::Ignored { .. })
+            )
+    }
+
+    /// The scrobble to submit when restoring this entry.
+    pub fn to_api_scrobble(&self) -> ApiScrobble {
+        let scrobble = ApiScrobble::new(&self.artist, &self.track, self.timestamp);
+        match &self.album {
+            Some(album) if !album.is_empty() => scrobble.with_album(album),
+            _ => scrobble,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,7 +73,8 @@ pub struct DeleteTarget {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
 pub enum DeleteAttemptResult {
     Deleted,
     NotDeleted { message: String },
@@ -57,6 +94,36 @@ impl DeleteAttemptResult {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RestoreAttemptResult {
+    Restored {
+        restored_at_unix: u64,
+    },
+    /// Last.fm accepted the request but did not record the scrobble (e.g. code 3 for
+    /// timestamps older than two weeks); retrying will not help
+    Ignored {
+        code: u32,
+        message: String,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl RestoreAttemptResult {
+    pub fn success(&self) -> bool {
+        matches!(self, Self::Restored { .. })
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Self::Restored { .. } => None,
+            Self::Ignored { message, .. } | Self::Error { message } => Some(message),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DeleteExecutionSummary {
     pub total_found: usize,
@@ -64,6 +131,13 @@ pub struct DeleteExecutionSummary {
     pub failed_deletions: usize,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreExecutionSummary {
+    pub total_candidates: usize,
+    pub restored: usize,
+    pub ignored: usize,
+}
+
 pub fn target_from_track(track: &Track, offset: Option<u64>, timestamp: u64) -> DeleteTarget {
     DeleteTarget {
         offset,
@@ -95,11 +169,35 @@ impl DeleteManifest {
                     track: target.track.clone(),
                     album: target.album.clone(),
                     timestamp: target.timestamp,
+                    delete_result: None,
+                    restore_result: None,
                 })
                 .collect(),
         })
     }
 
+    /// Record the outcome of deleting the entry with the given 1-based `index`.
+    ///
+    /// A recorded successful deletion is never overwritten by a later failure: re-running
+    /// a manifest reports already-deleted scrobbles as missing, and the entry must stay
+    /// restorable.
+    pub fn record_delete_result(&mut self, index: usize, result: DeleteAttemptResult) {
+        if let Some(entry) = self.scrobbles.iter_mut().find(|e| e.index == index) {
+            if entry.delete_result.as_ref().is_some_and(|r| r.success()) && !result.success() {
+                return;
+            }
+            entry.delete_result = Some(result);
+        }
+    }
+
+    /// Entries [`restore_manifest`] would submit; see [`DeleteManifestEntry::is_restorable`].
+    pub fn restore_candidates(&self, include_unrecorded: bool) -> Vec<&DeleteManifestEntry> {
+        self.scrobbles
+            .iter()
+            .filter(|e| e.is_restorable(include_unrecorded))
+            .collect()
+    }
+
     pub fn targets(&self) -> Vec<DeleteTarget> {
         self.scrobbles
             .iter()
@@ -136,9 +234,17 @@ pub fn write_manifest(
     targets: &[DeleteTarget],
 ) -> crate::Result<()> {
     let manifest = DeleteManifest::new(source, targets)?;
-    let json = serde_json::to_string_pretty(&manifest)
+    save_manifest(path, &manifest)
+}
+
+/// Write `manifest` to `path`, replacing the file atomically so an interrupted run never
+/// leaves a truncated manifest behind.
+pub fn save_manifest(path: &Path, manifest: &DeleteManifest) -> crate::Result<()> {
+    let json = serde_json::to_string_pretty(manifest)
         .map_err(|e| crate::LastFmError::Parse(e.to_string()))?;
-    fs::write(path, format!("{json}\n"))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, format!("{json}\n"))?;
+    fs::rename(&tmp_path, path)?;
     Ok(())
 }
 
@@ -203,3 +309,84 @@ where
         failed_deletions,
     })
 }
+
+/// Re-submit the scrobbles a manifest deleted, with their original timestamps.
+///
+/// Restorable entries (see [`DeleteManifestEntry::is_restorable`]) are sent through the
+/// API's `track.scrobble` endpoint in batches, so `client` needs an API secret and session
+/// key. Each entry's [`RestoreAttemptResult`] is written back to the manifest after every
+/// batch. If a batch fails, its entries are recorded as errors and the error is returned;
+/// running the restore again resumes with the entries that are still pending.
+///
+/// Last.fm ignores scrobbles older than two weeks, so only recent deletions can be
+/// restored; older entries are recorded as [`RestoreAttemptResult::Ignored`].
+pub async fn restore_manifest<F>(
+    client: &LastFmApiClientImpl,
+    manifest_path: &Path,
+    include_unrecorded: bool,
+    mut on_attempt: F,
+) -> crate::Result<RestoreExecutionSummary>
+where
+    F: FnMut(&DeleteManifestEntry, &RestoreAttemptResult),
+{
+    let mut manifest = read_manifest(manifest_path)?;
+    let candidates: Vec<usize> = manifest
+        .scrobbles
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_restorable(include_unrecorded))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut summary = RestoreExecutionSummary {
+        total_candidates: candidates.len(),
+        ..Default::default()
+    };
+
+    for batch in candidates.chunks(MAX_SCROBBLES_PER_REQUEST) {
+        let scrobbles: Vec<ApiScrobble> = batch
+            .iter()
+            .map(|&i| manifest.scrobbles[i].to_api_scrobble())
+            .collect();
+
+        let outcome = client.scrobble(&scrobbles).await;
+        let restored_at_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        for (&i, scrobble) in batch.iter().zip(&scrobbles) {
+            let result = match &outcome {
+                Ok(batch_result) => match batch_result
+                    .ignored
+                    .iter()
+                    .find(|ignored| &ignored.scrobble == scrobble)
+                {
+                    Some(ignored) => {
+                        summary.ignored += 1;
+                        RestoreAttemptResult::Ignored {
+                            code: ignored.code,
+                            message: ignored.message.clone(),
+                        }
+                    }
+                    None => {
+                        summary.restored += 1;
+                        RestoreAttemptResult::Restored { restored_at_unix }
+                    }
+                },
+                Err(e) => RestoreAttemptResult::Error {
+                    message: e.to_string(),
+                },
+            };
+
+            let entry = &mut manifest.scrobbles[i];
+            entry.restore_result = Some(result.clone());
+            on_attempt(entry, &result);
+        }
+
+        save_manifest(manifest_path, &manifest)?;
+        outcome?;
+    }
+
+    Ok(summary)
+}
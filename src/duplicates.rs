@@ -0,0 +1,167 @@
+//! Detection of double-submitted scrobbles.
+//!
+//! Some scrobblers submit the same play twice, leaving identical scrobbles (same artist,
+//! track and album) a few seconds apart. [`DuplicateFinder`] groups such plays from a
+//! newest-first stream of [`Track`]s; each [`DuplicateGroup`] keeps the oldest copy and
+//! lists the rest as [`DeleteTarget`]s, ready for
+//! [`write_manifest`](crate::delete_manifest::write_manifest).
+
+use crate::delete_manifest::DeleteTarget;
+use crate::{LastFmEditClient, Track};
+use std::collections::HashMap;
+
+/// Settings for [`DuplicateFinder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateFinderConfig {
+    /// Identical plays at most this many seconds after the kept copy are duplicates
+    pub window_secs: u64,
+}
+
+impl Default for DuplicateFinderConfig {
+    fn default() -> Self {
+        Self { window_secs: 30 }
+    }
+}
+
+/// One real play and the duplicate copies submitted shortly after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// The copy to keep: the earliest scrobble of the group
+    pub kept: DeleteTarget,
+    /// The later copies, newest first
+    pub duplicates: Vec<DeleteTarget>,
+}
+
+type PlayKey = (String, String, Option<String>);
+
+/// Incremental duplicate finder over a newest-first track stream.
+///
+/// Feed tracks in the order the recent-tracks pages list them with
+/// [`push`](Self::push), then call [`finish`](Self::finish). Identical plays need not be
+/// adjacent: another scrobbler's plays can be interleaved between the copies.
+#[derive(Debug)]
+pub struct DuplicateFinder {
+    config: DuplicateFinderConfig,
+    /// Plays seen so far for each key, newest first, in the group still being extended
+    open: HashMap<PlayKey, Vec<DeleteTarget>>,
+    groups: Vec<DuplicateGroup>,
+    next_offset: u64,
+}
+
+impl DuplicateFinder {
+    pub fn new(config: DuplicateFinderConfig) -> Self {
+        Self {
+            config,
+            open: HashMap::new(),
+            groups: Vec::new(),
+            next_offset: 0,
+        }
+    }
+
+    /// Add the next (older) track. Its offset is its position in the stream.
+    ///
+    /// Tracks without a timestamp (now playing) still advance the offset but are never
+    /// grouped.
+    pub fn push(&mut self, track: &Track) {
+        let offset = self.next_offset;
+        self.next_offset += 1;
+
+        let Some(timestamp) = track.timestamp else {
+            return;
+        };
+        let key = (
+            track.artist.clone(),
+            track.name.clone(),
+            track.album.clone(),
+        );
+        let target = DeleteTarget {
+            offset: Some(offset),
+            artist: track.artist.clone(),
+            track: track.name.clone(),
+            album: track.album.clone(),
+            timestamp,
+        };
+
+        let window = self.config.window_secs;
+        if let Some(plays) = self.open.get_mut(&key) {
+            // The newest play of the group must be within the window of this older one.
+            let newest = plays[0].timestamp;
+            if newest.saturating_sub(timestamp) <= window {
+                plays.push(target);
+                return;
+            }
+            let plays = self.open.remove(&key).unwrap_or_default();
+            self.close(plays);
+        }
+        self.open.insert(key, vec![target]);
+    }
+
+    /// Add every track of a page, in order.
+    pub fn extend<'a>(&mut self, tracks: impl IntoIterator<Item = &'a Track>) {
+        for track in tracks {
+            self.push(track);
+        }
+    }
+
+    /// The duplicate groups found, ordered by the offset of their newest copy.
+    pub fn finish(mut self) -> Vec<DuplicateGroup> {
+        for (_, plays) in std::mem::take(&mut self.open) {
+            self.close(plays);
+        }
+        self.groups
+            .sort_by_key(|g| g.duplicates.first().and_then(|d| d.offset));
+        self.groups
+    }
+
+    fn close(&mut self, mut plays: Vec<DeleteTarget>) {
+        if plays.len() < 2 {
+            return;
+        }
+        let kept = plays.pop().expect("group has at least two plays");
+        self.groups.push(DuplicateGroup {
+            kept,
+            duplicates: plays,
+        });
+    }
+}
+
+/// Find duplicate groups in a newest-first track list.
+pub fn find_duplicates(tracks: &[Track], config: &DuplicateFinderConfig) -> Vec<DuplicateGroup> {
+    let mut finder = DuplicateFinder::new(config.clone());
+    finder.extend(tracks);
+    finder.finish()
+}
+
+/// Every duplicate copy across `groups`, ordered by offset — the scrobbles to delete.
+pub fn duplicate_targets(groups: &[DuplicateGroup]) -> Vec<DeleteTarget> {
+    let mut targets: Vec<DeleteTarget> = groups
+        .iter()
+        .flat_map(|g| g.duplicates.iter().cloned())
+        .collect();
+    targets.sort_by_key(|t| t.offset);
+    targets
+}
+
+/// Scan the first `pages` pages of recent scrobbles for duplicates.
+pub async fn scan_recent_pages<C>(
+    client: &C,
+    pages: u32,
+    config: &DuplicateFinderConfig,
+) -> crate::Result<Vec<DuplicateGroup>>
+where
+    C: LastFmEditClient + ?Sized,
+{
+    let mut finder = DuplicateFinder::new(config.clone());
+    for page in 1..=pages {
+        let track_page = client.get_recent_tracks_page(page).await?;
+        log::debug!(
+            "Scanning page {page}: {} scrobbles",
+            track_page.tracks.len()
+        );
+        finder.extend(&track_page.tracks);
+        if !track_page.has_next_page || track_page.tracks.is_empty() {
+            break;
+        }
+    }
+    Ok(finder.finish())
+}
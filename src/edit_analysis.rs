@@ -1,3 +1,4 @@
+use crate::types::EditFailureKind;
 use http_types::StatusCode;
 use scraper::{Html, Selector};
 
@@ -12,6 +13,10 @@ pub struct EditAnalysisResult {
     pub actual_track_name: Option<String>,
     /// Album name found in the response (if any)
     pub actual_album_name: Option<String>,
+    /// Text of the error alert in the response (if any)
+    pub error_text: Option<String>,
+    /// Classified reason for the failure (`None` when the edit succeeded)
+    pub failure_kind: Option<EditFailureKind>,
 }
 
 /// Analyze the HTML response from a Last.fm edit request to determine success/failure
@@ -51,14 +56,25 @@ pub fn analyze_edit_response(response_text: &str, status_code: StatusCode) -> Ed
     // Determine if edit was truly successful
     let final_success = status_code.is_success() && has_success_alert && !has_error_alert;
 
+    let error_text = document
+        .select(&error_selector)
+        .next()
+        .map(|element| element.text().collect::<String>().trim().to_string());
+
+    let failure_kind = if final_success {
+        None
+    } else {
+        Some(classify_edit_failure(
+            error_text.as_deref().unwrap_or(""),
+            status_code,
+        ))
+    };
+
     // Create detailed message
     let message = if has_error_alert {
         // Extract error message
-        if let Some(error_element) = document.select(&error_selector).next() {
-            Some(format!(
-                "Edit failed: {}",
-                error_element.text().collect::<String>().trim()
-            ))
+        if let Some(error_text) = &error_text {
+            Some(format!("Edit failed: {error_text}"))
         } else {
             Some("Edit failed with unknown error".to_string())
         }
@@ -77,6 +93,45 @@ pub fn analyze_edit_response(response_text: &str, status_code: StatusCode) -> Ed
         message,
         actual_track_name,
         actual_album_name,
+        error_text,
+        failure_kind,
+    }
+}
+
+/// Classify a failed edit from its error alert text and HTTP status.
+///
+/// The status is checked first (403 means the CSRF token or session was rejected, 429 a
+/// rate limit, 5xx a server error); otherwise the alert text is matched against the
+/// phrasings Last.fm uses for each failure. Anything unrecognized is
+/// [`EditFailureKind::Unknown`].
+pub fn classify_edit_failure(error_text: &str, status_code: StatusCode) -> EditFailureKind {
+    match status_code {
+        StatusCode::Forbidden => return EditFailureKind::CsrfExpired,
+        StatusCode::TooManyRequests => return EditFailureKind::RateLimited,
+        status if status.is_server_error() => return EditFailureKind::ServerError,
+        _ => {}
+    }
+
+    let text = error_text.to_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+
+    if mentions(&["csrf", "session has expired", "log in again"]) {
+        EditFailureKind::CsrfExpired
+    } else if mentions(&[
+        "edit limit",
+        "maximum number of edits",
+        "quota",
+        "edits per",
+    ]) {
+        EditFailureKind::QuotaReached
+    } else if mentions(&["too many", "rate limit", "slow down", "try again later"]) {
+        EditFailureKind::RateLimited
+    } else if mentions(&["could not be found", "not found", "no longer exists"]) {
+        EditFailureKind::ScrobbleNotFound
+    } else if mentions(&["cannot be empty", "invalid", "too long", "must be"]) {
+        EditFailureKind::InvalidField
+    } else {
+        EditFailureKind::Unknown
     }
 }
 
@@ -185,12 +240,53 @@ mod tests {
 
         let result = analyze_edit_response(html, StatusCode::Ok);
         assert!(!result.success);
+        assert_eq!(result.failure_kind, Some(EditFailureKind::InvalidField));
         assert!(result
             .message
             .unwrap()
             .contains("Edit failed: Invalid data"));
     }
 
+    #[test]
+    fn test_classify_edit_failure() {
+        let cases = [
+            (
+                "The scrobble you are trying to edit could not be found.",
+                StatusCode::Ok,
+                EditFailureKind::ScrobbleNotFound,
+            ),
+            (
+                "Track name and artist name cannot be empty.",
+                StatusCode::Ok,
+                EditFailureKind::InvalidField,
+            ),
+            (
+                "CSRF verification failed.",
+                StatusCode::Forbidden,
+                EditFailureKind::CsrfExpired,
+            ),
+            (
+                "You have reached your edit limit for today.",
+                StatusCode::Ok,
+                EditFailureKind::QuotaReached,
+            ),
+            (
+                "Too many edits, please slow down.",
+                StatusCode::Ok,
+                EditFailureKind::RateLimited,
+            ),
+            ("", StatusCode::BadGateway, EditFailureKind::ServerError),
+            (
+                "Something odd happened.",
+                StatusCode::Ok,
+                EditFailureKind::Unknown,
+            ),
+        ];
+        for (text, status, expected) in cases {
+            assert_eq!(classify_edit_failure(text, status), expected, "{text}");
+        }
+    }
+
     #[test]
     fn test_extract_from_regex_patterns() {
         let html = r#"
@@ -0,0 +1,340 @@
+//! Durable record of applied scrobble edits, with one-call revert.
+//!
+//! Every edit that Last.fm confirms can be recorded into an [`EditJournal`]. A journal
+//! entry keeps the full [`ExactScrobbleEdit`], so [`revert_edit`] can submit its
+//! [`inverse`](ExactScrobbleEdit::inverse) later without re-discovering anything.
+//!
+//! [`FileEditJournal`] (JSON lines on disk) is the default backend; [`InMemoryEditJournal`]
+//! is useful in tests. Attach a journal to a client with
+//! [`LastFmEditClientImpl::with_edit_journal`](crate::LastFmEditClientImpl::with_edit_journal).
+
+use crate::{EditResponse, ExactScrobbleEdit, LastFmEditClient, LastFmError, SessionManager};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single applied edit stored in an [`EditJournal`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditJournalEntry {
+    /// Journal-assigned identifier, unique within one journal
+    pub id: u64,
+    /// When the edit was applied (seconds since the Unix epoch)
+    pub applied_at_unix: u64,
+    /// The edit exactly as it was submitted
+    pub edit: ExactScrobbleEdit,
+    /// When the edit was reverted through [`revert_edit`], if it has been
+    #[serde(default)]
+    pub reverted_at_unix: Option<u64>,
+}
+
+impl EditJournalEntry {
+    /// Whether this entry has already been reverted.
+    pub fn is_reverted(&self) -> bool {
+        self.reverted_at_unix.is_some()
+    }
+}
+
+/// Criteria for [`EditJournal::list`]. All set criteria must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditJournalFilter {
+    /// Only entries applied at or after this time
+    pub since: Option<u64>,
+    /// Only entries applied at or before this time
+    pub until: Option<u64>,
+    /// Only entries whose original or new artist matches (case-insensitive)
+    pub artist: Option<String>,
+    /// Whether to include entries that were already reverted
+    pub include_reverted: bool,
+}
+
+impl EditJournalFilter {
+    /// A filter matching every entry, including reverted ones.
+    pub fn all() -> Self {
+        Self {
+            include_reverted: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_since(mut self, since: u64) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn with_until(mut self, until: u64) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn with_artist(mut self, artist: &str) -> Self {
+        self.artist = Some(artist.to_string());
+        self
+    }
+
+    pub fn with_include_reverted(mut self, include_reverted: bool) -> Self {
+        self.include_reverted = include_reverted;
+        self
+    }
+
+    /// Check whether an entry satisfies this filter.
+    pub fn matches(&self, entry: &EditJournalEntry) -> bool {
+        if !self.include_reverted && entry.is_reverted() {
+            return false;
+        }
+        if self
+            .since
+            .is_some_and(|since| entry.applied_at_unix < since)
+        {
+            return false;
+        }
+        if self
+            .until
+            .is_some_and(|until| entry.applied_at_unix > until)
+        {
+            return false;
+        }
+        if let Some(artist) = &self.artist {
+            let artist = artist.to_lowercase();
+            if entry.edit.artist_name_original.to_lowercase() != artist
+                && entry.edit.artist_name.to_lowercase() != artist
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Storage backend for applied edits.
+///
+/// Implementations must be safe to share between client clones; the client records into
+/// the journal from whichever clone performed the edit.
+pub trait EditJournal: Send + Sync {
+    /// Record a successfully applied edit and return the stored entry.
+    fn record(&self, edit: &ExactScrobbleEdit) -> crate::Result<EditJournalEntry>;
+
+    /// Look up an entry by id.
+    fn get(&self, id: u64) -> crate::Result<Option<EditJournalEntry>>;
+
+    /// List entries matching `filter`, oldest first.
+    fn list(&self, filter: &EditJournalFilter) -> crate::Result<Vec<EditJournalEntry>>;
+
+    /// Mark an entry as reverted at `reverted_at_unix`.
+    fn mark_reverted(&self, id: u64, reverted_at_unix: u64) -> crate::Result<()>;
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn next_entry(entries: &[EditJournalEntry], edit: &ExactScrobbleEdit) -> EditJournalEntry {
+    EditJournalEntry {
+        id: entries.iter().map(|e| e.id).max().unwrap_or(0) + 1,
+        applied_at_unix: now_unix(),
+        edit: edit.clone(),
+        reverted_at_unix: None,
+    }
+}
+
+fn missing_entry(id: u64) -> LastFmError {
+    LastFmError::EditFailed(format!("No edit journal entry with id {id}"))
+}
+
+/// Journal kept in memory; entries are lost when it is dropped.
+#[derive(Debug, Default)]
+pub struct InMemoryEditJournal {
+    entries: Mutex<Vec<EditJournalEntry>>,
+}
+
+impl InMemoryEditJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EditJournal for InMemoryEditJournal {
+    fn record(&self, edit: &ExactScrobbleEdit) -> crate::Result<EditJournalEntry> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = next_entry(&entries, edit);
+        entries.push(entry.clone());
+        Ok(entry)
+    }
+
+    fn get(&self, id: u64) -> crate::Result<Option<EditJournalEntry>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.iter().find(|e| e.id == id).cloned())
+    }
+
+    fn list(&self, filter: &EditJournalFilter) -> crate::Result<Vec<EditJournalEntry>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .iter()
+            .filter(|e| filter.matches(e))
+            .cloned()
+            .collect())
+    }
+
+    fn mark_reverted(&self, id: u64, reverted_at_unix: u64) -> crate::Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| missing_entry(id))?;
+        entry.reverted_at_unix = Some(reverted_at_unix);
+        Ok(())
+    }
+}
+
+/// Journal stored as JSON lines, one [`EditJournalEntry`] per line.
+///
+/// New entries are appended; marking an entry reverted rewrites the file.
+#[derive(Debug)]
+pub struct FileEditJournal {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileEditJournal {
+    /// Use (or create on first write) the journal file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// The default per-user journal next to the saved session:
+    /// `~/.local/share/{app_name}/users/{username}/edit_journal.jsonl`
+    pub fn for_user(manager: &SessionManager, username: &str) -> crate::Result<Self> {
+        Ok(Self::new(
+            manager.get_user_dir(username)?.join("edit_journal.jsonl"),
+        ))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_entries(&self) -> crate::Result<Vec<EditJournalEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| {
+                    LastFmError::Parse(format!(
+                        "Invalid edit journal line in '{}': {e}",
+                        self.path.display()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    fn write_entries(&self, entries: &[EditJournalEntry]) -> crate::Result<()> {
+        let mut contents = String::new();
+        for entry in entries {
+            contents.push_str(&to_json_line(entry)?);
+        }
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+fn to_json_line(entry: &EditJournalEntry) -> crate::Result<String> {
+    let json = serde_json::to_string(entry).map_err(|e| LastFmError::Parse(e.to_string()))?;
+    Ok(format!("{json}\n"))
+}
+
+impl EditJournal for FileEditJournal {
+    fn record(&self, edit: &ExactScrobbleEdit) -> crate::Result<EditJournalEntry> {
+        let _guard = self.lock.lock().unwrap();
+        let entry = next_entry(&self.read_entries()?, edit);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(to_json_line(&entry)?.as_bytes())?;
+        Ok(entry)
+    }
+
+    fn get(&self, id: u64) -> crate::Result<Option<EditJournalEntry>> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self.read_entries()?.into_iter().find(|e| e.id == id))
+    }
+
+    fn list(&self, filter: &EditJournalFilter) -> crate::Result<Vec<EditJournalEntry>> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self
+            .read_entries()?
+            .into_iter()
+            .filter(|e| filter.matches(e))
+            .collect())
+    }
+
+    fn mark_reverted(&self, id: u64, reverted_at_unix: u64) -> crate::Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut entries = self.read_entries()?;
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| missing_entry(id))?;
+        entry.reverted_at_unix = Some(reverted_at_unix);
+        self.write_entries(&entries)
+    }
+}
+
+/// Revert a journaled edit by applying its inverse.
+///
+/// The inverse swaps original and new fields and keeps the timestamp and `edit_all`
+/// flag. When the inverse succeeds the entry is marked reverted; reverting an entry
+/// twice is rejected. If the original edit used `edit_all`, the inverse also renames
+/// any scrobbles that already carried the new metadata before the edit.
+pub async fn revert_edit<C>(
+    client: &C,
+    journal: &dyn EditJournal,
+    journal_id: u64,
+) -> crate::Result<EditResponse>
+where
+    C: LastFmEditClient + ?Sized,
+{
+    let entry = journal
+        .get(journal_id)?
+        .ok_or_else(|| missing_entry(journal_id))?;
+
+    if let Some(reverted_at) = entry.reverted_at_unix {
+        return Err(LastFmError::EditFailed(format!(
+            "Edit journal entry {journal_id} was already reverted at {reverted_at}"
+        )));
+    }
+
+    let inverse = entry.edit.inverse();
+    log::info!(
+        "Reverting journal entry {journal_id}: '{}' by '{}' -> '{}' by '{}'",
+        entry.edit.track_name,
+        entry.edit.artist_name,
+        inverse.track_name,
+        inverse.artist_name
+    );
+
+    let response = client.edit_scrobble_single(&inverse, 3).await?;
+    if response.all_successful() {
+        journal.mark_reverted(journal_id, now_unix())?;
+    }
+    Ok(response)
+}
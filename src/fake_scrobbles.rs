@@ -0,0 +1,338 @@
+//! Heuristic detection of runaway or unattended playback in recent scrobbles.
+//!
+//! A player left running (a looping album, a queue that kept going overnight, a client
+//! re-submitting the same plays) leaves recognizable shapes in the scrobble history.
+//! [`detect_fake_scrobbles`] scans a newest-first track list — the order
+//! [`RecentTracksIterator`](crate::RecentTracksIterator) yields — and returns ranked
+//! [`SuspiciousBlock`]s of contiguous offsets. Nothing is deleted: write the blocks with
+//! [`write_blocks_manifest`], review the manifest, then run
+//! `lastfm-edit delete --manifest <path> --apply`.
+
+use crate::delete_manifest::{write_manifest, DeleteManifestSource, DeleteTarget};
+use crate::{LastFmEditClient, Track};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Thresholds for [`detect_fake_scrobbles`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeScrobbleDetectorConfig {
+    /// Consecutive plays closer together than this (seconds) are implausible
+    pub min_plausible_gap_secs: u64,
+    /// Number of consecutive implausible gaps needed to flag a block
+    pub min_rapid_gaps: usize,
+    /// A track repeated this many times within one uninterrupted run of the same album
+    /// flags the run as a loop
+    pub min_album_loop_repeats: usize,
+    /// Gaps longer than this (seconds) split listening sessions
+    pub max_session_gap_secs: u64,
+    /// Start of the overnight window, as a local hour (inclusive)
+    pub overnight_start_hour: u64,
+    /// End of the overnight window, as a local hour (exclusive); may wrap past midnight
+    pub overnight_end_hour: u64,
+    /// Offset of the listener's local time from UTC, in seconds
+    pub utc_offset_secs: i64,
+    /// Plays inside the overnight window within one session needed to flag a streak
+    pub min_overnight_plays: usize,
+}
+
+impl Default for FakeScrobbleDetectorConfig {
+    fn default() -> Self {
+        Self {
+            min_plausible_gap_secs: 30,
+            min_rapid_gaps: 3,
+            min_album_loop_repeats: 3,
+            max_session_gap_secs: 20 * 60,
+            overnight_start_hour: 1,
+            overnight_end_hour: 6,
+            utc_offset_secs: 0,
+            min_overnight_plays: 15,
+        }
+    }
+}
+
+impl FakeScrobbleDetectorConfig {
+    pub fn with_utc_offset_secs(mut self, utc_offset_secs: i64) -> Self {
+        self.utc_offset_secs = utc_offset_secs;
+        self
+    }
+
+    fn is_overnight(&self, timestamp: u64) -> bool {
+        let local = timestamp as i64 + self.utc_offset_secs;
+        let hour = (local.rem_euclid(86_400) / 3_600) as u64;
+        if self.overnight_start_hour <= self.overnight_end_hour {
+            (self.overnight_start_hour..self.overnight_end_hour).contains(&hour)
+        } else {
+            hour >= self.overnight_start_hour || hour < self.overnight_end_hour
+        }
+    }
+}
+
+/// Why a block was flagged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum SuspicionReason {
+    /// The same album played uninterrupted, with at least one track repeated `repeats` times
+    AlbumLoop {
+        artist: String,
+        album: String,
+        repeats: usize,
+    },
+    /// Plays spaced closer together than any plausible track length
+    ImplausibleSpacing { shortest_gap_secs: u64 },
+    /// An uninterrupted session with `plays` scrobbles during overnight hours
+    OvernightStreak { plays: usize },
+}
+
+/// A contiguous range of recent offsets that looks like fake playback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspiciousBlock {
+    /// Offset of the most recent play in the block (0 = most recent scrobble)
+    pub start_offset: u64,
+    /// Offset of the oldest play in the block (inclusive)
+    pub end_offset: u64,
+    /// The timestamped plays in the block, newest first
+    pub plays: Vec<DeleteTarget>,
+    pub reasons: Vec<SuspicionReason>,
+    /// Ranking score: plays in the block times the number of independent reasons
+    pub score: usize,
+}
+
+impl SuspiciousBlock {
+    /// The block as `lastfm-edit delete --recent-offset` range syntax.
+    pub fn offset_range(&self) -> String {
+        format!("{}-{}", self.start_offset, self.end_offset)
+    }
+}
+
+/// A timestamped play at a position in the recent-tracks list.
+struct Play<'a> {
+    offset: u64,
+    timestamp: u64,
+    track: &'a Track,
+}
+
+/// Candidate range over indices into the timestamped play list (inclusive).
+struct Candidate {
+    first: usize,
+    last: usize,
+    reason: SuspicionReason,
+}
+
+/// Scan a newest-first track list for suspicious blocks, ranked by descending score.
+///
+/// Offsets are positions in `tracks`, matching `lastfm-edit show` and
+/// `delete --recent-offset`. Tracks without a timestamp (now playing) keep their offset
+/// but are never part of a block.
+pub fn detect_fake_scrobbles(
+    tracks: &[Track],
+    config: &FakeScrobbleDetectorConfig,
+) -> Vec<SuspiciousBlock> {
+    let plays: Vec<Play> = tracks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, track)| {
+            Some(Play {
+                offset: i as u64,
+                timestamp: track.timestamp?,
+                track,
+            })
+        })
+        .collect();
+
+    let mut candidates = Vec::new();
+    candidates.extend(rapid_runs(&plays, config));
+    candidates.extend(album_loops(&plays, config));
+    candidates.extend(overnight_streaks(&plays, config));
+
+    let mut blocks = merge_candidates(&plays, candidates);
+    blocks.sort_by_key(|b| (std::cmp::Reverse(b.score), b.start_offset));
+    blocks
+}
+
+/// Fetch up to `limit` recent scrobbles and run [`detect_fake_scrobbles`] over them.
+pub async fn scan_recent_tracks<C>(
+    client: &C,
+    limit: usize,
+    config: &FakeScrobbleDetectorConfig,
+) -> crate::Result<Vec<SuspiciousBlock>>
+where
+    C: LastFmEditClient + ?Sized,
+{
+    let tracks = client.recent_tracks().take(limit).await?;
+    log::info!(
+        "Scanned {} recent scrobbles for fake playback",
+        tracks.len()
+    );
+    Ok(detect_fake_scrobbles(&tracks, config))
+}
+
+/// Write the plays of `blocks` as a delete manifest for review.
+///
+/// Plays are ordered by offset and deduplicated; the manifest source records the
+/// detector and the offset ranges it flagged.
+pub fn write_blocks_manifest(path: &Path, blocks: &[SuspiciousBlock]) -> crate::Result<()> {
+    let mut targets: Vec<DeleteTarget> = blocks.iter().flat_map(|b| b.plays.clone()).collect();
+    targets.sort_by_key(|t| t.offset);
+    targets.dedup_by_key(|t| t.offset);
+
+    let mut ranges: Vec<&SuspiciousBlock> = blocks.iter().collect();
+    ranges.sort_by_key(|b| b.start_offset);
+    let range = ranges
+        .iter()
+        .map(|b| b.offset_range())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    write_manifest(
+        path,
+        DeleteManifestSource {
+            kind: "fake-scrobble-detector".to_string(),
+            range: Some(range),
+        },
+        &targets,
+    )
+}
+
+fn gap(plays: &[Play], i: usize) -> u64 {
+    plays[i].timestamp.saturating_sub(plays[i + 1].timestamp)
+}
+
+fn rapid_runs(plays: &[Play], config: &FakeScrobbleDetectorConfig) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mut i = 0;
+    while i + 1 < plays.len() {
+        if gap(plays, i) >= config.min_plausible_gap_secs {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let mut shortest = u64::MAX;
+        while i + 1 < plays.len() && gap(plays, i) < config.min_plausible_gap_secs {
+            shortest = shortest.min(gap(plays, i));
+            i += 1;
+        }
+        // Gaps start..i cover plays start..=i.
+        if i - start >= config.min_rapid_gaps {
+            candidates.push(Candidate {
+                first: start,
+                last: i,
+                reason: SuspicionReason::ImplausibleSpacing {
+                    shortest_gap_secs: shortest,
+                },
+            });
+        }
+    }
+    candidates
+}
+
+fn album_key(track: &Track) -> Option<(String, String)> {
+    let album = track.album.as_deref()?.trim();
+    if album.is_empty() {
+        return None;
+    }
+    Some((track.artist.to_lowercase(), album.to_lowercase()))
+}
+
+fn album_loops(plays: &[Play], config: &FakeScrobbleDetectorConfig) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mut start = 0;
+    while start < plays.len() {
+        let Some(key) = album_key(plays[start].track) else {
+            start += 1;
+            continue;
+        };
+        let mut end = start;
+        while end + 1 < plays.len()
+            && gap(plays, end) <= config.max_session_gap_secs
+            && album_key(plays[end + 1].track).as_ref() == Some(&key)
+        {
+            end += 1;
+        }
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for play in &plays[start..=end] {
+            *counts.entry(play.track.name.to_lowercase()).or_default() += 1;
+        }
+        let repeats = counts.values().copied().max().unwrap_or(0);
+        if repeats >= config.min_album_loop_repeats {
+            let track = plays[start].track;
+            candidates.push(Candidate {
+                first: start,
+                last: end,
+                reason: SuspicionReason::AlbumLoop {
+                    artist: track.artist.clone(),
+                    album: track.album.clone().unwrap_or_default(),
+                    repeats,
+                },
+            });
+        }
+        start = end + 1;
+    }
+    candidates
+}
+
+fn overnight_streaks(plays: &[Play], config: &FakeScrobbleDetectorConfig) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mut start = 0;
+    while start < plays.len() {
+        let mut end = start;
+        while end + 1 < plays.len() && gap(plays, end) <= config.max_session_gap_secs {
+            end += 1;
+        }
+
+        let overnight: Vec<usize> = (start..=end)
+            .filter(|&i| config.is_overnight(plays[i].timestamp))
+            .collect();
+        if overnight.len() >= config.min_overnight_plays {
+            candidates.push(Candidate {
+                first: overnight[0],
+                last: overnight[overnight.len() - 1],
+                reason: SuspicionReason::OvernightStreak {
+                    plays: overnight.len(),
+                },
+            });
+        }
+        start = end + 1;
+    }
+    candidates
+}
+
+/// Union overlapping candidates into blocks, combining their reasons.
+fn merge_candidates(plays: &[Play], mut candidates: Vec<Candidate>) -> Vec<SuspiciousBlock> {
+    candidates.sort_by_key(|c| (c.first, c.last));
+
+    let mut merged: Vec<(usize, usize, Vec<SuspicionReason>)> = Vec::new();
+    for candidate in candidates {
+        match merged.last_mut() {
+            Some((_, last, reasons)) if candidate.first <= *last => {
+                *last = (*last).max(candidate.last);
+                reasons.push(candidate.reason);
+            }
+            _ => merged.push((candidate.first, candidate.last, vec![candidate.reason])),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(first, last, reasons)| {
+            let block_plays: Vec<DeleteTarget> = plays[first..=last]
+                .iter()
+                .map(|play| DeleteTarget {
+                    offset: Some(play.offset),
+                    artist: play.track.artist.clone(),
+                    track: play.track.name.clone(),
+                    album: play.track.album.clone(),
+                    timestamp: play.timestamp,
+                })
+                .collect();
+            SuspiciousBlock {
+                start_offset: plays[first].offset,
+                end_offset: plays[last].offset,
+                score: block_plays.len() * reasons.len(),
+                plays: block_plays,
+                reasons,
+            }
+        })
+        .collect()
+}
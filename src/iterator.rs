@@ -1,8 +1,14 @@
 use crate::api::LastFmApiClient;
 use crate::r#trait::LastFmBaseClient;
-use crate::{Album, AlbumPage, Result, Track, TrackPage};
+use crate::{
+    Album, AlbumPage, LastFmError, LibraryDateRange, LibrarySort, Result, Track, TrackPage,
+};
 
 use async_trait::async_trait;
+use futures::stream::{self, LocalBoxStream};
+use futures::{future, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
 /// Async iterator trait for paginated Last.fm data.
 ///
@@ -68,6 +74,34 @@ pub trait AsyncPaginatedIterator<T> {
     fn total_pages(&self) -> Option<u32> {
         None // Default implementation returns None
     }
+
+    /// Get a serializable cursor for the iterator's current position.
+    ///
+    /// Items already returned by [`next`](Self::next) are behind the cursor, so an
+    /// iterator rebuilt from it (e.g. with [`IteratorCursor::track_iterator`]) carries on
+    /// with the next item. Returns `None` for iterators that cannot be resumed.
+    fn cursor(&self) -> Option<IteratorCursor> {
+        None
+    }
+}
+
+#[async_trait(?Send)]
+impl<T, I: AsyncPaginatedIterator<T> + ?Sized> AsyncPaginatedIterator<T> for Box<I> {
+    async fn next(&mut self) -> Result<Option<T>> {
+        (**self).next().await
+    }
+
+    fn current_page(&self) -> u32 {
+        (**self).current_page()
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        (**self).total_pages()
+    }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        (**self).cursor()
+    }
 }
 
 /// Iterator for browsing an artist's tracks from a user's library.
@@ -86,6 +120,10 @@ pub struct ArtistTracksIterator<C: LastFmBaseClient> {
     current_album_tracks: Option<AlbumTracksIterator<C>>,
     track_buffer: Vec<Track>,
     finished: bool,
+    /// Page and offset of the current album in the artist's album listing
+    album_position: (u32, usize),
+    /// Tracks to skip in the next album, when resuming from a cursor
+    album_track_skip: usize,
 }
 
 #[async_trait(?Send)]
@@ -110,18 +148,22 @@ impl<C: LastFmBaseClient + Clone> AsyncPaginatedIterator<Track> for ArtistTracks
 
                 // Get next album
                 if let Some(ref mut album_iter) = self.album_iterator {
+                    let position = album_iter.position();
                     if let Some(album) = album_iter.next().await? {
+                        self.album_position = position;
                         log::debug!(
                             "Processing album '{}' for artist '{}'",
                             album.name,
                             self.artist
                         );
                         // Create album tracks iterator for this album
-                        self.current_album_tracks = Some(AlbumTracksIterator::new(
+                        let mut album_tracks = AlbumTracksIterator::new(
                             self.client.clone(),
                             album.name.clone(),
                             self.artist.clone(),
-                        ));
+                        );
+                        album_tracks.index = std::mem::take(&mut self.album_track_skip);
+                        self.current_album_tracks = Some(album_tracks);
                     } else {
                         // No more albums, we're done
                         log::debug!("No more albums for artist '{}'", self.artist);
@@ -168,6 +210,29 @@ impl<C: LastFmBaseClient + Clone> AsyncPaginatedIterator<Track> for ArtistTracks
             None
         }
     }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        // Point at the album being walked, or at the next one between albums
+        let ((page, offset), album_track_offset) = match &self.current_album_tracks {
+            Some(album_tracks) => (self.album_position, album_tracks.index),
+            None => (
+                self.album_iterator
+                    .as_ref()
+                    .map_or(self.album_position, |album_iter| album_iter.position()),
+                self.album_track_skip,
+            ),
+        };
+        Some(IteratorCursor {
+            kind: CursorKind::ArtistTracks {
+                artist: self.artist.clone(),
+            },
+            page,
+            offset,
+            album_track_offset,
+            stop_at_timestamp: None,
+            last_timestamp: None,
+        })
+    }
 }
 
 impl<C: LastFmBaseClient + Clone> ArtistTracksIterator<C> {
@@ -182,6 +247,8 @@ impl<C: LastFmBaseClient + Clone> ArtistTracksIterator<C> {
             current_album_tracks: None,
             track_buffer: Vec::new(),
             finished: false,
+            album_position: (1, 0),
+            album_track_skip: 0,
         }
     }
 }
@@ -196,9 +263,12 @@ impl<C: LastFmBaseClient + Clone> ArtistTracksIterator<C> {
 pub struct ArtistTracksDirectIterator<C: LastFmBaseClient> {
     client: C,
     artist: String,
+    range: LibraryDateRange,
     current_page: u32,
     has_more: bool,
     buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
     total_pages: Option<u32>,
     tracks_yielded: u32,
 }
@@ -209,8 +279,12 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for ArtistTracksDirectIt
         // If buffer is empty, try to load next page
         if self.buffer.is_empty() {
             if let Some(page) = self.next_page().await? {
-                self.buffer = page.tracks;
-                self.buffer.reverse(); // Reverse so we can pop from end efficiently
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
         }
 
@@ -226,6 +300,19 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for ArtistTracksDirectIt
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::ArtistTracksDirect {
+                artist: self.artist.clone(),
+                range: self.range,
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
@@ -236,12 +323,22 @@ impl<C: LastFmBaseClient> ArtistTracksDirectIterator<C> {
     ///
     /// This is typically called via [`LastFmBaseClient::artist_tracks_direct`](crate::LastFmBaseClient::artist_tracks_direct).
     pub fn new(client: C, artist: String) -> Self {
+        Self::with_range(client, artist, LibraryDateRange::AllTime)
+    }
+
+    /// Create a direct artist tracks iterator over the tracks scrobbled within `range`.
+    ///
+    /// This is typically called via [`LastFmEditClient::artist_tracks_in_range`](crate::LastFmEditClient::artist_tracks_in_range).
+    pub fn with_range(client: C, artist: String, range: LibraryDateRange) -> Self {
         Self {
             client,
             artist,
+            range,
             current_page: 1,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             total_pages: None,
             tracks_yielded: 0,
         }
@@ -262,10 +359,15 @@ impl<C: LastFmBaseClient> ArtistTracksDirectIterator<C> {
             self.tracks_yielded
         );
 
-        let page = self
-            .client
-            .get_artist_tracks_page(&self.artist, self.current_page)
-            .await?;
+        let page = if self.range.is_all_time() {
+            self.client
+                .get_artist_tracks_page(&self.artist, self.current_page)
+                .await?
+        } else {
+            self.client
+                .get_artist_tracks_page_in_range(&self.artist, self.range, self.current_page)
+                .await?
+        };
 
         self.has_more = page.has_next_page;
         self.current_page += 1;
@@ -289,9 +391,12 @@ impl<C: LastFmBaseClient> ArtistTracksDirectIterator<C> {
 pub struct ArtistAlbumsIterator<C: LastFmBaseClient> {
     client: C,
     artist: String,
+    range: LibraryDateRange,
     current_page: u32,
     has_more: bool,
     buffer: Vec<Album>,
+    page_len: usize,
+    skip: usize,
     total_pages: Option<u32>,
 }
 
@@ -301,8 +406,12 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Album> for ArtistAlbumsIterator
         // If buffer is empty, try to load next page
         if self.buffer.is_empty() {
             if let Some(page) = self.next_page().await? {
-                self.buffer = page.albums;
-                self.buffer.reverse(); // Reverse so we can pop from end efficiently
+                fill_buffer(
+                    &mut self.buffer,
+                    page.albums,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
         }
 
@@ -313,6 +422,19 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Album> for ArtistAlbumsIterator
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::ArtistAlbums {
+                artist: self.artist.clone(),
+                range: self.range,
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
@@ -323,16 +445,36 @@ impl<C: LastFmBaseClient> ArtistAlbumsIterator<C> {
     ///
     /// This is typically called via [`LastFmBaseClient::artist_albums`](crate::LastFmBaseClient::artist_albums).
     pub fn new(client: C, artist: String) -> Self {
+        Self::with_range(client, artist, LibraryDateRange::AllTime)
+    }
+
+    /// Create an artist albums iterator over the albums scrobbled within `range`.
+    ///
+    /// This is typically called via [`LastFmEditClient::artist_albums_in_range`](crate::LastFmEditClient::artist_albums_in_range).
+    pub fn with_range(client: C, artist: String, range: LibraryDateRange) -> Self {
         Self {
             client,
             artist,
+            range,
             current_page: 1,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             total_pages: None,
         }
     }
 
+    /// Page and offset of the next album to be returned.
+    fn position(&self) -> (u32, usize) {
+        page_position(
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        )
+    }
+
     /// Fetch the next page of albums.
     ///
     /// This method handles pagination automatically and includes rate limiting.
@@ -341,10 +483,15 @@ impl<C: LastFmBaseClient> ArtistAlbumsIterator<C> {
             return Ok(None);
         }
 
-        let page = self
-            .client
-            .get_artist_albums_page(&self.artist, self.current_page)
-            .await?;
+        let page = if self.range.is_all_time() {
+            self.client
+                .get_artist_albums_page(&self.artist, self.current_page)
+                .await?
+        } else {
+            self.client
+                .get_artist_albums_page_in_range(&self.artist, self.range, self.current_page)
+                .await?
+        };
 
         self.has_more = page.has_next_page;
         self.current_page += 1;
@@ -371,14 +518,20 @@ pub struct RecentTracksIterator<C: LastFmBaseClient> {
     current_page: u32,
     has_more: bool,
     buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
     stop_at_timestamp: Option<u64>,
+    /// Timestamp of the last scrobble returned, saved in cursors
+    last_timestamp: Option<u64>,
+    /// When resumed from a cursor, skip leading tracks at or after this timestamp
+    resume_before: Option<u64>,
 }
 
 #[async_trait(?Send)]
 impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for RecentTracksIterator<C> {
     async fn next(&mut self) -> Result<Option<Track>> {
-        // If buffer is empty, try to load next page
-        if self.buffer.is_empty() {
+        // Load pages until one has tracks left (a resumed iterator may skip a whole page)
+        while self.buffer.is_empty() {
             if !self.has_more {
                 return Ok(None);
             }
@@ -394,6 +547,9 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for RecentTracksIterator
             }
 
             self.has_more = page.has_next_page;
+            self.skip = self
+                .skip
+                .max(already_yielded(&page.tracks, &mut self.resume_before));
 
             // Check if we should stop based on timestamp
             if let Some(stop_timestamp) = self.stop_at_timestamp {
@@ -407,21 +563,47 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for RecentTracksIterator
                     }
                     filtered_tracks.push(track);
                 }
-                self.buffer = filtered_tracks;
+                fill_buffer(
+                    &mut self.buffer,
+                    filtered_tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             } else {
-                self.buffer = page.tracks;
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
 
-            self.buffer.reverse(); // Reverse so we can pop from end efficiently
             self.current_page += 1;
         }
 
-        Ok(self.buffer.pop())
+        let track = self.buffer.pop();
+        if let Some(timestamp) = track.as_ref().and_then(|t| t.timestamp) {
+            self.last_timestamp = Some(timestamp);
+        }
+        Ok(track)
     }
 
     fn current_page(&self) -> u32 {
         self.current_page.saturating_sub(1)
     }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        let mut cursor = page_cursor(
+            CursorKind::RecentTracks,
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        );
+        cursor.stop_at_timestamp = self.stop_at_timestamp;
+        cursor.last_timestamp = self.last_timestamp;
+        Some(cursor)
+    }
 }
 
 impl<C: LastFmBaseClient> RecentTracksIterator<C> {
@@ -448,7 +630,11 @@ impl<C: LastFmBaseClient> RecentTracksIterator<C> {
             current_page: page,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             stop_at_timestamp: None,
+            last_timestamp: None,
+            resume_before: None,
         }
     }
 
@@ -477,7 +663,13 @@ pub struct ApiRecentTracksIterator<C: LastFmApiClient> {
     current_page: u32,
     has_more: bool,
     buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
     stop_at_timestamp: Option<u64>,
+    /// Timestamp of the last scrobble returned, saved in cursors
+    last_timestamp: Option<u64>,
+    /// When resumed from a cursor, skip leading tracks at or after this timestamp
+    resume_before: Option<u64>,
     total_pages: Option<u32>,
     from: Option<u64>,
     to: Option<u64>,
@@ -486,7 +678,7 @@ pub struct ApiRecentTracksIterator<C: LastFmApiClient> {
 #[async_trait(?Send)]
 impl<C: LastFmApiClient> AsyncPaginatedIterator<Track> for ApiRecentTracksIterator<C> {
     async fn next(&mut self) -> Result<Option<Track>> {
-        if self.buffer.is_empty() {
+        while self.buffer.is_empty() {
             if !self.has_more {
                 return Ok(None);
             }
@@ -503,6 +695,9 @@ impl<C: LastFmApiClient> AsyncPaginatedIterator<Track> for ApiRecentTracksIterat
 
             self.has_more = page.has_next_page;
             self.total_pages = page.total_pages;
+            self.skip = self
+                .skip
+                .max(already_yielded(&page.tracks, &mut self.resume_before));
 
             if let Some(stop_timestamp) = self.stop_at_timestamp {
                 let mut filtered_tracks = Vec::new();
@@ -515,22 +710,51 @@ impl<C: LastFmApiClient> AsyncPaginatedIterator<Track> for ApiRecentTracksIterat
                     }
                     filtered_tracks.push(track);
                 }
-                self.buffer = filtered_tracks;
+                fill_buffer(
+                    &mut self.buffer,
+                    filtered_tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             } else {
-                self.buffer = page.tracks;
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
 
-            self.buffer.reverse();
             self.current_page += 1;
         }
 
-        Ok(self.buffer.pop())
+        let track = self.buffer.pop();
+        if let Some(timestamp) = track.as_ref().and_then(|t| t.timestamp) {
+            self.last_timestamp = Some(timestamp);
+        }
+        Ok(track)
     }
 
     fn current_page(&self) -> u32 {
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        let mut cursor = page_cursor(
+            CursorKind::ApiRecentTracks {
+                from: self.from,
+                to: self.to,
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        );
+        cursor.stop_at_timestamp = self.stop_at_timestamp;
+        cursor.last_timestamp = self.last_timestamp;
+        Some(cursor)
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
@@ -548,7 +772,11 @@ impl<C: LastFmApiClient> ApiRecentTracksIterator<C> {
             current_page: page,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             stop_at_timestamp: None,
+            last_timestamp: None,
+            resume_before: None,
             total_pages: None,
             from: None,
             to: None,
@@ -642,6 +870,20 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for AlbumTracksIterator<
         // Album tracks don't have pages, so return 0
         0
     }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(IteratorCursor {
+            kind: CursorKind::AlbumTracks {
+                album: self.album_name.clone(),
+                artist: self.artist_name.clone(),
+            },
+            page: 1,
+            offset: self.index,
+            album_track_offset: 0,
+            stop_at_timestamp: None,
+            last_timestamp: None,
+        })
+    }
 }
 
 impl<C: LastFmBaseClient> AlbumTracksIterator<C> {
@@ -659,27 +901,36 @@ impl<C: LastFmBaseClient> AlbumTracksIterator<C> {
     }
 }
 
-/// Iterator for searching tracks in the user's library.
+/// Iterator over the individual scrobbles of one track.
 ///
-/// This iterator provides paginated access to tracks that match a search query
-/// in the authenticated user's Last.fm library, using Last.fm's built-in search functionality.
-pub struct SearchTracksIterator<C: LastFmBaseClient> {
+/// This iterator pages through the track's library page
+/// (`/user/{username}/library/music/{artist}/_/{track}`), newest first. Every item is a
+/// single scrobble with its timestamp and the album details from its edit form, so it
+/// can be turned into an exact edit without scanning the recent history.
+pub struct TrackScrobblesIterator<C: LastFmBaseClient> {
     client: C,
-    query: String,
+    artist: String,
+    track: String,
     current_page: u32,
     has_more: bool,
     buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
     total_pages: Option<u32>,
 }
 
 #[async_trait(?Send)]
-impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for SearchTracksIterator<C> {
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for TrackScrobblesIterator<C> {
     async fn next(&mut self) -> Result<Option<Track>> {
         // If buffer is empty, try to load next page
         if self.buffer.is_empty() {
             if let Some(page) = self.next_page().await? {
-                self.buffer = page.tracks;
-                self.buffer.reverse(); // Reverse so we can pop from end efficiently
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
         }
 
@@ -690,46 +941,43 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for SearchTracksIterator
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::TrackScrobbles {
+                artist: self.artist.clone(),
+                track: self.track.clone(),
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
 }
 
-impl<C: LastFmBaseClient> SearchTracksIterator<C> {
-    /// Create a new search tracks iterator.
+impl<C: LastFmBaseClient> TrackScrobblesIterator<C> {
+    /// Create a new track scrobbles iterator.
     ///
-    /// This is typically called via [`LastFmBaseClient::search_tracks`](crate::LastFmBaseClient::search_tracks).
-    pub fn new(client: C, query: String) -> Self {
+    /// This is typically called via [`LastFmEditClient::track_scrobbles`](crate::LastFmEditClient::track_scrobbles).
+    pub fn new(client: C, artist: String, track: String) -> Self {
         Self {
             client,
-            query,
+            artist,
+            track,
             current_page: 1,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             total_pages: None,
         }
     }
 
-    /// Create a new search tracks iterator starting from a specific page.
-    ///
-    /// This is useful for implementing offset functionality efficiently by starting
-    /// at the appropriate page rather than iterating through all previous pages.
-    pub fn with_starting_page(client: C, query: String, starting_page: u32) -> Self {
-        let page = std::cmp::max(1, starting_page);
-        Self {
-            client,
-            query,
-            current_page: page,
-            has_more: true,
-            buffer: Vec::new(),
-            total_pages: None,
-        }
-    }
-
-    /// Fetch the next page of search results.
-    ///
-    /// This method handles pagination automatically and includes rate limiting
-    /// to be respectful to Last.fm's servers.
+    /// Fetch the next page of scrobbles.
     pub async fn next_page(&mut self) -> Result<Option<TrackPage>> {
         if !self.has_more {
             return Ok(None);
@@ -737,7 +985,7 @@ impl<C: LastFmBaseClient> SearchTracksIterator<C> {
 
         let page = self
             .client
-            .search_tracks_page(&self.query, self.current_page)
+            .get_track_scrobbles_page(&self.artist, &self.track, self.current_page)
             .await?;
 
         self.has_more = page.has_next_page;
@@ -755,29 +1003,29 @@ impl<C: LastFmBaseClient> SearchTracksIterator<C> {
     }
 }
 
-/// Iterator for searching albums in the user's library.
-///
-/// This iterator provides paginated access to albums that match a search query
-/// in the authenticated user's Last.fm library, using Last.fm's built-in search functionality.
-///
-/// # Examples
-pub struct SearchAlbumsIterator<C: LastFmBaseClient> {
+/// Iterator over the user's loved tracks, most recently loved first.
+pub struct LovedTracksIterator<C: LastFmBaseClient> {
     client: C,
-    query: String,
     current_page: u32,
     has_more: bool,
-    buffer: Vec<Album>,
+    buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
     total_pages: Option<u32>,
 }
 
 #[async_trait(?Send)]
-impl<C: LastFmBaseClient> AsyncPaginatedIterator<Album> for SearchAlbumsIterator<C> {
-    async fn next(&mut self) -> Result<Option<Album>> {
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for LovedTracksIterator<C> {
+    async fn next(&mut self) -> Result<Option<Track>> {
         // If buffer is empty, try to load next page
         if self.buffer.is_empty() {
             if let Some(page) = self.next_page().await? {
-                self.buffer = page.albums;
-                self.buffer.reverse(); // Reverse so we can pop from end efficiently
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
         }
 
@@ -788,55 +1036,44 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<Album> for SearchAlbumsIterator
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::LovedTracks,
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
 }
 
-impl<C: LastFmBaseClient> SearchAlbumsIterator<C> {
-    /// Create a new search albums iterator.
+impl<C: LastFmBaseClient> LovedTracksIterator<C> {
+    /// Create a new loved tracks iterator.
     ///
-    /// This is typically called via [`LastFmBaseClient::search_albums`](crate::LastFmBaseClient::search_albums).
-    pub fn new(client: C, query: String) -> Self {
+    /// This is typically called via [`LastFmEditClient::loved_tracks`](crate::LastFmEditClient::loved_tracks).
+    pub fn new(client: C) -> Self {
         Self {
             client,
-            query,
             current_page: 1,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             total_pages: None,
         }
     }
 
-    /// Create a new search albums iterator starting from a specific page.
-    ///
-    /// This is useful for implementing offset functionality efficiently by starting
-    /// at the appropriate page rather than iterating through all previous pages.
-    pub fn with_starting_page(client: C, query: String, starting_page: u32) -> Self {
-        let page = std::cmp::max(1, starting_page);
-        Self {
-            client,
-            query,
-            current_page: page,
-            has_more: true,
-            buffer: Vec::new(),
-            total_pages: None,
-        }
-    }
-
-    /// Fetch the next page of search results.
-    ///
-    /// This method handles pagination automatically and includes rate limiting
-    /// to be respectful to Last.fm's servers.
-    pub async fn next_page(&mut self) -> Result<Option<AlbumPage>> {
+    /// Fetch the next page of loved tracks.
+    pub async fn next_page(&mut self) -> Result<Option<TrackPage>> {
         if !self.has_more {
             return Ok(None);
         }
 
-        let page = self
-            .client
-            .search_albums_page(&self.query, self.current_page)
-            .await?;
+        let page = self.client.get_loved_tracks_page(self.current_page).await?;
 
         self.has_more = page.has_next_page;
         self.current_page += 1;
@@ -853,27 +1090,34 @@ impl<C: LastFmBaseClient> SearchAlbumsIterator<C> {
     }
 }
 
-/// Iterator for searching artists in the user's library.
+/// Iterator over every track in the user's library.
 ///
-/// This iterator provides paginated access to artists that match a search query
-/// in the authenticated user's Last.fm library, using Last.fm's built-in search functionality.
-pub struct SearchArtistsIterator<C: LastFmBaseClient> {
+/// This iterator pages through `/user/{username}/library/tracks`, which lists the tracks
+/// of all artists at once, optionally limited to a date range.
+pub struct LibraryTracksIterator<C: LastFmBaseClient> {
     client: C,
-    query: String,
+    range: LibraryDateRange,
+    sort: LibrarySort,
     current_page: u32,
     has_more: bool,
-    buffer: Vec<crate::Artist>,
+    buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
     total_pages: Option<u32>,
 }
 
 #[async_trait(?Send)]
-impl<C: LastFmBaseClient> AsyncPaginatedIterator<crate::Artist> for SearchArtistsIterator<C> {
-    async fn next(&mut self) -> Result<Option<crate::Artist>> {
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for LibraryTracksIterator<C> {
+    async fn next(&mut self) -> Result<Option<Track>> {
         // If buffer is empty, try to load next page
         if self.buffer.is_empty() {
             if let Some(page) = self.next_page().await? {
-                self.buffer = page.artists;
-                self.buffer.reverse(); // Reverse so we can pop from end efficiently
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
         }
 
@@ -884,54 +1128,51 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<crate::Artist> for SearchArtist
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::LibraryTracks {
+                range: self.range,
+                sort: self.sort,
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
 }
 
-impl<C: LastFmBaseClient> SearchArtistsIterator<C> {
-    /// Create a new search artists iterator.
+impl<C: LastFmBaseClient> LibraryTracksIterator<C> {
+    /// Create a new library tracks iterator.
     ///
-    /// This is typically called via [`LastFmBaseClient::search_artists`](crate::LastFmBaseClient::search_artists).
-    pub fn new(client: C, query: String) -> Self {
+    /// This is typically called via [`LastFmEditClient::library_tracks`](crate::LastFmEditClient::library_tracks).
+    pub fn new(client: C, range: LibraryDateRange, sort: LibrarySort) -> Self {
         Self {
             client,
-            query,
+            range,
+            sort,
             current_page: 1,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             total_pages: None,
         }
     }
 
-    /// Create a new search artists iterator starting from a specific page.
-    ///
-    /// This is useful for implementing offset functionality efficiently by starting
-    /// at the appropriate page rather than iterating through all previous pages.
-    pub fn with_starting_page(client: C, query: String, starting_page: u32) -> Self {
-        let page = std::cmp::max(1, starting_page);
-        Self {
-            client,
-            query,
-            current_page: page,
-            has_more: true,
-            buffer: Vec::new(),
-            total_pages: None,
-        }
-    }
-
-    /// Fetch the next page of search results.
-    ///
-    /// This method handles pagination automatically and includes rate limiting
-    /// to be respectful to Last.fm's servers.
-    pub async fn next_page(&mut self) -> Result<Option<crate::ArtistPage>> {
+    /// Fetch the next page of tracks.
+    pub async fn next_page(&mut self) -> Result<Option<TrackPage>> {
         if !self.has_more {
             return Ok(None);
         }
 
         let page = self
             .client
-            .search_artists_page(&self.query, self.current_page)
+            .get_library_tracks_page(self.range, self.sort, self.current_page)
             .await?;
 
         self.has_more = page.has_next_page;
@@ -949,31 +1190,34 @@ impl<C: LastFmBaseClient> SearchArtistsIterator<C> {
     }
 }
 
-// =============================================================================
-// ARTISTS ITERATOR
-// =============================================================================
-
-/// Iterator for browsing all artists in the user's library.
+/// Iterator over every album in the user's library.
 ///
-/// This iterator provides access to all artists in the authenticated user's Last.fm library,
-/// sorted by play count (highest first). The iterator loads artists as needed and handles
-/// rate limiting automatically to be respectful to Last.fm's servers.
-pub struct ArtistsIterator<C: LastFmBaseClient> {
+/// This iterator pages through `/user/{username}/library/albums`, which lists the albums
+/// of all artists at once, optionally limited to a date range.
+pub struct LibraryAlbumsIterator<C: LastFmBaseClient> {
     client: C,
+    range: LibraryDateRange,
+    sort: LibrarySort,
     current_page: u32,
     has_more: bool,
-    buffer: Vec<crate::Artist>,
+    buffer: Vec<Album>,
+    page_len: usize,
+    skip: usize,
     total_pages: Option<u32>,
 }
 
 #[async_trait(?Send)]
-impl<C: LastFmBaseClient> AsyncPaginatedIterator<crate::Artist> for ArtistsIterator<C> {
-    async fn next(&mut self) -> Result<Option<crate::Artist>> {
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<Album> for LibraryAlbumsIterator<C> {
+    async fn next(&mut self) -> Result<Option<Album>> {
         // If buffer is empty, try to load next page
         if self.buffer.is_empty() {
             if let Some(page) = self.next_page().await? {
-                self.buffer = page.artists;
-                self.buffer.reverse(); // Reverse so we can pop from end efficiently
+                fill_buffer(
+                    &mut self.buffer,
+                    page.albums,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
             }
         }
 
@@ -984,50 +1228,52 @@ impl<C: LastFmBaseClient> AsyncPaginatedIterator<crate::Artist> for ArtistsItera
         self.current_page.saturating_sub(1)
     }
 
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::LibraryAlbums {
+                range: self.range,
+                sort: self.sort,
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
     fn total_pages(&self) -> Option<u32> {
         self.total_pages
     }
 }
 
-impl<C: LastFmBaseClient> ArtistsIterator<C> {
-    /// Create a new artists iterator.
+impl<C: LastFmBaseClient> LibraryAlbumsIterator<C> {
+    /// Create a new library albums iterator.
     ///
-    /// This iterator will start from page 1 and load all artists in the user's library.
-    pub fn new(client: C) -> Self {
+    /// This is typically called via [`LastFmEditClient::library_albums`](crate::LastFmEditClient::library_albums).
+    pub fn new(client: C, range: LibraryDateRange, sort: LibrarySort) -> Self {
         Self {
             client,
+            range,
+            sort,
             current_page: 1,
             has_more: true,
             buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
             total_pages: None,
         }
     }
 
-    /// Create a new artists iterator starting from a specific page.
-    ///
-    /// This is useful for implementing offset functionality efficiently by starting
-    /// at the appropriate page rather than iterating through all previous pages.
-    pub fn with_starting_page(client: C, starting_page: u32) -> Self {
-        let page = std::cmp::max(1, starting_page);
-        Self {
-            client,
-            current_page: page,
-            has_more: true,
-            buffer: Vec::new(),
-            total_pages: None,
-        }
-    }
-
-    /// Fetch the next page of artists.
-    ///
-    /// This method handles pagination automatically and includes rate limiting
-    /// to be respectful to Last.fm's servers.
-    pub async fn next_page(&mut self) -> Result<Option<crate::ArtistPage>> {
+    /// Fetch the next page of albums.
+    pub async fn next_page(&mut self) -> Result<Option<AlbumPage>> {
         if !self.has_more {
             return Ok(None);
         }
 
-        let page = self.client.get_artists_page(self.current_page).await?;
+        let page = self
+            .client
+            .get_library_albums_page(self.range, self.sort, self.current_page)
+            .await?;
 
         self.has_more = page.has_next_page;
         self.current_page += 1;
@@ -1043,3 +1289,1360 @@ impl<C: LastFmBaseClient> ArtistsIterator<C> {
         self.total_pages
     }
 }
+
+/// Iterator for searching tracks in the user's library.
+///
+/// This iterator provides paginated access to tracks that match a search query
+/// in the authenticated user's Last.fm library, using Last.fm's built-in search functionality.
+pub struct SearchTracksIterator<C: LastFmBaseClient> {
+    client: C,
+    query: String,
+    current_page: u32,
+    has_more: bool,
+    buffer: Vec<Track>,
+    page_len: usize,
+    skip: usize,
+    total_pages: Option<u32>,
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<Track> for SearchTracksIterator<C> {
+    async fn next(&mut self) -> Result<Option<Track>> {
+        // If buffer is empty, try to load next page
+        if self.buffer.is_empty() {
+            if let Some(page) = self.next_page().await? {
+                fill_buffer(
+                    &mut self.buffer,
+                    page.tracks,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
+            }
+        }
+
+        Ok(self.buffer.pop())
+    }
+
+    fn current_page(&self) -> u32 {
+        self.current_page.saturating_sub(1)
+    }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::SearchTracks {
+                query: self.query.clone(),
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+impl<C: LastFmBaseClient> SearchTracksIterator<C> {
+    /// Create a new search tracks iterator.
+    ///
+    /// This is typically called via [`LastFmBaseClient::search_tracks`](crate::LastFmBaseClient::search_tracks).
+    pub fn new(client: C, query: String) -> Self {
+        Self {
+            client,
+            query,
+            current_page: 1,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Create a new search tracks iterator starting from a specific page.
+    ///
+    /// This is useful for implementing offset functionality efficiently by starting
+    /// at the appropriate page rather than iterating through all previous pages.
+    pub fn with_starting_page(client: C, query: String, starting_page: u32) -> Self {
+        let page = std::cmp::max(1, starting_page);
+        Self {
+            client,
+            query,
+            current_page: page,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Fetch the next page of search results.
+    ///
+    /// This method handles pagination automatically and includes rate limiting
+    /// to be respectful to Last.fm's servers.
+    pub async fn next_page(&mut self) -> Result<Option<TrackPage>> {
+        if !self.has_more {
+            return Ok(None);
+        }
+
+        let page = self
+            .client
+            .search_tracks_page(&self.query, self.current_page)
+            .await?;
+
+        self.has_more = page.has_next_page;
+        self.current_page += 1;
+        self.total_pages = page.total_pages;
+
+        Ok(Some(page))
+    }
+
+    /// Get the total number of pages, if known.
+    ///
+    /// Returns `None` until at least one page has been fetched.
+    pub fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+/// Iterator for searching albums in the user's library.
+///
+/// This iterator provides paginated access to albums that match a search query
+/// in the authenticated user's Last.fm library, using Last.fm's built-in search functionality.
+///
+/// # Examples
+pub struct SearchAlbumsIterator<C: LastFmBaseClient> {
+    client: C,
+    query: String,
+    current_page: u32,
+    has_more: bool,
+    buffer: Vec<Album>,
+    page_len: usize,
+    skip: usize,
+    total_pages: Option<u32>,
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<Album> for SearchAlbumsIterator<C> {
+    async fn next(&mut self) -> Result<Option<Album>> {
+        // If buffer is empty, try to load next page
+        if self.buffer.is_empty() {
+            if let Some(page) = self.next_page().await? {
+                fill_buffer(
+                    &mut self.buffer,
+                    page.albums,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
+            }
+        }
+
+        Ok(self.buffer.pop())
+    }
+
+    fn current_page(&self) -> u32 {
+        self.current_page.saturating_sub(1)
+    }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::SearchAlbums {
+                query: self.query.clone(),
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+impl<C: LastFmBaseClient> SearchAlbumsIterator<C> {
+    /// Create a new search albums iterator.
+    ///
+    /// This is typically called via [`LastFmBaseClient::search_albums`](crate::LastFmBaseClient::search_albums).
+    pub fn new(client: C, query: String) -> Self {
+        Self {
+            client,
+            query,
+            current_page: 1,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Create a new search albums iterator starting from a specific page.
+    ///
+    /// This is useful for implementing offset functionality efficiently by starting
+    /// at the appropriate page rather than iterating through all previous pages.
+    pub fn with_starting_page(client: C, query: String, starting_page: u32) -> Self {
+        let page = std::cmp::max(1, starting_page);
+        Self {
+            client,
+            query,
+            current_page: page,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Fetch the next page of search results.
+    ///
+    /// This method handles pagination automatically and includes rate limiting
+    /// to be respectful to Last.fm's servers.
+    pub async fn next_page(&mut self) -> Result<Option<AlbumPage>> {
+        if !self.has_more {
+            return Ok(None);
+        }
+
+        let page = self
+            .client
+            .search_albums_page(&self.query, self.current_page)
+            .await?;
+
+        self.has_more = page.has_next_page;
+        self.current_page += 1;
+        self.total_pages = page.total_pages;
+
+        Ok(Some(page))
+    }
+
+    /// Get the total number of pages, if known.
+    ///
+    /// Returns `None` until at least one page has been fetched.
+    pub fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+/// Iterator for searching artists in the user's library.
+///
+/// This iterator provides paginated access to artists that match a search query
+/// in the authenticated user's Last.fm library, using Last.fm's built-in search functionality.
+pub struct SearchArtistsIterator<C: LastFmBaseClient> {
+    client: C,
+    query: String,
+    current_page: u32,
+    has_more: bool,
+    buffer: Vec<crate::Artist>,
+    page_len: usize,
+    skip: usize,
+    total_pages: Option<u32>,
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<crate::Artist> for SearchArtistsIterator<C> {
+    async fn next(&mut self) -> Result<Option<crate::Artist>> {
+        // If buffer is empty, try to load next page
+        if self.buffer.is_empty() {
+            if let Some(page) = self.next_page().await? {
+                fill_buffer(
+                    &mut self.buffer,
+                    page.artists,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
+            }
+        }
+
+        Ok(self.buffer.pop())
+    }
+
+    fn current_page(&self) -> u32 {
+        self.current_page.saturating_sub(1)
+    }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::SearchArtists {
+                query: self.query.clone(),
+            },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+impl<C: LastFmBaseClient> SearchArtistsIterator<C> {
+    /// Create a new search artists iterator.
+    ///
+    /// This is typically called via [`LastFmBaseClient::search_artists`](crate::LastFmBaseClient::search_artists).
+    pub fn new(client: C, query: String) -> Self {
+        Self {
+            client,
+            query,
+            current_page: 1,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Create a new search artists iterator starting from a specific page.
+    ///
+    /// This is useful for implementing offset functionality efficiently by starting
+    /// at the appropriate page rather than iterating through all previous pages.
+    pub fn with_starting_page(client: C, query: String, starting_page: u32) -> Self {
+        let page = std::cmp::max(1, starting_page);
+        Self {
+            client,
+            query,
+            current_page: page,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Fetch the next page of search results.
+    ///
+    /// This method handles pagination automatically and includes rate limiting
+    /// to be respectful to Last.fm's servers.
+    pub async fn next_page(&mut self) -> Result<Option<crate::ArtistPage>> {
+        if !self.has_more {
+            return Ok(None);
+        }
+
+        let page = self
+            .client
+            .search_artists_page(&self.query, self.current_page)
+            .await?;
+
+        self.has_more = page.has_next_page;
+        self.current_page += 1;
+        self.total_pages = page.total_pages;
+
+        Ok(Some(page))
+    }
+
+    /// Get the total number of pages, if known.
+    ///
+    /// Returns `None` until at least one page has been fetched.
+    pub fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+// =============================================================================
+// ARTISTS ITERATOR
+// =============================================================================
+
+/// Iterator for browsing all artists in the user's library.
+///
+/// This iterator provides access to all artists in the authenticated user's Last.fm library,
+/// sorted by play count (highest first). The iterator loads artists as needed and handles
+/// rate limiting automatically to be respectful to Last.fm's servers.
+pub struct ArtistsIterator<C: LastFmBaseClient> {
+    client: C,
+    range: LibraryDateRange,
+    current_page: u32,
+    has_more: bool,
+    buffer: Vec<crate::Artist>,
+    page_len: usize,
+    skip: usize,
+    total_pages: Option<u32>,
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> AsyncPaginatedIterator<crate::Artist> for ArtistsIterator<C> {
+    async fn next(&mut self) -> Result<Option<crate::Artist>> {
+        // If buffer is empty, try to load next page
+        if self.buffer.is_empty() {
+            if let Some(page) = self.next_page().await? {
+                fill_buffer(
+                    &mut self.buffer,
+                    page.artists,
+                    &mut self.page_len,
+                    &mut self.skip,
+                );
+            }
+        }
+
+        Ok(self.buffer.pop())
+    }
+
+    fn current_page(&self) -> u32 {
+        self.current_page.saturating_sub(1)
+    }
+
+    fn cursor(&self) -> Option<IteratorCursor> {
+        Some(page_cursor(
+            CursorKind::Artists { range: self.range },
+            self.current_page,
+            self.buffer.len(),
+            self.page_len,
+            self.skip,
+        ))
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+impl<C: LastFmBaseClient> ArtistsIterator<C> {
+    /// Create a new artists iterator.
+    ///
+    /// This iterator will start from page 1 and load all artists in the user's library.
+    pub fn new(client: C) -> Self {
+        Self::with_range(client, LibraryDateRange::AllTime)
+    }
+
+    /// Create an artists iterator over the artists scrobbled within `range`.
+    ///
+    /// Artists come sorted by their play count inside the range.
+    pub fn with_range(client: C, range: LibraryDateRange) -> Self {
+        Self {
+            client,
+            range,
+            current_page: 1,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Create a new artists iterator starting from a specific page.
+    ///
+    /// This is useful for implementing offset functionality efficiently by starting
+    /// at the appropriate page rather than iterating through all previous pages.
+    pub fn with_starting_page(client: C, starting_page: u32) -> Self {
+        let page = std::cmp::max(1, starting_page);
+        Self {
+            client,
+            range: LibraryDateRange::AllTime,
+            current_page: page,
+            has_more: true,
+            buffer: Vec::new(),
+            page_len: 0,
+            skip: 0,
+            total_pages: None,
+        }
+    }
+
+    /// Fetch the next page of artists.
+    ///
+    /// This method handles pagination automatically and includes rate limiting
+    /// to be respectful to Last.fm's servers.
+    pub async fn next_page(&mut self) -> Result<Option<crate::ArtistPage>> {
+        if !self.has_more {
+            return Ok(None);
+        }
+
+        let page = if self.range.is_all_time() {
+            self.client.get_artists_page(self.current_page).await?
+        } else {
+            self.client
+                .get_artists_page_in_range(self.range, self.current_page)
+                .await?
+        };
+
+        self.has_more = page.has_next_page;
+        self.current_page += 1;
+        self.total_pages = page.total_pages;
+
+        Ok(Some(page))
+    }
+
+    /// Get the total number of pages, if known.
+    ///
+    /// Returns `None` until at least one page has been fetched.
+    pub fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+}
+
+// =============================================================================
+// PAGE ACCESS AND STREAMS
+// =============================================================================
+
+/// One page of items, as fetched by [`PagedIterator::fetch_page`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedPage<T> {
+    pub items: Vec<T>,
+    pub has_next_page: bool,
+    pub total_pages: Option<u32>,
+}
+
+impl From<TrackPage> for FetchedPage<Track> {
+    fn from(page: TrackPage) -> Self {
+        Self {
+            items: page.tracks,
+            has_next_page: page.has_next_page,
+            total_pages: page.total_pages,
+        }
+    }
+}
+
+impl From<AlbumPage> for FetchedPage<Album> {
+    fn from(page: AlbumPage) -> Self {
+        Self {
+            items: page.albums,
+            has_next_page: page.has_next_page,
+            total_pages: page.total_pages,
+        }
+    }
+}
+
+impl From<crate::ArtistPage> for FetchedPage<crate::Artist> {
+    fn from(page: crate::ArtistPage) -> Self {
+        Self {
+            items: page.artists,
+            has_next_page: page.has_next_page,
+            total_pages: page.total_pages,
+        }
+    }
+}
+
+/// Where an iterator stands, as handed over by [`PagedIterator::take_position`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PagePosition<T> {
+    /// Items already fetched but not yet yielded, in iteration order
+    pub buffered: Vec<T>,
+    /// The next page to fetch (1-indexed), or `None` when no pages are left
+    pub next_page: Option<u32>,
+    /// Items at the start of `next_page` that were already yielded
+    pub skip: usize,
+    pub total_pages: Option<u32>,
+}
+
+/// Iterators over numbered pages, any of which can be fetched on its own.
+///
+/// This is what lets [`PagedIteratorExt::into_prefetching_stream`] request several pages
+/// at once instead of waiting for each one before asking for the next.
+#[async_trait(?Send)]
+pub trait PagedIterator<T>: AsyncPaginatedIterator<T> {
+    /// Fetch page `page` (1-indexed) without advancing the iterator.
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<T>>;
+
+    /// Take the iterator's buffered items and next page, leaving it exhausted.
+    fn take_position(&mut self) -> PagePosition<T>;
+}
+
+/// Take the position of an iterator built on the usual page/has_more/buffer fields.
+fn take_buffered_position<T>(
+    buffer: &mut Vec<T>,
+    has_more: &mut bool,
+    skip: &mut usize,
+    current_page: u32,
+    total_pages: Option<u32>,
+) -> PagePosition<T> {
+    // Buffers are kept reversed so items can be popped off the end.
+    let mut buffered = std::mem::take(buffer);
+    buffered.reverse();
+    let next_page = std::mem::replace(has_more, false).then_some(current_page);
+    PagePosition {
+        buffered,
+        next_page,
+        skip: std::mem::take(skip),
+        total_pages,
+    }
+}
+
+/// Keep the tracks newer than `stop_timestamp`, ending pagination at the first older one.
+fn stop_at_timestamp(page: &mut FetchedPage<Track>, stop_timestamp: Option<u64>) {
+    let Some(stop_timestamp) = stop_timestamp else {
+        return;
+    };
+    if let Some(index) = page
+        .items
+        .iter()
+        .position(|track| track.timestamp.is_some_and(|ts| ts <= stop_timestamp))
+    {
+        page.items.truncate(index);
+        page.has_next_page = false;
+    }
+}
+
+/// Adapts any [`AsyncPaginatedIterator`] into a [`futures::Stream`].
+pub trait AsyncPaginatedIteratorExt<T: 'static>:
+    AsyncPaginatedIterator<T> + Sized + 'static
+{
+    /// Turn the iterator into a stream of its remaining items.
+    ///
+    /// Pages are fetched one at a time, exactly as [`next`](AsyncPaginatedIterator::next)
+    /// would. The stream ends after yielding the first error.
+    fn into_stream(self) -> LocalBoxStream<'static, Result<T>> {
+        stream::unfold(Some(self), |iterator| async move {
+            let mut iterator = iterator?;
+            match iterator.next().await {
+                Ok(Some(item)) => Some((Ok(item), Some(iterator))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+        .boxed_local()
+    }
+}
+
+impl<T: 'static, I: AsyncPaginatedIterator<T> + Sized + 'static> AsyncPaginatedIteratorExt<T>
+    for I
+{
+}
+
+/// Streams that fetch several pages ahead of the consumer.
+pub trait PagedIteratorExt<T: 'static>: PagedIterator<T> + Sized + 'static {
+    /// Turn the iterator into a stream that keeps up to `ahead` page requests in flight.
+    ///
+    /// Items already buffered are yielded first. Once the total page count is known
+    /// (fetching the next page alone if needed to learn it) the remaining pages are
+    /// requested concurrently and yielded in page order. Every request still goes
+    /// through the client, so its pacing and rate limit handling decide how fast they
+    /// are actually sent. Without a total page count the pages are fetched one at a
+    /// time. The stream ends after the last page or the first error.
+    fn into_prefetching_stream(mut self, ahead: usize) -> LocalBoxStream<'static, Result<T>> {
+        let position = self.take_position();
+        let skip = position.skip;
+        let buffered = stream::iter(position.buffered.into_iter().map(Ok));
+        let Some(first_page) = position.next_page else {
+            return buffered.boxed_local();
+        };
+
+        let source = Rc::new(self);
+        let ahead = ahead.max(1);
+        let pages = stream::once(async move {
+            let (head, next_page, total_pages) = match position.total_pages {
+                Some(total) => (None, first_page, Some(total)),
+                None => {
+                    let head = source.fetch_page(first_page).await;
+                    let total = head.as_ref().ok().and_then(|page| page.total_pages);
+                    (Some(head), first_page + 1, total)
+                }
+            };
+
+            let rest = match total_pages {
+                Some(total) => stream::iter(next_page..=total)
+                    .map(move |page| {
+                        let source = source.clone();
+                        async move { source.fetch_page(page).await }
+                    })
+                    .buffered(ahead)
+                    .boxed_local(),
+                None => stream::unfold(next_page, move |page| {
+                    let source = source.clone();
+                    async move { Some((source.fetch_page(page).await, page + 1)) }
+                })
+                .boxed_local(),
+            };
+            stream::iter(head).chain(rest)
+        })
+        .flatten()
+        .scan((false, skip), |(finished, skip), page| {
+            if *finished {
+                return future::ready(None);
+            }
+            let items = match page {
+                Ok(page) => {
+                    *finished = !page.has_next_page || page.items.is_empty();
+                    let skip = std::mem::take(skip);
+                    page.items.into_iter().skip(skip).map(Ok).collect()
+                }
+                Err(e) => {
+                    *finished = true;
+                    vec![Err(e)]
+                }
+            };
+            future::ready(Some(stream::iter(items)))
+        })
+        .flatten();
+
+        buffered.chain(pages).boxed_local()
+    }
+}
+
+impl<T: 'static, I: PagedIterator<T> + Sized + 'static> PagedIteratorExt<T> for I {}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Track> for ArtistTracksDirectIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        let page = if self.range.is_all_time() {
+            self.client
+                .get_artist_tracks_page(&self.artist, page)
+                .await?
+        } else {
+            self.client
+                .get_artist_tracks_page_in_range(&self.artist, self.range, page)
+                .await?
+        };
+        Ok(page.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Album> for ArtistAlbumsIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Album>> {
+        let page = if self.range.is_all_time() {
+            self.client
+                .get_artist_albums_page(&self.artist, page)
+                .await?
+        } else {
+            self.client
+                .get_artist_albums_page_in_range(&self.artist, self.range, page)
+                .await?
+        };
+        Ok(page.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Album> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Track> for RecentTracksIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        let mut page: FetchedPage<Track> = self.client.get_recent_tracks_page(page).await?.into();
+        stop_at_timestamp(&mut page, self.stop_at_timestamp);
+        Ok(page)
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            None,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmApiClient> PagedIterator<Track> for ApiRecentTracksIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        let mut page: FetchedPage<Track> = self
+            .client
+            .api_get_recent_tracks_page_in_range(page, self.from, self.to)
+            .await?
+            .into();
+        stop_at_timestamp(&mut page, self.stop_at_timestamp);
+        Ok(page)
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Track> for TrackScrobblesIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        let page = self
+            .client
+            .get_track_scrobbles_page(&self.artist, &self.track, page)
+            .await?;
+        Ok(page.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Track> for LovedTracksIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        Ok(self.client.get_loved_tracks_page(page).await?.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Track> for LibraryTracksIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        let page = self
+            .client
+            .get_library_tracks_page(self.range, self.sort, page)
+            .await?;
+        Ok(page.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Album> for LibraryAlbumsIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Album>> {
+        let page = self
+            .client
+            .get_library_albums_page(self.range, self.sort, page)
+            .await?;
+        Ok(page.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Album> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Track> for SearchTracksIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Track>> {
+        Ok(self
+            .client
+            .search_tracks_page(&self.query, page)
+            .await?
+            .into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Track> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<Album> for SearchAlbumsIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<Album>> {
+        Ok(self
+            .client
+            .search_albums_page(&self.query, page)
+            .await?
+            .into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<Album> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<crate::Artist> for SearchArtistsIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<crate::Artist>> {
+        Ok(self
+            .client
+            .search_artists_page(&self.query, page)
+            .await?
+            .into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<crate::Artist> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: LastFmBaseClient> PagedIterator<crate::Artist> for ArtistsIterator<C> {
+    async fn fetch_page(&self, page: u32) -> Result<FetchedPage<crate::Artist>> {
+        let page = if self.range.is_all_time() {
+            self.client.get_artists_page(page).await?
+        } else {
+            self.client
+                .get_artists_page_in_range(self.range, page)
+                .await?
+        };
+        Ok(page.into())
+    }
+
+    fn take_position(&mut self) -> PagePosition<crate::Artist> {
+        take_buffered_position(
+            &mut self.buffer,
+            &mut self.has_more,
+            &mut self.skip,
+            self.current_page,
+            self.total_pages,
+        )
+    }
+}
+
+// =============================================================================
+// CURSORS
+// =============================================================================
+
+/// What a cursor iterates over, with the arguments its iterator was created with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CursorKind {
+    RecentTracks,
+    ApiRecentTracks {
+        from: Option<u64>,
+        to: Option<u64>,
+    },
+    ArtistTracks {
+        artist: String,
+    },
+    ArtistTracksDirect {
+        artist: String,
+        range: LibraryDateRange,
+    },
+    ArtistAlbums {
+        artist: String,
+        range: LibraryDateRange,
+    },
+    AlbumTracks {
+        album: String,
+        artist: String,
+    },
+    TrackScrobbles {
+        artist: String,
+        track: String,
+    },
+    LovedTracks,
+    LibraryTracks {
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    },
+    LibraryAlbums {
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    },
+    SearchTracks {
+        query: String,
+    },
+    SearchAlbums {
+        query: String,
+    },
+    SearchArtists {
+        query: String,
+    },
+    Artists {
+        range: LibraryDateRange,
+    },
+}
+
+/// A saved iterator position, from [`AsyncPaginatedIterator::cursor`].
+///
+/// Cursors serialize to small JSON objects, so long listings can be checkpointed to disk
+/// and resumed after a crash instead of starting over from page 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IteratorCursor {
+    #[serde(flatten)]
+    pub kind: CursorKind,
+    /// Page holding the next item (1-indexed)
+    pub page: u32,
+    /// Items of `page` already yielded
+    pub offset: usize,
+    /// For album-based artist tracks, tracks of the album at `offset` already yielded
+    #[serde(default)]
+    pub album_track_offset: usize,
+    #[serde(default)]
+    pub stop_at_timestamp: Option<u64>,
+    /// For recent-tracks listings, timestamp of the last scrobble yielded
+    ///
+    /// New scrobbles push older ones down the listing, so a resumed iterator skips
+    /// tracks at or after this timestamp instead of trusting `offset` alone.
+    #[serde(default)]
+    pub last_timestamp: Option<u64>,
+}
+
+impl IteratorCursor {
+    /// Rebuild a track iterator positioned at this cursor.
+    ///
+    /// Cursors of `user.getRecentTracks` API iterators need a [`LastFmApiClient`]; resume
+    /// those with [`ApiRecentTracksIterator::from_cursor`].
+    pub fn track_iterator<C: LastFmBaseClient + Clone + 'static>(
+        &self,
+        client: C,
+    ) -> Result<Box<dyn AsyncPaginatedIterator<Track>>> {
+        Ok(match &self.kind {
+            CursorKind::RecentTracks => Box::new(RecentTracksIterator::from_cursor(client, self)?),
+            CursorKind::ArtistTracks { .. } => {
+                Box::new(ArtistTracksIterator::from_cursor(client, self)?)
+            }
+            CursorKind::ArtistTracksDirect { .. } => {
+                Box::new(ArtistTracksDirectIterator::from_cursor(client, self)?)
+            }
+            CursorKind::AlbumTracks { .. } => {
+                Box::new(AlbumTracksIterator::from_cursor(client, self)?)
+            }
+            CursorKind::TrackScrobbles { .. } => {
+                Box::new(TrackScrobblesIterator::from_cursor(client, self)?)
+            }
+            CursorKind::LovedTracks => Box::new(LovedTracksIterator::from_cursor(client, self)?),
+            CursorKind::LibraryTracks { .. } => {
+                Box::new(LibraryTracksIterator::from_cursor(client, self)?)
+            }
+            CursorKind::SearchTracks { .. } => {
+                Box::new(SearchTracksIterator::from_cursor(client, self)?)
+            }
+            _ => return Err(self.mismatch("a track listing")),
+        })
+    }
+
+    /// Rebuild an album iterator positioned at this cursor.
+    pub fn album_iterator<C: LastFmBaseClient + 'static>(
+        &self,
+        client: C,
+    ) -> Result<Box<dyn AsyncPaginatedIterator<Album>>> {
+        Ok(match &self.kind {
+            CursorKind::ArtistAlbums { .. } => {
+                Box::new(ArtistAlbumsIterator::from_cursor(client, self)?)
+            }
+            CursorKind::LibraryAlbums { .. } => {
+                Box::new(LibraryAlbumsIterator::from_cursor(client, self)?)
+            }
+            CursorKind::SearchAlbums { .. } => {
+                Box::new(SearchAlbumsIterator::from_cursor(client, self)?)
+            }
+            _ => return Err(self.mismatch("an album listing")),
+        })
+    }
+
+    /// Rebuild an artist iterator positioned at this cursor.
+    pub fn artist_iterator<C: LastFmBaseClient + 'static>(
+        &self,
+        client: C,
+    ) -> Result<Box<dyn AsyncPaginatedIterator<crate::Artist>>> {
+        Ok(match &self.kind {
+            CursorKind::Artists { .. } => Box::new(ArtistsIterator::from_cursor(client, self)?),
+            CursorKind::SearchArtists { .. } => {
+                Box::new(SearchArtistsIterator::from_cursor(client, self)?)
+            }
+            _ => return Err(self.mismatch("an artist listing")),
+        })
+    }
+
+    fn mismatch(&self, expected: &str) -> LastFmError {
+        LastFmError::Parse(format!(
+            "Cursor for {:?} cannot resume {expected}",
+            self.kind
+        ))
+    }
+
+    /// The page to start from, never below 1.
+    fn start_page(&self) -> u32 {
+        self.page.max(1)
+    }
+}
+
+/// Replace a page-based iterator's buffer with a freshly fetched page, dropping the first
+/// `skip` items (already yielded before the iterator was resumed).
+fn fill_buffer<T>(buffer: &mut Vec<T>, mut items: Vec<T>, page_len: &mut usize, skip: &mut usize) {
+    *page_len = items.len();
+    let skip = std::mem::take(skip).min(items.len());
+    items.drain(..skip);
+    items.reverse(); // Reverse so we can pop from end efficiently
+    *buffer = items;
+}
+
+/// Number of leading `tracks` a resumed recent-tracks iterator already yielded: those at
+/// or after `resume_before` (plus a now-playing row). Clears `resume_before` once an older
+/// track shows up, since everything from there on is new to the iterator.
+fn already_yielded(tracks: &[Track], resume_before: &mut Option<u64>) -> usize {
+    let Some(last_seen) = *resume_before else {
+        return 0;
+    };
+    let seen = tracks
+        .iter()
+        .take_while(|track| track.timestamp.is_none_or(|ts| ts >= last_seen))
+        .count();
+    if seen < tracks.len() {
+        *resume_before = None;
+    }
+    seen
+}
+
+/// Page and offset of the next item of a page-based iterator.
+fn page_position(current_page: u32, buffered: usize, page_len: usize, skip: usize) -> (u32, usize) {
+    if buffered > 0 {
+        // `current_page` already points past the page the buffer came from
+        (current_page.saturating_sub(1).max(1), page_len - buffered)
+    } else {
+        (current_page, skip)
+    }
+}
+
+fn page_cursor(
+    kind: CursorKind,
+    current_page: u32,
+    buffered: usize,
+    page_len: usize,
+    skip: usize,
+) -> IteratorCursor {
+    let (page, offset) = page_position(current_page, buffered, page_len, skip);
+    IteratorCursor {
+        kind,
+        page,
+        offset,
+        album_track_offset: 0,
+        stop_at_timestamp: None,
+        last_timestamp: None,
+    }
+}
+
+impl<C: LastFmBaseClient + Clone> ArtistTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::ArtistTracks { artist } = &cursor.kind else {
+            return Err(cursor.mismatch("artist tracks"));
+        };
+        let mut iterator = Self::new(client.clone(), artist.clone());
+        let mut albums = ArtistAlbumsIterator::new(client, artist.clone());
+        albums.current_page = cursor.start_page();
+        albums.skip = cursor.offset;
+        iterator.album_iterator = Some(albums);
+        iterator.album_position = (cursor.start_page(), cursor.offset);
+        iterator.album_track_skip = cursor.album_track_offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> ArtistTracksDirectIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::ArtistTracksDirect { artist, range } = &cursor.kind else {
+            return Err(cursor.mismatch("artist tracks"));
+        };
+        let mut iterator = Self::with_range(client, artist.clone(), *range);
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> ArtistAlbumsIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::ArtistAlbums { artist, range } = &cursor.kind else {
+            return Err(cursor.mismatch("artist albums"));
+        };
+        let mut iterator = Self::with_range(client, artist.clone(), *range);
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> RecentTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    ///
+    /// The recent tracks listing shifts as new scrobbles arrive, so tracks are skipped by
+    /// timestamp rather than by position when the cursor records one.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::RecentTracks = &cursor.kind else {
+            return Err(cursor.mismatch("recent tracks"));
+        };
+        let mut iterator = Self::with_starting_page(client, cursor.start_page());
+        iterator.stop_at_timestamp = cursor.stop_at_timestamp;
+        iterator.last_timestamp = cursor.last_timestamp;
+        iterator.resume_before = cursor.last_timestamp;
+        if cursor.last_timestamp.is_none() {
+            iterator.skip = cursor.offset;
+        }
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmApiClient> ApiRecentTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::ApiRecentTracks { from, to } = &cursor.kind else {
+            return Err(cursor.mismatch("API recent tracks"));
+        };
+        let mut iterator = Self::with_range(client, *from, *to);
+        iterator.current_page = cursor.start_page();
+        iterator.stop_at_timestamp = cursor.stop_at_timestamp;
+        iterator.last_timestamp = cursor.last_timestamp;
+        iterator.resume_before = cursor.last_timestamp;
+        if cursor.last_timestamp.is_none() {
+            iterator.skip = cursor.offset;
+        }
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> AlbumTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::AlbumTracks { album, artist } = &cursor.kind else {
+            return Err(cursor.mismatch("album tracks"));
+        };
+        let mut iterator = Self::new(client, album.clone(), artist.clone());
+        iterator.index = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> TrackScrobblesIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::TrackScrobbles { artist, track } = &cursor.kind else {
+            return Err(cursor.mismatch("track scrobbles"));
+        };
+        let mut iterator = Self::new(client, artist.clone(), track.clone());
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> LovedTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::LovedTracks = &cursor.kind else {
+            return Err(cursor.mismatch("loved tracks"));
+        };
+        let mut iterator = Self::new(client);
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> LibraryTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::LibraryTracks { range, sort } = &cursor.kind else {
+            return Err(cursor.mismatch("library tracks"));
+        };
+        let mut iterator = Self::new(client, *range, *sort);
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> LibraryAlbumsIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::LibraryAlbums { range, sort } = &cursor.kind else {
+            return Err(cursor.mismatch("library albums"));
+        };
+        let mut iterator = Self::new(client, *range, *sort);
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> SearchTracksIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::SearchTracks { query } = &cursor.kind else {
+            return Err(cursor.mismatch("track search"));
+        };
+        let mut iterator = Self::with_starting_page(client, query.clone(), cursor.start_page());
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> SearchAlbumsIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::SearchAlbums { query } = &cursor.kind else {
+            return Err(cursor.mismatch("album search"));
+        };
+        let mut iterator = Self::with_starting_page(client, query.clone(), cursor.start_page());
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> SearchArtistsIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::SearchArtists { query } = &cursor.kind else {
+            return Err(cursor.mismatch("artist search"));
+        };
+        let mut iterator = Self::with_starting_page(client, query.clone(), cursor.start_page());
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
+
+impl<C: LastFmBaseClient> ArtistsIterator<C> {
+    /// Rebuild an iterator at the position saved in `cursor`.
+    pub fn from_cursor(client: C, cursor: &IteratorCursor) -> Result<Self> {
+        let CursorKind::Artists { range } = &cursor.kind else {
+            return Err(cursor.mismatch("artists"));
+        };
+        let mut iterator = Self::with_range(client, *range);
+        iterator.current_page = cursor.start_page();
+        iterator.skip = cursor.offset;
+        Ok(iterator)
+    }
+}
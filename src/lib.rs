@@ -1,41 +1,73 @@
 #![doc = include_str!("../README.md")]
 
 pub mod api;
+#[cfg(feature = "browser-import")]
+pub mod browser_session;
 pub mod cancel;
 pub mod client;
 pub mod delete_manifest;
 pub mod discovery;
+pub mod duplicates;
 pub mod edit_analysis;
+pub mod edit_journal;
+pub mod fake_scrobbles;
 pub mod headers;
 pub mod iterator;
 pub mod login;
+pub mod middleware;
+pub mod pacing;
+pub mod parse_health;
 pub mod parsing;
+pub mod response_cache;
 pub mod retry;
+#[cfg(feature = "session-encryption")]
+pub mod session_encryption;
 pub mod session_persistence;
+pub mod shared_rate_limit;
+pub mod simulation;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod r#trait;
 pub mod types;
 pub mod vcr_form_data;
 pub mod vcr_matcher;
 pub mod vcr_test_utils;
 
-pub use api::{LastFmApiClient, LastFmApiClientImpl};
+pub use api::{
+    ApiAlbumInfo, ApiAlbumTrack, ApiArtistCorrection, ApiArtistInfo, ApiLovedTrack, ApiPage,
+    ApiPeriod, ApiScrobble, ApiTopAlbum, ApiTopArtist, ApiTopTrack, ApiTrackInfo, ApiUserInfo,
+    FailedScrobbleBatch, IgnoredScrobble, LastFmApiClient, LastFmApiClientImpl,
+    ScrobbleBatchResult,
+};
 pub use cancel::CancellationState;
 pub use client::LastFmEditClientImpl;
 pub use discovery::{
     AlbumTracksDiscovery, ArtistTracksDiscovery, AsyncDiscoveryIterator, ExactMatchDiscovery,
     TrackVariationsDiscovery,
 };
-pub use login::LoginManager;
+pub use edit_journal::{
+    EditJournal, EditJournalEntry, EditJournalFilter, FileEditJournal, InMemoryEditJournal,
+};
+pub use login::{CredentialProvider, LoginManager, StaticCredentials};
+pub use middleware::{
+    AuditLayer, DryRunLayer, Layer, LayerExt, Layered, LoggingLayer, Middleware, Mutation,
+    MutationOutcome,
+};
 pub use r#trait::{LastFmBaseClient, LastFmEditClient};
 
 // Re-export all types from the consolidated types module
-pub use iterator::AsyncPaginatedIterator;
+pub use iterator::{
+    AsyncPaginatedIterator, AsyncPaginatedIteratorExt, CursorKind, FetchedPage, IteratorCursor,
+    PagePosition, PagedIterator, PagedIteratorExt,
+};
 pub use types::{
-    Album, AlbumPage, Artist, ArtistPage, ClientConfig, ClientEvent, ClientEventReceiver,
-    ClientEventWatcher, DelayReason, EditResponse, ExactScrobbleEdit, LastFmEditSession,
-    LastFmError, OperationalDelayConfig, RateLimitBehavior, RateLimitConfig, RateLimitState,
-    RateLimitStateWatcher, RateLimitType, RequestInfo, RetryConfig, RetryResult, ScrobbleEdit,
-    SharedEventBroadcaster, SingleEditResponse, Track, TrackPage,
+    AdaptivePacingConfig, Album, AlbumPage, Artist, ArtistPage, ClientConfig, ClientEvent,
+    ClientEventReceiver, ClientEventWatcher, DatePreset, DelayReason, EditFailureKind,
+    EditResponse, EditVerificationConfig, EditVerificationStatus, ExactScrobbleEdit,
+    LastFmEditSession, LastFmError, LibraryDateRange, LibrarySort, OperationalDelayConfig,
+    RateLimitBehavior, RateLimitConfig, RateLimitState, RateLimitStateWatcher, RateLimitType,
+    RequestInfo, RetryConfig, RetryResult, ScrobbleEdit, SharedEventBroadcaster,
+    SingleEditResponse, Track, TrackPage, TrafficClass,
 };
 
 // Type aliases for iterators with the concrete client type
@@ -45,6 +77,10 @@ pub type ArtistTracksDirectIterator = iterator::ArtistTracksDirectIterator<LastF
 pub type ArtistAlbumsIterator = iterator::ArtistAlbumsIterator<LastFmEditClientImpl>;
 pub type AlbumTracksIterator = iterator::AlbumTracksIterator<LastFmEditClientImpl>;
 pub type RecentTracksIterator = iterator::RecentTracksIterator<LastFmEditClientImpl>;
+pub type TrackScrobblesIterator = iterator::TrackScrobblesIterator<LastFmEditClientImpl>;
+pub type LovedTracksIterator = iterator::LovedTracksIterator<LastFmEditClientImpl>;
+pub type LibraryTracksIterator = iterator::LibraryTracksIterator<LastFmEditClientImpl>;
+pub type LibraryAlbumsIterator = iterator::LibraryAlbumsIterator<LastFmEditClientImpl>;
 pub type ApiRecentTracksIterator = iterator::ApiRecentTracksIterator<api::LastFmApiClientImpl>;
 pub type SearchTracksIterator = iterator::SearchTracksIterator<LastFmEditClientImpl>;
 pub type SearchAlbumsIterator = iterator::SearchAlbumsIterator<LastFmEditClientImpl>;
@@ -55,7 +91,18 @@ pub use r#trait::{MockLastFmBaseClient, MockLastFmEditClient};
 // Re-export the mock iterator when the mock feature is enabled
 #[cfg(feature = "mock")]
 pub use iterator::MockAsyncPaginatedIterator;
+pub use parse_health::{PageKind, ParseAnomaly, ParseReport};
+pub use response_cache::{
+    CachePolicy, CachedPage, FileResponseCache, InMemoryResponseCache, LibraryChange, PageScope,
+    ResponseCache,
+};
+#[cfg(feature = "session-encryption")]
+pub use session_encryption::SessionPassphrase;
 pub use session_persistence::{SessionManager, SessionPersistence};
+pub use shared_rate_limit::SharedRateLimiter;
+pub use simulation::{
+    ScrobbleIdentity, SimulatedChange, SimulatedEditClient, SimulationLayer, SimulationReport,
+};
 
 // Re-export scraper types for testing
 pub use scraper::Html;
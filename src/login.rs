@@ -1,11 +1,55 @@
 use crate::types::{LastFmEditSession, LastFmError};
 use crate::Result;
+use async_trait::async_trait;
 use http_client::{HttpClient, Request};
 use http_types::{Method, Url};
 use scraper::{Html, Selector};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Supplies the credentials used to log in again when a session expires.
+///
+/// Attach one with
+/// [`LastFmEditClientImpl::with_credential_provider`](crate::LastFmEditClientImpl::with_credential_provider).
+/// It is only asked when a re-login is actually needed, so implementations can read a
+/// keyring or prompt lazily.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// The username and password to log in with.
+    async fn credentials(&self) -> Result<(String, String)>;
+}
+
+/// A [`CredentialProvider`] holding a fixed username and password.
+#[derive(Clone)]
+pub struct StaticCredentials {
+    username: String,
+    password: String,
+}
+
+impl StaticCredentials {
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for StaticCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticCredentials")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for StaticCredentials {
+    async fn credentials(&self) -> Result<(String, String)> {
+        Ok((self.username.clone(), self.password.clone()))
+    }
+}
+
 /// Login functionality separated from the main client
 pub struct LoginManager {
     client: Arc<dyn HttpClient + Send + Sync>,
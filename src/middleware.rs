@@ -0,0 +1,1004 @@
+//! Composable middleware for [`LastFmEditClient`]s.
+//!
+//! A [`Middleware`] sees every read and mutation of the client it wraps and may answer
+//! a mutation itself instead of passing it on. [`Layered`] implements both client
+//! traits once on top of any middleware, so a wrapper only implements the hooks it
+//! needs. Layers stack, the last one added being the outermost:
+//!
+//! ```rust,no_run
+//! # use lastfm_edit::middleware::{AuditLayer, DryRunLayer, LayerExt, LoggingLayer};
+//! # fn example(client: lastfm_edit::LastFmEditClientImpl) -> lastfm_edit::Result<()> {
+//! let dry_run = DryRunLayer::new();
+//! let client = client
+//!     .with_layer(AuditLayer::open("mutations.jsonl")?)
+//!     .with_layer(dry_run.clone())
+//!     .with_layer(LoggingLayer::new());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Here every call is logged, and mutations are recorded by the dry run without ever
+//! reaching the audit log or Last.fm.
+
+use crate::discovery::AsyncDiscoveryIterator;
+use crate::iterator::{
+    AlbumTracksIterator, ArtistAlbumsIterator, ArtistTracksDirectIterator, ArtistTracksIterator,
+    ArtistsIterator, AsyncPaginatedIterator, LibraryAlbumsIterator, LibraryTracksIterator,
+    LovedTracksIterator, RecentTracksIterator, SearchAlbumsIterator, SearchArtistsIterator,
+    SearchTracksIterator, TrackScrobblesIterator,
+};
+use crate::r#trait::{LastFmBaseClient, LastFmEditClient};
+use crate::types::{
+    Album, AlbumPage, Artist, ArtistPage, ClientEvent, ClientEventReceiver, EditResponse,
+    ExactScrobbleEdit, LastFmEditSession, LastFmError, LibraryDateRange, LibrarySort,
+    RateLimitState, RateLimitStateWatcher, ScrobbleEdit, SingleEditResponse, Track, TrackPage,
+};
+use crate::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A read passed through a [`Layered`] client: the trait method and its named
+/// arguments (`artist`, `album`, `album_artist`, `track`, `query`, `range`, `sort`,
+/// `page`, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadRequest {
+    pub method: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl ReadRequest {
+    pub fn new<const N: usize>(method: &'static str, args: [(&'static str, String); N]) -> Self {
+        Self {
+            method,
+            args: args.into(),
+        }
+    }
+
+    /// The value of the argument called `name`, if the method takes one.
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .find(|(arg, _)| *arg == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl std::fmt::Display for ReadRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        write!(f, "{}({})", self.method, args.join(", "))
+    }
+}
+
+/// A change to the user's library, as seen by middleware.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Mutation {
+    /// [`LastFmEditClient::edit_scrobble`]: every scrobble matching the edit
+    Edit {
+        edit: ScrobbleEdit,
+    },
+    /// [`LastFmEditClient::edit_scrobble_single`]
+    EditSingle {
+        edit: ExactScrobbleEdit,
+        max_retries: u32,
+    },
+    Delete {
+        artist: String,
+        track: String,
+        timestamp: u64,
+    },
+    Love {
+        artist: String,
+        track: String,
+    },
+    Unlove {
+        artist: String,
+        track: String,
+    },
+}
+
+/// The result of a [`Mutation`]: an edit response for edits, and whether Last.fm
+/// accepted the request for deletes and (un)loves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum MutationOutcome {
+    Edited { response: EditResponse },
+    Accepted { accepted: bool },
+}
+
+impl MutationOutcome {
+    fn into_edit_response(self) -> Result<EditResponse> {
+        match self {
+            Self::Edited { response } => Ok(response),
+            Self::Accepted { .. } => Err(LastFmError::EditFailed(
+                "Middleware answered an edit without an edit response".to_string(),
+            )),
+        }
+    }
+
+    fn into_accepted(self) -> Result<bool> {
+        match self {
+            Self::Accepted { accepted } => Ok(accepted),
+            Self::Edited { response } => Ok(response.all_successful()),
+        }
+    }
+}
+
+/// Hooks run by a [`Layered`] client around the calls of the client it wraps.
+///
+/// Every hook defaults to doing nothing, so the wrapped client behaves unchanged.
+#[async_trait(?Send)]
+pub trait Middleware {
+    /// Called before a page fetch or lookup is passed on.
+    fn before_read(&self, _request: &ReadRequest) {}
+
+    /// Called after a page fetch or lookup, with how long it took and its error, if any.
+    fn after_read(&self, _request: &ReadRequest, _elapsed: Duration, _error: Option<&LastFmError>) {
+    }
+
+    /// Called with the tracks of every successful track read, which it may change.
+    fn rewrite_tracks(&self, _request: &ReadRequest, _tracks: &mut Vec<Track>) {}
+
+    /// Called with the albums of every successful album read, which it may change.
+    fn rewrite_albums(&self, _request: &ReadRequest, _albums: &mut Vec<Album>) {}
+
+    /// Called with the artists of every successful artist read, which it may change.
+    fn rewrite_artists(&self, _request: &ReadRequest, _artists: &mut Vec<Artist>) {}
+
+    /// Called with the result of
+    /// [`get_scrobble_edit_variations`](LastFmEditClient::get_scrobble_edit_variations),
+    /// which it may replace.
+    fn rewrite_variations(
+        &self,
+        _request: &ReadRequest,
+        variations: Result<Vec<ExactScrobbleEdit>>,
+    ) -> Result<Vec<ExactScrobbleEdit>> {
+        variations
+    }
+
+    /// Called with the inner client's discovery for `edit`, which it may wrap or replace.
+    fn discover(
+        &self,
+        _edit: &ScrobbleEdit,
+        discovery: Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>>,
+    ) -> Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>> {
+        discovery
+    }
+
+    /// Called before a mutation is passed on to `inner`.
+    ///
+    /// Returning an outcome answers the mutation without passing it on; `inner` can be
+    /// used to look up what the mutation would change.
+    async fn before_mutation(
+        &self,
+        _mutation: &Mutation,
+        _inner: &dyn LastFmEditClient,
+    ) -> Result<Option<MutationOutcome>> {
+        Ok(None)
+    }
+
+    /// Called with the result of every mutation, including those answered by
+    /// [`before_mutation`](Self::before_mutation).
+    fn after_mutation(
+        &self,
+        _mutation: &Mutation,
+        _outcome: &Result<MutationOutcome>,
+        _inner: &dyn LastFmEditClient,
+    ) {
+    }
+}
+
+/// Wraps a client into another client, in the style of tower's `Layer`.
+pub trait Layer<C> {
+    type Client;
+
+    fn layer(&self, inner: C) -> Self::Client;
+}
+
+impl<M, C> Layer<C> for M
+where
+    M: Middleware + Clone,
+    C: LastFmEditClient,
+{
+    type Client = Layered<M, C>;
+
+    fn layer(&self, inner: C) -> Self::Client {
+        Layered::new(self.clone(), inner)
+    }
+}
+
+/// Adds [`with_layer`](LayerExt::with_layer) to every client.
+pub trait LayerExt: Sized {
+    /// Wrap this client in `layer`.
+    fn with_layer<L: Layer<Self>>(self, layer: L) -> L::Client {
+        layer.layer(self)
+    }
+}
+
+impl<C: LastFmEditClient> LayerExt for C {}
+
+/// A client that runs `M`'s hooks around every call to the inner client `C`.
+///
+/// Iterators are created over the layered client, so each of their page fetches goes
+/// through the middleware as well. Scrobble discovery runs on the inner client, behind
+/// [`Middleware::discover`].
+pub struct Layered<M, C> {
+    middleware: Arc<M>,
+    inner: Arc<C>,
+}
+
+impl<M, C> Clone for Layered<M, C> {
+    fn clone(&self) -> Self {
+        Self {
+            middleware: self.middleware.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<M: Middleware, C: LastFmEditClient> Layered<M, C> {
+    pub fn new(middleware: M, inner: C) -> Self {
+        Self {
+            middleware: Arc::new(middleware),
+            inner: Arc::new(inner),
+        }
+    }
+
+    pub fn middleware(&self) -> &M {
+        &self.middleware
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    async fn read<T>(
+        &self,
+        request: &ReadRequest,
+        call: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        self.middleware.before_read(request);
+        let started = Instant::now();
+        let result = call.await;
+        self.middleware
+            .after_read(request, started.elapsed(), result.as_ref().err());
+        result
+    }
+
+    async fn read_tracks(
+        &self,
+        request: ReadRequest,
+        call: impl Future<Output = Result<TrackPage>>,
+    ) -> Result<TrackPage> {
+        let mut page = self.read(&request, call).await?;
+        self.middleware.rewrite_tracks(&request, &mut page.tracks);
+        Ok(page)
+    }
+
+    async fn read_albums(
+        &self,
+        request: ReadRequest,
+        call: impl Future<Output = Result<AlbumPage>>,
+    ) -> Result<AlbumPage> {
+        let mut page = self.read(&request, call).await?;
+        self.middleware.rewrite_albums(&request, &mut page.albums);
+        Ok(page)
+    }
+
+    async fn read_artists(
+        &self,
+        request: ReadRequest,
+        call: impl Future<Output = Result<ArtistPage>>,
+    ) -> Result<ArtistPage> {
+        let mut page = self.read(&request, call).await?;
+        self.middleware.rewrite_artists(&request, &mut page.artists);
+        Ok(page)
+    }
+
+    async fn mutate(&self, mutation: Mutation) -> Result<MutationOutcome> {
+        let outcome = match self
+            .middleware
+            .before_mutation(&mutation, self.inner.as_ref())
+            .await
+        {
+            Ok(Some(outcome)) => Ok(outcome),
+            Ok(None) => self.forward(&mutation).await,
+            Err(e) => Err(e),
+        };
+        self.middleware
+            .after_mutation(&mutation, &outcome, self.inner.as_ref());
+        outcome
+    }
+
+    async fn forward(&self, mutation: &Mutation) -> Result<MutationOutcome> {
+        let inner = self.inner.as_ref();
+        Ok(match mutation {
+            Mutation::Edit { edit } => MutationOutcome::Edited {
+                response: inner.edit_scrobble(edit).await?,
+            },
+            Mutation::EditSingle { edit, max_retries } => MutationOutcome::Edited {
+                response: inner.edit_scrobble_single(edit, *max_retries).await?,
+            },
+            Mutation::Delete {
+                artist,
+                track,
+                timestamp,
+            } => MutationOutcome::Accepted {
+                accepted: inner.delete_scrobble(artist, track, *timestamp).await?,
+            },
+            Mutation::Love { artist, track } => MutationOutcome::Accepted {
+                accepted: inner.love_track(artist, track).await?,
+            },
+            Mutation::Unlove { artist, track } => MutationOutcome::Accepted {
+                accepted: inner.unlove_track(artist, track).await?,
+            },
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl<M: Middleware, C: LastFmEditClient> LastFmBaseClient for Layered<M, C> {
+    async fn get_artists_page(&self, page: u32) -> Result<ArtistPage> {
+        let request = ReadRequest::new("get_artists_page", [("page", page.to_string())]);
+        self.read_artists(request, self.inner.get_artists_page(page))
+            .await
+    }
+
+    async fn get_artist_tracks_page(&self, artist: &str, page: u32) -> Result<TrackPage> {
+        let request = ReadRequest::new(
+            "get_artist_tracks_page",
+            [("artist", artist.into()), ("page", page.to_string())],
+        );
+        self.read_tracks(request, self.inner.get_artist_tracks_page(artist, page))
+            .await
+    }
+
+    async fn get_artist_albums_page(&self, artist: &str, page: u32) -> Result<AlbumPage> {
+        let request = ReadRequest::new(
+            "get_artist_albums_page",
+            [("artist", artist.into()), ("page", page.to_string())],
+        );
+        self.read_albums(request, self.inner.get_artist_albums_page(artist, page))
+            .await
+    }
+
+    async fn get_loved_tracks_page(&self, page: u32) -> Result<TrackPage> {
+        let request = ReadRequest::new("get_loved_tracks_page", [("page", page.to_string())]);
+        self.read_tracks(request, self.inner.get_loved_tracks_page(page))
+            .await
+    }
+
+    async fn get_library_tracks_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<TrackPage> {
+        let request = ReadRequest::new(
+            "get_library_tracks_page",
+            [
+                ("range", range.to_string()),
+                ("sort", format!("{sort:?}")),
+                ("page", page.to_string()),
+            ],
+        );
+        self.read_tracks(
+            request,
+            self.inner.get_library_tracks_page(range, sort, page),
+        )
+        .await
+    }
+
+    async fn get_library_albums_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<AlbumPage> {
+        let request = ReadRequest::new(
+            "get_library_albums_page",
+            [
+                ("range", range.to_string()),
+                ("sort", format!("{sort:?}")),
+                ("page", page.to_string()),
+            ],
+        );
+        self.read_albums(
+            request,
+            self.inner.get_library_albums_page(range, sort, page),
+        )
+        .await
+    }
+
+    async fn get_artists_page_in_range(
+        &self,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<ArtistPage> {
+        let request = ReadRequest::new(
+            "get_artists_page_in_range",
+            [("range", range.to_string()), ("page", page.to_string())],
+        );
+        self.read_artists(request, self.inner.get_artists_page_in_range(range, page))
+            .await
+    }
+
+    async fn get_artist_tracks_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<TrackPage> {
+        let request = ReadRequest::new(
+            "get_artist_tracks_page_in_range",
+            [
+                ("artist", artist.into()),
+                ("range", range.to_string()),
+                ("page", page.to_string()),
+            ],
+        );
+        self.read_tracks(
+            request,
+            self.inner
+                .get_artist_tracks_page_in_range(artist, range, page),
+        )
+        .await
+    }
+
+    async fn get_artist_albums_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<AlbumPage> {
+        let request = ReadRequest::new(
+            "get_artist_albums_page_in_range",
+            [
+                ("artist", artist.into()),
+                ("range", range.to_string()),
+                ("page", page.to_string()),
+            ],
+        );
+        self.read_albums(
+            request,
+            self.inner
+                .get_artist_albums_page_in_range(artist, range, page),
+        )
+        .await
+    }
+
+    async fn get_album_tracks_page(
+        &self,
+        album_name: &str,
+        artist_name: &str,
+        page: u32,
+    ) -> Result<TrackPage> {
+        let request = ReadRequest::new(
+            "get_album_tracks_page",
+            [
+                ("album", album_name.into()),
+                ("album_artist", artist_name.into()),
+                ("page", page.to_string()),
+            ],
+        );
+        self.read_tracks(
+            request,
+            self.inner
+                .get_album_tracks_page(album_name, artist_name, page),
+        )
+        .await
+    }
+
+    async fn get_recent_tracks_page(&self, page: u32) -> Result<TrackPage> {
+        let request = ReadRequest::new("get_recent_tracks_page", [("page", page.to_string())]);
+        self.read_tracks(request, self.inner.get_recent_tracks_page(page))
+            .await
+    }
+
+    async fn get_track_scrobbles_page(
+        &self,
+        artist: &str,
+        track: &str,
+        page: u32,
+    ) -> Result<TrackPage> {
+        let request = ReadRequest::new(
+            "get_track_scrobbles_page",
+            [
+                ("artist", artist.into()),
+                ("track", track.into()),
+                ("page", page.to_string()),
+            ],
+        );
+        self.read_tracks(
+            request,
+            self.inner.get_track_scrobbles_page(artist, track, page),
+        )
+        .await
+    }
+
+    async fn search_tracks_page(&self, query: &str, page: u32) -> Result<TrackPage> {
+        let request = ReadRequest::new(
+            "search_tracks_page",
+            [("query", query.into()), ("page", page.to_string())],
+        );
+        self.read_tracks(request, self.inner.search_tracks_page(query, page))
+            .await
+    }
+
+    async fn search_albums_page(&self, query: &str, page: u32) -> Result<AlbumPage> {
+        let request = ReadRequest::new(
+            "search_albums_page",
+            [("query", query.into()), ("page", page.to_string())],
+        );
+        self.read_albums(request, self.inner.search_albums_page(query, page))
+            .await
+    }
+
+    async fn search_artists_page(&self, query: &str, page: u32) -> Result<ArtistPage> {
+        let request = ReadRequest::new(
+            "search_artists_page",
+            [("query", query.into()), ("page", page.to_string())],
+        );
+        self.read_artists(request, self.inner.search_artists_page(query, page))
+            .await
+    }
+
+    fn username(&self) -> String {
+        self.inner.username()
+    }
+
+    fn get_session(&self) -> LastFmEditSession {
+        self.inner.get_session()
+    }
+
+    fn subscribe(&self) -> ClientEventReceiver {
+        self.inner.subscribe()
+    }
+
+    fn latest_event(&self) -> Option<ClientEvent> {
+        self.inner.latest_event()
+    }
+
+    fn rate_limit_state(&self) -> RateLimitState {
+        self.inner.rate_limit_state()
+    }
+
+    fn watch_rate_limit_state(&self) -> RateLimitStateWatcher {
+        self.inner.watch_rate_limit_state()
+    }
+
+    async fn validate_session(&self) -> bool {
+        self.inner.validate_session().await
+    }
+
+    async fn find_recent_scrobble_for_track(
+        &self,
+        track_name: &str,
+        artist_name: &str,
+        max_pages: u32,
+    ) -> Result<Option<Track>> {
+        let request = ReadRequest::new(
+            "find_recent_scrobble_for_track",
+            [
+                ("track", track_name.into()),
+                ("artist", artist_name.into()),
+                ("max_pages", max_pages.to_string()),
+            ],
+        );
+        let found = self
+            .read(
+                &request,
+                self.inner
+                    .find_recent_scrobble_for_track(track_name, artist_name, max_pages),
+            )
+            .await?;
+        let mut tracks: Vec<Track> = found.into_iter().collect();
+        self.middleware.rewrite_tracks(&request, &mut tracks);
+        Ok(tracks.into_iter().next())
+    }
+
+    fn cancel(&self) {
+        self.inner.cancel()
+    }
+
+    fn reset_cancel(&self) {
+        self.inner.reset_cancel()
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
+
+#[async_trait(?Send)]
+impl<M, C> LastFmEditClient for Layered<M, C>
+where
+    M: Middleware + 'static,
+    C: LastFmEditClient + 'static,
+{
+    async fn edit_scrobble(&self, edit: &ScrobbleEdit) -> Result<EditResponse> {
+        self.mutate(Mutation::Edit { edit: edit.clone() })
+            .await?
+            .into_edit_response()
+    }
+
+    async fn edit_scrobble_single(
+        &self,
+        exact_edit: &ExactScrobbleEdit,
+        max_retries: u32,
+    ) -> Result<EditResponse> {
+        self.mutate(Mutation::EditSingle {
+            edit: exact_edit.clone(),
+            max_retries,
+        })
+        .await?
+        .into_edit_response()
+    }
+
+    async fn delete_scrobble(
+        &self,
+        artist_name: &str,
+        track_name: &str,
+        timestamp: u64,
+    ) -> Result<bool> {
+        self.mutate(Mutation::Delete {
+            artist: artist_name.to_string(),
+            track: track_name.to_string(),
+            timestamp,
+        })
+        .await?
+        .into_accepted()
+    }
+
+    async fn love_track(&self, artist_name: &str, track_name: &str) -> Result<bool> {
+        self.mutate(Mutation::Love {
+            artist: artist_name.to_string(),
+            track: track_name.to_string(),
+        })
+        .await?
+        .into_accepted()
+    }
+
+    async fn unlove_track(&self, artist_name: &str, track_name: &str) -> Result<bool> {
+        self.mutate(Mutation::Unlove {
+            artist: artist_name.to_string(),
+            track: track_name.to_string(),
+        })
+        .await?
+        .into_accepted()
+    }
+
+    async fn get_scrobble_edit_variations(
+        &self,
+        track_name: &str,
+        artist_name: &str,
+    ) -> Result<Vec<ExactScrobbleEdit>> {
+        let request = ReadRequest::new(
+            "get_scrobble_edit_variations",
+            [("track", track_name.into()), ("artist", artist_name.into())],
+        );
+        let variations = self
+            .read(
+                &request,
+                self.inner
+                    .get_scrobble_edit_variations(track_name, artist_name),
+            )
+            .await;
+        self.middleware.rewrite_variations(&request, variations)
+    }
+
+    fn discover_scrobbles(
+        &self,
+        edit: ScrobbleEdit,
+    ) -> Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>> {
+        let discovery = self.inner.discover_scrobbles(edit.clone());
+        self.middleware.discover(&edit, discovery)
+    }
+
+    fn artists(&self) -> Box<dyn AsyncPaginatedIterator<Artist>> {
+        Box::new(ArtistsIterator::new(self.clone()))
+    }
+
+    fn artist_tracks(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(ArtistTracksIterator::new(self.clone(), artist.to_string()))
+    }
+
+    fn artist_tracks_direct(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(ArtistTracksDirectIterator::new(
+            self.clone(),
+            artist.to_string(),
+        ))
+    }
+
+    fn artist_albums(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Album>> {
+        Box::new(ArtistAlbumsIterator::new(self.clone(), artist.to_string()))
+    }
+
+    fn track_scrobbles(&self, artist: &str, track: &str) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(TrackScrobblesIterator::new(
+            self.clone(),
+            artist.to_string(),
+            track.to_string(),
+        ))
+    }
+
+    fn loved_tracks(&self) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(LovedTracksIterator::new(self.clone()))
+    }
+
+    fn library_tracks(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    ) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(LibraryTracksIterator::new(self.clone(), range, sort))
+    }
+
+    fn library_albums(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    ) -> Box<dyn AsyncPaginatedIterator<Album>> {
+        Box::new(LibraryAlbumsIterator::new(self.clone(), range, sort))
+    }
+
+    fn artists_in_range(&self, range: LibraryDateRange) -> Box<dyn AsyncPaginatedIterator<Artist>> {
+        Box::new(ArtistsIterator::with_range(self.clone(), range))
+    }
+
+    fn artist_tracks_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+    ) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(ArtistTracksDirectIterator::with_range(
+            self.clone(),
+            artist.to_string(),
+            range,
+        ))
+    }
+
+    fn artist_albums_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+    ) -> Box<dyn AsyncPaginatedIterator<Album>> {
+        Box::new(ArtistAlbumsIterator::with_range(
+            self.clone(),
+            artist.to_string(),
+            range,
+        ))
+    }
+
+    fn album_tracks(
+        &self,
+        album_name: &str,
+        artist_name: &str,
+    ) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(AlbumTracksIterator::new(
+            self.clone(),
+            album_name.to_string(),
+            artist_name.to_string(),
+        ))
+    }
+
+    fn recent_tracks(&self) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(RecentTracksIterator::new(self.clone()))
+    }
+
+    fn recent_tracks_from_page(
+        &self,
+        starting_page: u32,
+    ) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(RecentTracksIterator::with_starting_page(
+            self.clone(),
+            starting_page,
+        ))
+    }
+
+    fn search_tracks(&self, query: &str) -> Box<dyn AsyncPaginatedIterator<Track>> {
+        Box::new(SearchTracksIterator::new(self.clone(), query.to_string()))
+    }
+
+    fn search_albums(&self, query: &str) -> Box<dyn AsyncPaginatedIterator<Album>> {
+        Box::new(SearchAlbumsIterator::new(self.clone(), query.to_string()))
+    }
+
+    fn search_artists(&self, query: &str) -> Box<dyn AsyncPaginatedIterator<Artist>> {
+        Box::new(SearchArtistsIterator::new(self.clone(), query.to_string()))
+    }
+}
+
+// =============================================================================
+// BUILT-IN LAYERS
+// =============================================================================
+
+/// Logs every read at debug level and every mutation at info level.
+#[derive(Debug, Clone, Default)]
+pub struct LoggingLayer;
+
+impl LoggingLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait(?Send)]
+impl Middleware for LoggingLayer {
+    fn after_read(&self, request: &ReadRequest, elapsed: Duration, error: Option<&LastFmError>) {
+        match error {
+            None => log::debug!("{request} took {}ms", elapsed.as_millis()),
+            Some(e) => log::debug!("{request} failed after {}ms: {e}", elapsed.as_millis()),
+        }
+    }
+
+    fn after_mutation(
+        &self,
+        mutation: &Mutation,
+        outcome: &Result<MutationOutcome>,
+        _inner: &dyn LastFmEditClient,
+    ) {
+        match outcome {
+            Ok(outcome) => log::info!("{mutation:?} -> {outcome:?}"),
+            Err(e) => log::info!("{mutation:?} failed: {e}"),
+        }
+    }
+}
+
+/// Blocks every mutation and records it instead, answering as if Last.fm accepted it.
+///
+/// Reads still reach Last.fm. Pattern edits are recorded as given; the response lists
+/// each scrobble they would have changed, found by discovery on the inner client.
+/// Clones share their record.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunLayer {
+    recorded: Arc<Mutex<Vec<Mutation>>>,
+}
+
+impl DryRunLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The mutations blocked so far, oldest first.
+    pub fn recorded(&self) -> Vec<Mutation> {
+        self.recorded.lock().unwrap().clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl Middleware for DryRunLayer {
+    async fn before_mutation(
+        &self,
+        mutation: &Mutation,
+        inner: &dyn LastFmEditClient,
+    ) -> Result<Option<MutationOutcome>> {
+        let outcome = match mutation {
+            Mutation::Edit { edit } => {
+                let discovered = inner.discover_scrobble_edit_variations(edit).await?;
+                if discovered.is_empty() {
+                    return Err(edit.no_scrobbles_error());
+                }
+                let results = discovered
+                    .iter()
+                    .map(|variation| dry_run_result(edit.apply_to(variation)))
+                    .collect();
+                MutationOutcome::Edited {
+                    response: EditResponse::from_results(results),
+                }
+            }
+            Mutation::EditSingle { edit, .. } => MutationOutcome::Edited {
+                response: EditResponse::from_results(vec![dry_run_result(edit.clone())]),
+            },
+            Mutation::Delete { .. } | Mutation::Love { .. } | Mutation::Unlove { .. } => {
+                MutationOutcome::Accepted { accepted: true }
+            }
+        };
+
+        log::info!("Dry run: not sending {mutation:?}");
+        self.recorded.lock().unwrap().push(mutation.clone());
+        Ok(Some(outcome))
+    }
+}
+
+pub(crate) fn dry_run_result(exact_edit: ExactScrobbleEdit) -> SingleEditResponse {
+    SingleEditResponse {
+        success: true,
+        message: Some("Dry run: edit not sent".to_string()),
+        album_info: Some(format!(
+            "{} by {}",
+            exact_edit.album_name_original, exact_edit.album_artist_name_original
+        )),
+        exact_scrobble_edit: exact_edit,
+        verification: None,
+        failure_kind: None,
+    }
+}
+
+/// One line of an [`AuditLayer`] log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unix timestamp (seconds) of when the mutation finished
+    pub timestamp: u64,
+    pub username: String,
+    pub mutation: Mutation,
+    /// The outcome, or `None` when the mutation failed
+    pub outcome: Option<MutationOutcome>,
+    pub error: Option<String>,
+}
+
+/// Appends every mutation and its outcome to a JSON Lines file.
+///
+/// Clones append to the same file.
+#[derive(Debug, Clone)]
+pub struct AuditLayer {
+    file: Arc<Mutex<File>>,
+}
+
+impl AuditLayer {
+    /// Append to the log at `path`, creating it (and its directory) if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Read back the entries of an audit log.
+    pub fn read_entries(path: impl AsRef<Path>) -> Result<Vec<AuditEntry>> {
+        std::fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| LastFmError::Parse(format!("Invalid audit log entry: {e}")))
+            })
+            .collect()
+    }
+}
+
+#[async_trait(?Send)]
+impl Middleware for AuditLayer {
+    fn after_mutation(
+        &self,
+        mutation: &Mutation,
+        outcome: &Result<MutationOutcome>,
+        inner: &dyn LastFmEditClient,
+    ) {
+        let entry = AuditEntry {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            username: inner.username(),
+            mutation: mutation.clone(),
+            outcome: outcome.as_ref().ok().cloned(),
+            error: outcome.as_ref().err().map(|e| e.to_string()),
+        };
+
+        let written = serde_json::to_string(&entry)
+            .map_err(std::io::Error::other)
+            .and_then(|line| writeln!(self.file.lock().unwrap(), "{line}"));
+        if let Err(e) = written {
+            log::warn!("Failed to write audit log entry for {mutation:?}: {e}");
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Adaptive request pacing.
+//!
+//! [`AdaptivePacer`] keeps one delay per [`TrafficClass`] and hands out send slots spaced
+//! by that delay. The delay shrinks additively while requests succeed and grows
+//! multiplicatively when Last.fm rate limits, so long bulk jobs settle near the fastest
+//! rate Last.fm tolerates. [`LastFmEditClientImpl`](crate::LastFmEditClientImpl) drives it
+//! from its own request events when [`AdaptivePacingConfig::enabled`] is set.
+
+use crate::types::{AdaptivePacingConfig, OperationalDelayConfig, TrafficClass};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    delay_ms: u64,
+    /// Earliest time the next request of this class may be sent
+    next_slot: Option<Instant>,
+}
+
+/// Per-class AIMD pacing state, shared by all clones of a client.
+#[derive(Debug)]
+pub struct AdaptivePacer {
+    config: AdaptivePacingConfig,
+    budgets: Mutex<[Budget; 3]>,
+}
+
+fn index(class: TrafficClass) -> usize {
+    match class {
+        TrafficClass::Get => 0,
+        TrafficClass::Edit => 1,
+        TrafficClass::Delete => 2,
+    }
+}
+
+impl AdaptivePacer {
+    /// Create a pacer whose initial delays are the fixed operational delays.
+    pub fn new(config: AdaptivePacingConfig, initial: &OperationalDelayConfig) -> Self {
+        let budget = |delay_ms: u64| Budget {
+            delay_ms: delay_ms.clamp(config.min_delay_ms, config.max_delay_ms),
+            next_slot: None,
+        };
+        let budgets = [
+            budget(initial.get_delay_ms),
+            budget(initial.edit_delay_ms),
+            budget(initial.delete_delay_ms),
+        ];
+        Self {
+            config,
+            budgets: Mutex::new(budgets),
+        }
+    }
+
+    /// Current delay between requests of `class`, in milliseconds.
+    pub fn delay_ms(&self, class: TrafficClass) -> u64 {
+        self.budgets.lock().unwrap()[index(class)].delay_ms
+    }
+
+    /// Claim the next send slot for `class` and return how long to wait for it.
+    ///
+    /// Slots are handed out in call order, so concurrent callers queue up behind each other.
+    pub fn reserve(&self, class: TrafficClass) -> Duration {
+        let now = Instant::now();
+        let mut budgets = self.budgets.lock().unwrap();
+        let budget = &mut budgets[index(class)];
+        let slot = budget.next_slot.map_or(now, |next| next.max(now));
+        budget.next_slot = Some(slot + Duration::from_millis(budget.delay_ms));
+        slot - now
+    }
+
+    /// Shorten the delay after a successful request. Returns the new delay if it changed.
+    pub fn record_success(&self, class: TrafficClass) -> Option<u64> {
+        let mut budgets = self.budgets.lock().unwrap();
+        let budget = &mut budgets[index(class)];
+        let delay_ms = budget
+            .delay_ms
+            .saturating_sub(self.config.speedup_step_ms)
+            .max(self.config.min_delay_ms);
+        Self::update(budget, delay_ms)
+    }
+
+    /// Back off after a rate limit. Returns the new delay if it changed.
+    pub fn record_rate_limit(&self, class: TrafficClass) -> Option<u64> {
+        let mut budgets = self.budgets.lock().unwrap();
+        let budget = &mut budgets[index(class)];
+        let delay_ms = budget
+            .delay_ms
+            .saturating_mul(self.config.backoff_factor)
+            .max(self.config.backoff_floor_ms)
+            .clamp(self.config.min_delay_ms, self.config.max_delay_ms);
+        Self::update(budget, delay_ms)
+    }
+
+    fn update(budget: &mut Budget, delay_ms: u64) -> Option<u64> {
+        if budget.delay_ms == delay_ms {
+            return None;
+        }
+        budget.delay_ms = delay_ms;
+        Some(delay_ms)
+    }
+}
+
+/// Requests per minute allowed by a delay between requests.
+pub fn requests_per_minute(delay_ms: u64) -> u64 {
+    60_000 / delay_ms.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pacer() -> AdaptivePacer {
+        AdaptivePacer::new(
+            AdaptivePacingConfig::enabled().with_delay_bounds(100, 10_000),
+            &OperationalDelayConfig::with_delays(1_000, 1_000),
+        )
+    }
+
+    #[test]
+    fn successes_speed_up_and_rate_limits_back_off() {
+        let pacer = pacer();
+        // The GET delay starts at 0 and is raised to the minimum.
+        assert_eq!(pacer.delay_ms(TrafficClass::Get), 100);
+        assert_eq!(pacer.record_success(TrafficClass::Get), None);
+
+        assert_eq!(pacer.record_success(TrafficClass::Edit), Some(900));
+        assert_eq!(pacer.record_rate_limit(TrafficClass::Edit), Some(2_000));
+        assert_eq!(pacer.record_rate_limit(TrafficClass::Edit), Some(4_000));
+        assert_eq!(pacer.record_rate_limit(TrafficClass::Edit), Some(8_000));
+        assert_eq!(pacer.record_rate_limit(TrafficClass::Edit), Some(10_000));
+        assert_eq!(pacer.record_rate_limit(TrafficClass::Edit), None);
+
+        // Budgets are independent.
+        assert_eq!(pacer.delay_ms(TrafficClass::Delete), 1_000);
+    }
+
+    #[test]
+    fn reserved_slots_are_spaced_by_the_delay() {
+        let pacer = pacer();
+        assert_eq!(pacer.reserve(TrafficClass::Delete), Duration::ZERO);
+        let second = pacer.reserve(TrafficClass::Delete);
+        let third = pacer.reserve(TrafficClass::Delete);
+        assert!(second > Duration::from_millis(900) && second <= Duration::from_millis(1_000));
+        assert!(third > Duration::from_millis(1_900) && third <= Duration::from_millis(2_000));
+        assert_eq!(pacer.reserve(TrafficClass::Edit), Duration::ZERO);
+    }
+}
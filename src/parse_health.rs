@@ -0,0 +1,209 @@
+//! Detection of Last.fm markup changes that break the HTML parsers.
+//!
+//! When Last.fm changes its markup, the selectors in [`parsing`](crate::parsing) stop
+//! matching and the parsers quietly return empty pages. Every page the client parses is
+//! also checked with [`LastFmParser::check_page`](crate::parsing::LastFmParser::check_page):
+//! the [`ParseReport`] lists how many elements each selector matched, and any
+//! [`ParseAnomaly`] is broadcast as [`ClientEvent::ParseDrift`](crate::ClientEvent::ParseDrift).
+//! [`self_check`] fetches one page of every kind and collects the reports, which is what
+//! `lastfm-edit selfcheck` prints.
+
+use crate::{LastFmEditClientImpl, LastFmError, LibraryDateRange, LibrarySort, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The kinds of Last.fm pages the client parses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageKind {
+    /// `/user/{user}/library` (also used for the per-track scrobble list)
+    RecentScrobbles,
+    /// `/user/{user}/library/artists`
+    Artists,
+    /// `/user/{user}/library/music/{artist}/+tracks` and album track listings
+    ArtistTracks,
+    /// `/user/{user}/library/music/{artist}/+albums`
+    ArtistAlbums,
+    /// `/user/{user}/library/tracks/search`
+    TrackSearch,
+    /// `/user/{user}/library/albums/search`
+    AlbumSearch,
+    /// `/user/{user}/library/artists/search`
+    ArtistSearch,
+    /// `/user/{user}/library/tracks`
+    LibraryTracks,
+    /// `/user/{user}/library/albums`
+    LibraryAlbums,
+    /// `/user/{user}/loved`
+    LovedTracks,
+}
+
+impl PageKind {
+    /// Every page kind, in the order `self_check` visits them.
+    pub const ALL: [PageKind; 10] = [
+        PageKind::RecentScrobbles,
+        PageKind::Artists,
+        PageKind::ArtistTracks,
+        PageKind::ArtistAlbums,
+        PageKind::TrackSearch,
+        PageKind::AlbumSearch,
+        PageKind::ArtistSearch,
+        PageKind::LibraryTracks,
+        PageKind::LibraryAlbums,
+        PageKind::LovedTracks,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PageKind::RecentScrobbles => "recent_scrobbles",
+            PageKind::Artists => "artists",
+            PageKind::ArtistTracks => "artist_tracks",
+            PageKind::ArtistAlbums => "artist_albums",
+            PageKind::TrackSearch => "track_search",
+            PageKind::AlbumSearch => "album_search",
+            PageKind::ArtistSearch => "artist_search",
+            PageKind::LibraryTracks => "library_tracks",
+            PageKind::LibraryAlbums => "library_albums",
+            PageKind::LovedTracks => "loved_tracks",
+        }
+    }
+}
+
+impl fmt::Display for PageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How many elements one selector matched on a page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectorMatch {
+    pub selector: String,
+    /// Whether the parser needs this selector to find anything on a non-empty page
+    pub required: bool,
+    pub matches: usize,
+}
+
+/// A sign that the page markup no longer fits the parser.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ParseAnomaly {
+    /// Some rows of the listing could not be parsed (a field selector stopped matching)
+    RowsMissingFields { failed: usize, rows: usize },
+    /// Nothing was parsed although the pagination says this page should have entries
+    EmptyPageWithPagination {
+        has_next_page: bool,
+        total_pages: Option<u32>,
+    },
+}
+
+/// What a parser found on one page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseReport {
+    pub page: PageKind,
+    pub page_number: u32,
+    pub selectors: Vec<SelectorMatch>,
+    /// Listing rows found on the page
+    pub rows: usize,
+    /// Rows that parsed into an entry
+    pub parsed: usize,
+    pub anomalies: Vec<ParseAnomaly>,
+}
+
+impl ParseReport {
+    /// Whether no anomaly was found.
+    pub fn is_healthy(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// Required selectors that matched nothing.
+    pub fn missing_selectors(&self) -> impl Iterator<Item = &str> {
+        self.selectors
+            .iter()
+            .filter(|s| s.required && s.matches == 0)
+            .map(|s| s.selector.as_str())
+    }
+}
+
+/// Outcome of checking one page kind in [`self_check`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfCheckEntry {
+    pub page: PageKind,
+    /// The parse report, when the page could be fetched
+    pub report: Option<ParseReport>,
+    /// Why the page could not be fetched or parsed
+    pub error: Option<String>,
+}
+
+/// Fetch the first page of every [`PageKind`] and collect its parse report.
+///
+/// `artist` picks the artist track and album listings and defaults to the artist of the
+/// latest scrobble; `query` is used for all three searches and defaults to the artist.
+/// Failed fetches are recorded in the entry rather than aborting the check.
+pub async fn self_check(
+    client: &LastFmEditClientImpl,
+    artist: Option<&str>,
+    query: Option<&str>,
+) -> Vec<SelfCheckEntry> {
+    let recent = client.get_recent_tracks_page(1).await;
+    let artist = artist.map(str::to_string).or_else(|| {
+        recent
+            .as_ref()
+            .ok()
+            .and_then(|page| page.tracks.first())
+            .map(|track| track.artist.clone())
+    });
+    let query = query.map(str::to_string).or_else(|| artist.clone());
+    log::info!("Checking parsers with artist {artist:?} and search query {query:?}");
+
+    let mut entries = vec![entry(client, PageKind::RecentScrobbles, recent.map(drop))];
+    for page in PageKind::ALL.into_iter().skip(1) {
+        let fetched = match (page, &artist, &query) {
+            (PageKind::Artists, _, _) => client.get_artists_page(1).await.map(drop),
+            (PageKind::ArtistTracks, Some(artist), _) => {
+                client.get_artist_tracks_page(artist, 1).await.map(drop)
+            }
+            (PageKind::ArtistAlbums, Some(artist), _) => {
+                client.get_artist_albums_page(artist, 1).await.map(drop)
+            }
+            (PageKind::TrackSearch, _, Some(query)) => {
+                client.search_tracks_page(query, 1).await.map(drop)
+            }
+            (PageKind::AlbumSearch, _, Some(query)) => {
+                client.search_albums_page(query, 1).await.map(drop)
+            }
+            (PageKind::ArtistSearch, _, Some(query)) => {
+                client.search_artists_page(query, 1).await.map(drop)
+            }
+            (PageKind::LibraryTracks, _, _) => client
+                .get_library_tracks_page(LibraryDateRange::AllTime, LibrarySort::default(), 1)
+                .await
+                .map(drop),
+            (PageKind::LibraryAlbums, _, _) => client
+                .get_library_albums_page(LibraryDateRange::AllTime, LibrarySort::default(), 1)
+                .await
+                .map(drop),
+            (PageKind::LovedTracks, _, _) => client.get_loved_tracks_page(1).await.map(drop),
+            _ => Err(LastFmError::Parse(
+                "No artist to check: no recent scrobbles and none given".to_string(),
+            )),
+        };
+        entries.push(entry(client, page, fetched));
+    }
+    entries
+}
+
+fn entry(client: &LastFmEditClientImpl, page: PageKind, fetched: Result<()>) -> SelfCheckEntry {
+    match fetched {
+        Ok(()) => SelfCheckEntry {
+            page,
+            report: client.last_parse_report(page),
+            error: None,
+        },
+        Err(e) => SelfCheckEntry {
+            page,
+            report: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
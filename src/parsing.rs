@@ -4,6 +4,7 @@
 //! and other data from Last.fm web pages. These functions are primarily pure
 //! functions that take HTML documents and return structured data.
 
+use crate::parse_health::{PageKind, ParseAnomaly, ParseReport, SelectorMatch};
 use crate::{Album, AlbumPage, Artist, ArtistPage, LastFmError, Result, Track, TrackPage};
 use scraper::{Html, Selector};
 
@@ -84,6 +85,8 @@ impl LastFmParser {
         // For recent scrobbles, playcount is typically 1 since they're individual scrobbles
         let playcount = 1;
 
+        let loved = self.extract_loved_state(row);
+
         Ok(Track {
             name,
             artist,
@@ -91,6 +94,11 @@ impl LastFmParser {
             timestamp,
             album,
             album_artist,
+            loved,
+            track_mbid: None,
+            artist_mbid: None,
+            album_mbid: None,
+            image_url: None,
         })
     }
 
@@ -165,6 +173,18 @@ impl LastFmParser {
         None
     }
 
+    /// Read the love button of a row: `Some(true)` for a filled heart.
+    ///
+    /// `None` when the row has no love button (e.g. someone else's library).
+    fn extract_loved_state(&self, row: &scraper::ElementRef) -> Option<bool> {
+        let toggle_selector =
+            Selector::parse(".chartlist-loved [data-toggle-button-current-state]").unwrap();
+        row.select(&toggle_selector)
+            .next()
+            .and_then(|el| el.value().attr("data-toggle-button-current-state"))
+            .map(|state| state == "loved")
+    }
+
     /// Parse a tracks page into a `TrackPage` structure
     pub fn parse_tracks_page(
         &self,
@@ -229,6 +249,11 @@ impl LastFmParser {
                         timestamp,
                         album: album.map(|a| a.to_string()),
                         album_artist: None, // Not available in aggregate track listings
+                        loved: None,
+                        track_mbid: None,
+                        artist_mbid: None,
+                        album_mbid: None,
+                        image_url: None,
                     };
                     tracks.push(track);
                     log::debug!("Added track '{track_name}' with {playcount} plays");
@@ -294,6 +319,11 @@ impl LastFmParser {
             timestamp: None,    // Not available in table parsing mode
             album: None,        // Not available in table parsing mode
             album_artist: None, // Not available in table parsing mode
+            loved: self.extract_loved_state(row),
+            track_mbid: None,
+            artist_mbid: None,
+            album_mbid: None,
+            image_url: None,
         })
     }
 
@@ -472,6 +502,51 @@ impl LastFmParser {
         Ok(artists)
     }
 
+    /// Parse a page of the whole-library track listing (`/user/{username}/library/tracks`).
+    ///
+    /// The rows have the same shape as track search results: name, artist and play count.
+    pub fn parse_library_tracks_page(
+        &self,
+        document: &Html,
+        page_number: u32,
+    ) -> Result<TrackPage> {
+        let tracks = self.parse_track_search_results(document)?;
+        let (has_next_page, total_pages) = self.parse_pagination(document, page_number)?;
+        Ok(TrackPage {
+            tracks,
+            page_number,
+            has_next_page,
+            total_pages,
+        })
+    }
+
+    /// Parse a page of the loved tracks listing (`/user/{username}/loved`).
+    ///
+    /// Every track on it is marked as loved.
+    pub fn parse_loved_tracks_page(&self, document: &Html, page_number: u32) -> Result<TrackPage> {
+        let mut page = self.parse_library_tracks_page(document, page_number)?;
+        for track in &mut page.tracks {
+            track.loved = Some(true);
+        }
+        Ok(page)
+    }
+
+    /// Parse a page of the whole-library album listing (`/user/{username}/library/albums`).
+    pub fn parse_library_albums_page(
+        &self,
+        document: &Html,
+        page_number: u32,
+    ) -> Result<AlbumPage> {
+        let albums = self.parse_album_search_results(document)?;
+        let (has_next_page, total_pages) = self.parse_pagination(document, page_number)?;
+        Ok(AlbumPage {
+            albums,
+            page_number,
+            has_next_page,
+            total_pages,
+        })
+    }
+
     /// Parse a single artist row from search results
     fn parse_search_artist_row(&self, row: &scraper::ElementRef) -> Result<Artist> {
         // Extract artist name from the name column
@@ -529,6 +604,11 @@ impl LastFmParser {
             timestamp,
             album,
             album_artist,
+            loved: self.extract_loved_state(row),
+            track_mbid: None,
+            artist_mbid: None,
+            album_mbid: None,
+            image_url: None,
         })
     }
 
@@ -710,6 +790,136 @@ impl LastFmParser {
         max_page
     }
 
+    // === PARSE HEALTH ===
+
+    /// Check how well the parser for `kind` fits `document`.
+    ///
+    /// Counts the matches of every selector the parser relies on and re-parses each
+    /// listing row. Rows the parser would skip (or, on recent scrobbles, rows without a
+    /// timestamp other than the now-playing row) and an empty listing on a page the
+    /// pagination says has entries are reported as [`ParseAnomaly`]s.
+    pub fn check_page(&self, kind: PageKind, document: &Html, page_number: u32) -> ParseReport {
+        let row_selector_str = match kind {
+            PageKind::Artists => "tr.js-link-block",
+            _ => "tbody tr",
+        };
+        let field_selectors: &[(&str, bool)] = match kind {
+            PageKind::RecentScrobbles => &[
+                (".chartlist-name a", true),
+                (".chartlist-artist a", true),
+                ("input[name='timestamp']", true),
+                ("form[data-edit-scrobble] input[name='album_name']", false),
+            ],
+            PageKind::Artists => &[
+                ("td.chartlist-name a", true),
+                (".chartlist-count-bar", false),
+            ],
+            PageKind::ArtistTracks | PageKind::ArtistAlbums => &[
+                (".chartlist-name a", true),
+                (".chartlist-count-bar-value", false),
+            ],
+            PageKind::TrackSearch
+            | PageKind::AlbumSearch
+            | PageKind::LibraryTracks
+            | PageKind::LibraryAlbums
+            | PageKind::LovedTracks => &[
+                (".chartlist-name a", true),
+                (".chartlist-artist a", true),
+                (".chartlist-count-bar-value", false),
+            ],
+            PageKind::ArtistSearch => &[
+                ("td.chartlist-name a", true),
+                (".chartlist-count-bar-value", false),
+            ],
+        };
+
+        let mut selectors = vec![
+            ("table.chartlist", true),
+            (row_selector_str, true),
+            (".pagination-list, .pagination", false),
+        ];
+        selectors.extend_from_slice(field_selectors);
+        let selectors: Vec<SelectorMatch> = selectors
+            .into_iter()
+            .map(|(selector, required)| SelectorMatch {
+                selector: selector.to_string(),
+                required,
+                matches: document.select(&Selector::parse(selector).unwrap()).count(),
+            })
+            .collect();
+
+        let table_selector = Selector::parse("table.chartlist").unwrap();
+        let row_selector = Selector::parse(row_selector_str).unwrap();
+        let both = |ok: bool| (ok, ok);
+        let mut rows = 0;
+        let mut parsed = 0;
+        let mut failed = 0;
+        for table in document.select(&table_selector) {
+            for row in table.select(&row_selector) {
+                // Loading skeletons carry no data
+                if row
+                    .value()
+                    .classes()
+                    .any(|c| c == "chartlist__placeholder-row")
+                {
+                    continue;
+                }
+                rows += 1;
+                // (parsed at all, parsed with every field the callers rely on)
+                let (ok, complete) = match kind {
+                    PageKind::RecentScrobbles => match self.parse_recent_scrobble_row(&row) {
+                        Ok(track) => {
+                            let now_playing = row
+                                .value()
+                                .classes()
+                                .any(|c| c == "chartlist-row--now-scrobbling");
+                            (true, track.timestamp.is_some() || now_playing)
+                        }
+                        Err(_) => (false, false),
+                    },
+                    PageKind::Artists => both(self.parse_artist_row(&row).is_ok()),
+                    PageKind::ArtistTracks => both(self.parse_track_row(&row).is_ok()),
+                    PageKind::ArtistAlbums => both(self.parse_album_row(&row).is_ok()),
+                    PageKind::TrackSearch | PageKind::LibraryTracks | PageKind::LovedTracks => {
+                        both(self.parse_search_track_row(&row).is_ok())
+                    }
+                    PageKind::AlbumSearch | PageKind::LibraryAlbums => {
+                        both(self.parse_search_album_row(&row).is_ok())
+                    }
+                    PageKind::ArtistSearch => both(self.parse_search_artist_row(&row).is_ok()),
+                };
+                parsed += usize::from(ok);
+                if !complete {
+                    failed += 1;
+                }
+            }
+        }
+
+        let mut anomalies = Vec::new();
+        if failed > 0 {
+            anomalies.push(ParseAnomaly::RowsMissingFields { failed, rows });
+        }
+        let (has_next_page, total_pages) = self
+            .parse_pagination(document, page_number)
+            .unwrap_or((false, None));
+        let paginated = has_next_page || total_pages.is_some_and(|t| t > 1 && page_number <= t);
+        if parsed == 0 && paginated {
+            anomalies.push(ParseAnomaly::EmptyPageWithPagination {
+                has_next_page,
+                total_pages,
+            });
+        }
+
+        ParseReport {
+            page: kind,
+            page_number,
+            selectors,
+            rows,
+            parsed,
+            anomalies,
+        }
+    }
+
     // === JSON PARSING METHODS ===
     // Removed unused JSON parsing method
 
@@ -0,0 +1,462 @@
+//! Time-limited cache of scraped library pages.
+//!
+//! Walking an artist's tracks or albums, or discovering a track's edit variations, fetches
+//! the same library pages again and again. With a [`ResponseCache`] attached (see
+//! [`LastFmEditClientImpl::with_response_cache`](crate::LastFmEditClientImpl::with_response_cache))
+//! the client serves those pages from the cache while they are younger than the
+//! [`CachePolicy`] TTL for their [`PageKind`], and reports hits and misses as
+//! [`ClientEvent::CacheHit`](crate::ClientEvent::CacheHit) and
+//! [`ClientEvent::CacheMiss`](crate::ClientEvent::CacheMiss).
+//!
+//! When an edit or delete succeeds the client drops every cached page that could show the
+//! changed scrobbles: the artist's pages, the matching album and track pages, and the
+//! library-wide listings.
+//!
+//! [`InMemoryResponseCache`] lives as long as the client; [`FileResponseCache`] keeps one
+//! JSON file per page so later runs can reuse it.
+
+use crate::parse_health::PageKind;
+use crate::{LastFmError, SessionManager};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// What a cached page shows: its kind and the artist, album or track it is about.
+///
+/// Library-wide listings (all artists, all tracks, all albums) have no artist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageScope {
+    pub kind: PageKind,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track: Option<String>,
+}
+
+impl PageScope {
+    /// A library-wide listing.
+    pub fn library(kind: PageKind) -> Self {
+        Self {
+            kind,
+            artist: None,
+            album: None,
+            track: None,
+        }
+    }
+
+    /// A listing of one artist's tracks or albums.
+    pub fn artist(kind: PageKind, artist: &str) -> Self {
+        Self {
+            artist: Some(artist.to_string()),
+            ..Self::library(kind)
+        }
+    }
+
+    /// The tracks of one album.
+    pub fn album(kind: PageKind, artist: &str, album: &str) -> Self {
+        Self {
+            album: Some(album.to_string()),
+            ..Self::artist(kind, artist)
+        }
+    }
+
+    /// The scrobbles of one track.
+    pub fn track(kind: PageKind, artist: &str, track: &str) -> Self {
+        Self {
+            track: Some(track.to_string()),
+            ..Self::artist(kind, artist)
+        }
+    }
+
+    /// Whether a page with this scope can show scrobbles touched by `change`.
+    pub fn is_affected_by(&self, change: &LibraryChange) -> bool {
+        let Some(artist) = &self.artist else {
+            return true;
+        };
+        let differs = |page: &Option<String>, changed: &Option<String>| match (page, changed) {
+            (Some(page), Some(changed)) => page.to_lowercase() != changed.to_lowercase(),
+            _ => false,
+        };
+        artist.to_lowercase() == change.artist.to_lowercase()
+            && !differs(&self.album, &change.album)
+            && !differs(&self.track, &change.track)
+    }
+}
+
+/// Scrobbles changed by an edit or delete. Names compare case-insensitively, as on Last.fm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryChange {
+    pub artist: String,
+    /// The album, when only scrobbles on that album changed
+    pub album: Option<String>,
+    /// The track, when only scrobbles of that track changed
+    pub track: Option<String>,
+}
+
+impl LibraryChange {
+    pub fn new(artist: &str, album: Option<&str>, track: Option<&str>) -> Self {
+        Self {
+            artist: artist.to_string(),
+            album: album.map(str::to_string),
+            track: track.map(str::to_string),
+        }
+    }
+}
+
+/// A page body stored in a [`ResponseCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedPage {
+    pub url: String,
+    pub scope: PageScope,
+    pub body: String,
+    /// When the page was fetched (milliseconds since the Unix epoch)
+    pub stored_at_ms: u64,
+}
+
+impl CachedPage {
+    /// How long ago the page was fetched, as of `now_ms`.
+    pub fn age(&self, now_ms: u64) -> Duration {
+        Duration::from_millis(now_ms.saturating_sub(self.stored_at_ms))
+    }
+}
+
+/// How long cached pages stay fresh, per [`PageKind`].
+///
+/// A TTL of zero disables caching for that kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub default_ttl: Duration,
+    pub ttls: HashMap<PageKind, Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10 * 60))
+    }
+}
+
+impl CachePolicy {
+    /// Cache every page kind for `default_ttl`.
+    pub fn new(default_ttl: Duration) -> Self {
+        Self {
+            default_ttl,
+            ttls: HashMap::new(),
+        }
+    }
+
+    /// Use `ttl` for pages of `kind` instead of the default.
+    pub fn with_ttl(mut self, kind: PageKind, ttl: Duration) -> Self {
+        self.ttls.insert(kind, ttl);
+        self
+    }
+
+    pub fn ttl_for(&self, kind: PageKind) -> Duration {
+        self.ttls.get(&kind).copied().unwrap_or(self.default_ttl)
+    }
+}
+
+/// Storage backend for cached library pages, keyed by URL.
+///
+/// Implementations must be safe to share between client clones. Freshness is decided by
+/// the client from [`CachedPage::stored_at_ms`]; backends only store and drop pages.
+pub trait ResponseCache: Send + Sync {
+    /// Look up the page cached for `url`, however old.
+    fn get(&self, url: &str) -> crate::Result<Option<CachedPage>>;
+
+    /// Store `page`, replacing any page cached for the same URL.
+    fn put(&self, page: CachedPage) -> crate::Result<()>;
+
+    /// Drop every page whose scope is affected by `change`; returns how many were dropped.
+    fn invalidate(&self, change: &LibraryChange) -> crate::Result<usize>;
+
+    /// Drop every cached page.
+    fn clear(&self) -> crate::Result<()>;
+}
+
+/// Cache kept in memory; pages are lost when it is dropped.
+#[derive(Debug, Default)]
+pub struct InMemoryResponseCache {
+    pages: Mutex<HashMap<String, CachedPage>>,
+}
+
+impl InMemoryResponseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached pages, fresh or not.
+    pub fn len(&self) -> usize {
+        self.pages.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ResponseCache for InMemoryResponseCache {
+    fn get(&self, url: &str) -> crate::Result<Option<CachedPage>> {
+        Ok(self.pages.lock().unwrap().get(url).cloned())
+    }
+
+    fn put(&self, page: CachedPage) -> crate::Result<()> {
+        self.pages.lock().unwrap().insert(page.url.clone(), page);
+        Ok(())
+    }
+
+    fn invalidate(&self, change: &LibraryChange) -> crate::Result<usize> {
+        let mut pages = self.pages.lock().unwrap();
+        let before = pages.len();
+        pages.retain(|_, page| !page.scope.is_affected_by(change));
+        Ok(before - pages.len())
+    }
+
+    fn clear(&self) -> crate::Result<()> {
+        self.pages.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+/// Cache stored as one JSON [`CachedPage`] file per URL in a directory.
+#[derive(Debug)]
+pub struct FileResponseCache {
+    dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileResponseCache {
+    /// Use (or create on first write) the cache directory at `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// The default per-user cache next to the saved session:
+    /// `~/.local/share/{app_name}/users/{username}/page_cache`
+    pub fn for_user(manager: &SessionManager, username: &str) -> crate::Result<Self> {
+        Ok(Self::new(
+            manager.get_user_dir(username)?.join("page_cache"),
+        ))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn page_path(&self, url: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:x}.json", md5::compute(url.as_bytes())))
+    }
+
+    fn read_page(path: &Path) -> crate::Result<CachedPage> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| {
+            LastFmError::Parse(format!("Invalid cached page '{}': {e}", path.display()))
+        })
+    }
+
+    fn page_files(&self) -> crate::Result<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl ResponseCache for FileResponseCache {
+    fn get(&self, url: &str) -> crate::Result<Option<CachedPage>> {
+        let _guard = self.lock.lock().unwrap();
+        let path = self.page_path(url);
+        if !path.exists() {
+            return Ok(None);
+        }
+        // A hash collision would return another URL's page; treat it as a miss.
+        Ok(Some(Self::read_page(&path)?).filter(|page| page.url == url))
+    }
+
+    fn put(&self, page: CachedPage) -> crate::Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        // Library pages show the user's listening history, so keep them private like the
+        // saved session next to them.
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(&self.dir)?;
+
+        let json = serde_json::to_string(&page).map_err(|e| LastFmError::Parse(e.to_string()))?;
+        let path = self.page_path(&page.url);
+        let tmp_path = path.with_extension("json.tmp");
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(json.as_bytes())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        drop(file);
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn invalidate(&self, change: &LibraryChange) -> crate::Result<usize> {
+        let _guard = self.lock.lock().unwrap();
+        let mut dropped = 0;
+        for path in self.page_files()? {
+            // Unreadable files cannot be checked for freshness either, so drop them too.
+            let affected = Self::read_page(&path)
+                .map(|page| page.scope.is_affected_by(change))
+                .unwrap_or(true);
+            if affected {
+                fs::remove_file(&path)?;
+                dropped += 1;
+            }
+        }
+        Ok(dropped)
+    }
+
+    fn clear(&self) -> crate::Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        for path in self.page_files()? {
+            fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(url: &str, scope: PageScope) -> CachedPage {
+        CachedPage {
+            url: url.to_string(),
+            scope,
+            body: format!("<html>{url}</html>"),
+            stored_at_ms: 1_700_000_000_000,
+        }
+    }
+
+    fn sample_pages() -> Vec<CachedPage> {
+        vec![
+            page("artists", PageScope::library(PageKind::Artists)),
+            page(
+                "radiohead-tracks",
+                PageScope::artist(PageKind::ArtistTracks, "Radiohead"),
+            ),
+            page(
+                "pablo-honey",
+                PageScope::album(PageKind::ArtistTracks, "Radiohead", "Pablo Honey"),
+            ),
+            page(
+                "ok-computer",
+                PageScope::album(PageKind::ArtistTracks, "Radiohead", "OK Computer"),
+            ),
+            page(
+                "creep",
+                PageScope::track(PageKind::RecentScrobbles, "Radiohead", "Creep"),
+            ),
+            page(
+                "portishead-tracks",
+                PageScope::artist(PageKind::ArtistTracks, "Portishead"),
+            ),
+        ]
+    }
+
+    fn remaining(cache: &dyn ResponseCache) -> Vec<&'static str> {
+        [
+            "artists",
+            "radiohead-tracks",
+            "pablo-honey",
+            "ok-computer",
+            "creep",
+            "portishead-tracks",
+        ]
+        .into_iter()
+        .filter(|url| cache.get(url).unwrap().is_some())
+        .collect()
+    }
+
+    fn check_invalidation(cache: &dyn ResponseCache) {
+        for page in sample_pages() {
+            cache.put(page).unwrap();
+        }
+
+        let change = LibraryChange::new("radiohead", Some("Pablo Honey"), Some("Creep"));
+        assert_eq!(cache.invalidate(&change).unwrap(), 4);
+        assert_eq!(remaining(cache), vec!["ok-computer", "portishead-tracks"]);
+
+        cache.clear().unwrap();
+        assert!(remaining(cache).is_empty());
+    }
+
+    #[test]
+    fn in_memory_cache_drops_only_affected_pages() {
+        check_invalidation(&InMemoryResponseCache::new());
+    }
+
+    #[test]
+    fn file_cache_drops_only_affected_pages() {
+        let dir =
+            std::env::temp_dir().join(format!("lastfm-edit-response-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = FileResponseCache::new(&dir);
+
+        cache.put(sample_pages().remove(1)).unwrap();
+        let reopened = FileResponseCache::new(&dir);
+        assert_eq!(
+            reopened.get("radiohead-tracks").unwrap(),
+            Some(sample_pages().remove(1))
+        );
+
+        check_invalidation(&cache);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_cache_is_private_to_the_user() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "lastfm-edit-response-cache-private-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = FileResponseCache::new(dir.join("page_cache"));
+        let page = sample_pages().remove(0);
+        cache.put(page.clone()).unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(cache.dir()), 0o700);
+        assert_eq!(mode(&cache.page_path(&page.url)), 0o600);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn policy_falls_back_to_the_default_ttl() {
+        let policy = CachePolicy::new(Duration::from_secs(60))
+            .with_ttl(PageKind::Artists, Duration::from_secs(5));
+        assert_eq!(policy.ttl_for(PageKind::Artists), Duration::from_secs(5));
+        assert_eq!(
+            policy.ttl_for(PageKind::ArtistAlbums),
+            Duration::from_secs(60)
+        );
+    }
+}
@@ -1,10 +1,13 @@
-use crate::types::{LastFmError, RetryConfig, RetryResult};
+use crate::types::{EditFailureKind, LastFmError, RetryConfig, RetryResult};
 use crate::Result;
 use std::future::Future;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use crate::cancel;
 
+/// Retry cap for transient non-rate-limit failures when rate-limit retries are unbounded.
+const MAX_UNBOUNDED_FAILURE_RETRIES: u32 = 3;
+
 /// Execute an async operation with retry logic for rate limiting
 ///
 /// This function handles the common pattern of retrying operations that may fail
@@ -45,15 +48,79 @@ where
 
 /// Like [`retry_with_backoff`], but allows callers to cooperatively cancel during backoff sleeps.
 ///
+/// Besides rate limits, errors classified as retryable by [`LastFmError::is_retryable`]
+/// (an expired CSRF token) are retried with plain exponential backoff. They share the
+/// `max_retries` budget but do not trigger the rate-limit callbacks. Server errors are
+/// not retried: a POST may have been applied before the server failed, so repeating it
+/// blindly could act on stale data. Use [`retry_idempotent_with_backoff_cancelable`] for
+/// requests that are safe to repeat.
+///
 /// Cancellation returns `LastFmError::Io(ErrorKind::Interrupted)` so downstream crates do not need
 /// to handle a new `LastFmError` variant.
 pub async fn retry_with_backoff_cancelable<T, F, Fut, OnRateLimit, OnRateLimitEnd>(
+    config: RetryConfig,
+    operation_name: &str,
+    operation: F,
+    on_rate_limit: OnRateLimit,
+    on_rate_limit_end: OnRateLimitEnd,
+    cancel_rx: Option<tokio::sync::watch::Receiver<bool>>,
+) -> Result<RetryResult<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    OnRateLimit: FnMut(u64, u64, &str),
+    OnRateLimitEnd: FnMut(u64, &str),
+{
+    retry_loop(
+        config,
+        operation_name,
+        operation,
+        on_rate_limit,
+        on_rate_limit_end,
+        cancel_rx,
+        false,
+    )
+    .await
+}
+
+/// Like [`retry_with_backoff_cancelable`], but also retries server errors
+/// ([`EditFailureKind::ServerError`]).
+///
+/// Only use this for requests that can be repeated without side effects, such as GETs.
+pub async fn retry_idempotent_with_backoff_cancelable<T, F, Fut, OnRateLimit, OnRateLimitEnd>(
+    config: RetryConfig,
+    operation_name: &str,
+    operation: F,
+    on_rate_limit: OnRateLimit,
+    on_rate_limit_end: OnRateLimitEnd,
+    cancel_rx: Option<tokio::sync::watch::Receiver<bool>>,
+) -> Result<RetryResult<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    OnRateLimit: FnMut(u64, u64, &str),
+    OnRateLimitEnd: FnMut(u64, &str),
+{
+    retry_loop(
+        config,
+        operation_name,
+        operation,
+        on_rate_limit,
+        on_rate_limit_end,
+        cancel_rx,
+        true,
+    )
+    .await
+}
+
+async fn retry_loop<T, F, Fut, OnRateLimit, OnRateLimitEnd>(
     config: RetryConfig,
     operation_name: &str,
     mut operation: F,
     mut on_rate_limit: OnRateLimit,
     mut on_rate_limit_end: OnRateLimitEnd,
     cancel_rx: Option<tokio::sync::watch::Receiver<bool>>,
+    retry_server_errors: bool,
 ) -> Result<RetryResult<T>>
 where
     F: FnMut() -> Fut,
@@ -62,6 +129,7 @@ where
     OnRateLimitEnd: FnMut(u64, &str),
 {
     let mut retries = 0;
+    let mut failure_retries = 0;
     let mut total_retry_time = 0;
     let mut rate_limit_start_time: Option<Instant> = None;
     let unbounded = config.enabled && config.max_retries == u32::MAX;
@@ -138,6 +206,39 @@ where
                 retries = retries.saturating_add(1);
                 total_retry_time = total_retry_time.saturating_add(delay);
             }
+            Err(error)
+                if error.is_retryable()
+                    || (retry_server_errors
+                        && error.edit_failure_kind() == Some(EditFailureKind::ServerError)) =>
+            {
+                let max_failure_retries = if unbounded {
+                    MAX_UNBOUNDED_FAILURE_RETRIES
+                } else {
+                    config.max_retries
+                };
+                if !config.enabled
+                    || failure_retries >= max_failure_retries
+                    || (!unbounded && retries >= config.max_retries)
+                {
+                    return Err(error);
+                }
+
+                let pow = 2u64.checked_pow(failure_retries).unwrap_or(u64::MAX);
+                let delay = std::cmp::min(config.base_delay.saturating_mul(pow), config.max_delay);
+                log::info!(
+                    "{operation_name} failed with a retryable error ({error}). Waiting {delay} seconds before retry {}",
+                    failure_retries + 1
+                );
+
+                if let Some(rx) = cancel_rx.clone() {
+                    cancel::sleep_with_cancel(rx, std::time::Duration::from_secs(delay)).await?;
+                } else {
+                    tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+                }
+                failure_retries += 1;
+                retries = retries.saturating_add(1);
+                total_retry_time = total_retry_time.saturating_add(delay);
+            }
             Err(other_error) => {
                 return Err(other_error);
             }
@@ -247,6 +348,101 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_retryable_edit_failures_are_retried() {
+        let config = RetryConfig {
+            max_retries: 3,
+            base_delay: 0,
+            max_delay: 0,
+            enabled: true,
+        };
+
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = call_count.clone();
+        let result = retry_operation(config.clone(), "test", move || {
+            let count = call_count_clone.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if count == 0 {
+                    Err(LastFmError::EditRejected {
+                        kind: EditFailureKind::CsrfExpired,
+                        message: "CSRF verification failed.".to_string(),
+                    })
+                } else {
+                    Ok::<i32, LastFmError>(42)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap().attempts_made, 1);
+
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = call_count.clone();
+        let result = retry_operation(config, "test", move || {
+            call_count_clone.fetch_add(1, Ordering::SeqCst);
+            async {
+                Err::<i32, LastFmError>(LastFmError::EditRejected {
+                    kind: EditFailureKind::ScrobbleNotFound,
+                    message: "not found".to_string(),
+                })
+            }
+        })
+        .await;
+        assert!(matches!(
+            result,
+            Err(LastFmError::EditRejected {
+                kind: EditFailureKind::ScrobbleNotFound,
+                ..
+            })
+        ));
+        assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_only_retried_for_idempotent_requests() {
+        let config = RetryConfig {
+            max_retries: 3,
+            base_delay: 0,
+            max_delay: 0,
+            enabled: true,
+        };
+        let server_error = || LastFmError::EditRejected {
+            kind: EditFailureKind::ServerError,
+            message: "HTTP 502".to_string(),
+        };
+
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = call_count.clone();
+        let result = retry_operation(config.clone(), "test", move || {
+            call_count_clone.fetch_add(1, Ordering::SeqCst);
+            async move { Err::<i32, LastFmError>(server_error()) }
+        })
+        .await;
+        assert!(result.unwrap_err().edit_failure_kind() == Some(EditFailureKind::ServerError));
+        assert_eq!(call_count.load(Ordering::SeqCst), 1);
+
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = call_count.clone();
+        let result = retry_idempotent_with_backoff_cancelable(
+            config,
+            "test",
+            move || {
+                let count = call_count_clone.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if count < 2 {
+                        Err(server_error())
+                    } else {
+                        Ok::<i32, LastFmError>(42)
+                    }
+                }
+            },
+            |_, _, _| {},
+            |_, _| {},
+            None,
+        )
+        .await;
+        assert_eq!(result.unwrap().attempts_made, 2);
+    }
+
     #[tokio::test]
     async fn test_retries_disabled() {
         let config = RetryConfig::disabled();
@@ -0,0 +1,258 @@
+//! Passphrase-encrypted session files.
+//!
+//! The session cookies saved by [`SessionManager`](crate::SessionManager) grant full
+//! edit and delete access to the account. With a passphrase configured they are stored
+//! sealed with XChaCha20-Poly1305 under a key derived from the passphrase with Argon2id.
+//! The salt, nonce and KDF parameters are kept next to the ciphertext, so a file can be
+//! opened with nothing but the passphrase.
+
+use crate::session_persistence::ENCRYPTED_SESSION_FORMAT;
+use crate::types::{LastFmEditSession, LastFmError};
+use crate::Result;
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+const FORMAT_VERSION: u32 = 1;
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER: &str = "xchacha20poly1305";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// A passphrase used to encrypt saved sessions. Its `Debug` output is redacted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionPassphrase(String);
+
+impl SessionPassphrase {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self(passphrase.into())
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl std::fmt::Debug for SessionPassphrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionPassphrase(<redacted>)")
+    }
+}
+
+impl From<String> for SessionPassphrase {
+    fn from(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+}
+
+impl From<&str> for SessionPassphrase {
+    fn from(passphrase: &str) -> Self {
+        Self(passphrase.to_string())
+    }
+}
+
+/// Argon2id cost parameters, stored in the file so they can be raised later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub algorithm: String,
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    /// Base64-encoded salt
+    pub salt: String,
+}
+
+/// On-disk form of an encrypted session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedSessionFile {
+    pub format: String,
+    pub version: u32,
+    pub kdf: KdfParams,
+    pub cipher: String,
+    /// Base64-encoded nonce
+    pub nonce: String,
+    /// Base64-encoded session JSON, sealed together with the username
+    pub ciphertext: String,
+}
+
+impl EncryptedSessionFile {
+    /// Encrypt `session` with a key derived from `passphrase`.
+    pub fn seal(session: &LastFmEditSession, passphrase: &SessionPassphrase) -> Result<Self> {
+        let params = Params::default();
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let kdf = KdfParams {
+            algorithm: KDF_ALGORITHM.to_string(),
+            memory_kib: params.m_cost(),
+            iterations: params.t_cost(),
+            parallelism: params.p_cost(),
+            salt: BASE64.encode(salt),
+        };
+
+        let cipher = XChaCha20Poly1305::new(&derive_key(&kdf, passphrase)?.into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let plaintext = session
+            .to_json()
+            .map_err(|e| LastFmError::Http(format!("Failed to serialize session: {e}")))?;
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: session.username.as_bytes(),
+                },
+            )
+            .map_err(|_| LastFmError::Http("Failed to encrypt session".to_string()))?;
+
+        Ok(Self {
+            format: ENCRYPTED_SESSION_FORMAT.to_string(),
+            version: FORMAT_VERSION,
+            kdf,
+            cipher: CIPHER.to_string(),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the session saved for `username`.
+    ///
+    /// A wrong passphrase, a file saved for another user and a tampered file all fail
+    /// with [`LastFmError::Auth`].
+    pub fn open(
+        &self,
+        username: &str,
+        passphrase: &SessionPassphrase,
+    ) -> Result<LastFmEditSession> {
+        if self.version != FORMAT_VERSION || self.cipher != CIPHER {
+            return Err(LastFmError::Parse(format!(
+                "Unsupported encrypted session format: version {} with {}",
+                self.version, self.cipher
+            )));
+        }
+
+        let nonce = decode_field("nonce", &self.nonce)?;
+        if nonce.len() != 24 {
+            return Err(LastFmError::Parse(
+                "Encrypted session nonce has the wrong length".to_string(),
+            ));
+        }
+        let ciphertext = decode_field("ciphertext", &self.ciphertext)?;
+
+        let cipher = XChaCha20Poly1305::new(&derive_key(&self.kdf, passphrase)?.into());
+        let plaintext = cipher
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: username.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                LastFmError::Auth(format!(
+                    "Cannot decrypt the saved session for {username}: wrong passphrase or corrupted file"
+                ))
+            })?;
+
+        let json = String::from_utf8(plaintext)
+            .map_err(|e| LastFmError::Parse(format!("Decrypted session is not UTF-8: {e}")))?;
+        LastFmEditSession::from_json(&json)
+            .map_err(|e| LastFmError::Http(format!("Failed to parse session JSON: {e}")))
+    }
+
+    /// Parse `contents` as an encrypted session file, or `None` when it is something else
+    /// (such as a plaintext session).
+    pub fn from_file_contents(contents: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(contents)
+            .ok()
+            .filter(|file| file.format == ENCRYPTED_SESSION_FORMAT)
+    }
+}
+
+fn derive_key(kdf: &KdfParams, passphrase: &SessionPassphrase) -> Result<[u8; KEY_LEN]> {
+    if kdf.algorithm != KDF_ALGORITHM {
+        return Err(LastFmError::Parse(format!(
+            "Unsupported session key derivation: {}",
+            kdf.algorithm
+        )));
+    }
+    let params = Params::new(
+        kdf.memory_kib,
+        kdf.iterations,
+        kdf.parallelism,
+        Some(KEY_LEN),
+    )
+    .map_err(|e| LastFmError::Parse(format!("Invalid session KDF parameters: {e}")))?;
+    let salt = decode_field("salt", &kdf.salt)?;
+
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| LastFmError::Parse(format!("Failed to derive session key: {e}")))?;
+    Ok(key)
+}
+
+fn decode_field(name: &str, value: &str) -> Result<Vec<u8>> {
+    BASE64
+        .decode(value)
+        .map_err(|e| LastFmError::Parse(format!("Invalid encrypted session {name}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> LastFmEditSession {
+        LastFmEditSession::new(
+            "test_user".to_string(),
+            vec![
+                "sessionid=.abc123".to_string(),
+                "csrftoken=token".to_string(),
+            ],
+            Some("token".to_string()),
+            "https://www.last.fm".to_string(),
+        )
+    }
+
+    #[test]
+    fn sealed_session_round_trips_and_hides_cookies() {
+        let passphrase = SessionPassphrase::new("correct horse");
+        let sealed = EncryptedSessionFile::seal(&session(), &passphrase).unwrap();
+        let contents = serde_json::to_string(&sealed).unwrap();
+        assert!(!contents.contains("abc123"));
+
+        let parsed = EncryptedSessionFile::from_file_contents(&contents).unwrap();
+        assert_eq!(parsed.open("test_user", &passphrase).unwrap(), session());
+    }
+
+    #[test]
+    fn wrong_passphrase_or_user_is_rejected() {
+        let sealed =
+            EncryptedSessionFile::seal(&session(), &SessionPassphrase::new("correct horse"))
+                .unwrap();
+
+        let err = sealed
+            .open("test_user", &SessionPassphrase::new("battery staple"))
+            .unwrap_err();
+        assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+
+        let err = sealed
+            .open("other_user", &SessionPassphrase::new("correct horse"))
+            .unwrap_err();
+        assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+    }
+
+    #[test]
+    fn plaintext_sessions_are_not_mistaken_for_encrypted_ones() {
+        let json = session().to_json().unwrap();
+        assert!(EncryptedSessionFile::from_file_contents(&json).is_none());
+        assert_eq!(
+            format!("{:?}", SessionPassphrase::new("secret")),
+            "SessionPassphrase(<redacted>)"
+        );
+    }
+}
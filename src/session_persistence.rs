@@ -1,3 +1,5 @@
+#[cfg(feature = "session-encryption")]
+use crate::session_encryption::{EncryptedSessionFile, SessionPassphrase};
 use crate::types::{LastFmEditSession, LastFmError};
 use crate::Result;
 use std::fs::{self, OpenOptions};
@@ -7,15 +9,24 @@ use std::path::PathBuf;
 #[cfg(unix)]
 use std::os::unix::fs::OpenOptionsExt;
 
+/// Value of the `format` field that marks an encrypted session file.
+pub const ENCRYPTED_SESSION_FORMAT: &str = "lastfm-edit-encrypted-session";
+
 /// Configurable session manager for storing session data in XDG directories.
 ///
 /// This struct allows customization of the application prefix for session storage.
 /// Sessions are stored per-user in the format:
 /// `~/.local/share/{app_name}/users/{username}/session.json`
+///
+/// By default the session is saved as plaintext JSON. With a passphrase configured
+/// (see [`SessionManager::with_passphrase`]) it is saved encrypted instead; loading
+/// detects the format, and plaintext sessions are re-saved encrypted when loaded.
 #[derive(Clone, Debug)]
 pub struct SessionManager {
     app_name: String,
     data_dir: Option<PathBuf>,
+    #[cfg(feature = "session-encryption")]
+    passphrase: Option<SessionPassphrase>,
 }
 
 impl SessionManager {
@@ -27,6 +38,8 @@ impl SessionManager {
         Self {
             app_name: app_name.into(),
             data_dir: None,
+            #[cfg(feature = "session-encryption")]
+            passphrase: None,
         }
     }
 
@@ -38,6 +51,30 @@ impl SessionManager {
         Self {
             app_name: app_name.into(),
             data_dir: Some(data_dir.into()),
+            #[cfg(feature = "session-encryption")]
+            passphrase: None,
+        }
+    }
+
+    /// Encrypt saved sessions with a key derived from `passphrase`.
+    ///
+    /// Sessions saved through this manager are sealed with authenticated encryption,
+    /// and plaintext sessions found on load are migrated to the encrypted format.
+    #[cfg(feature = "session-encryption")]
+    pub fn with_passphrase(mut self, passphrase: impl Into<SessionPassphrase>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    /// Whether this manager saves sessions encrypted.
+    pub fn encrypts_sessions(&self) -> bool {
+        #[cfg(feature = "session-encryption")]
+        {
+            self.passphrase.is_some()
+        }
+        #[cfg(not(feature = "session-encryption"))]
+        {
+            false
         }
     }
 
@@ -61,17 +98,26 @@ impl SessionManager {
     /// Returns the path where the session should be stored, or an error if
     /// the XDG data directory cannot be determined.
     pub fn get_session_path(&self, username: &str) -> Result<PathBuf> {
-        let data_dir = self.data_dir()?;
+        Ok(self.get_user_dir(username)?.join("session.json"))
+    }
 
-        let session_dir = data_dir.join(&self.app_name).join("users").join(username);
+    /// Get the per-user directory holding the session and other per-account state.
+    ///
+    /// Returns a path like: `~/.local/share/{app_name}/users/{username}`
+    pub fn get_user_dir(&self, username: &str) -> Result<PathBuf> {
+        let data_dir = self.data_dir()?;
 
-        Ok(session_dir.join("session.json"))
+        Ok(data_dir.join(&self.app_name).join("users").join(username))
     }
 
     /// Save a session to the XDG data directory.
     ///
     /// This creates the necessary directory structure and saves the session
-    /// as JSON to `~/.local/share/{app_name}/users/{username}/session.json`
+    /// as JSON to `~/.local/share/{app_name}/users/{username}/session.json`,
+    /// encrypted when the manager has a passphrase.
+    ///
+    /// An encrypted session is never replaced by a plaintext one: without a passphrase,
+    /// saving over an encrypted session file fails with [`LastFmError::Auth`].
     ///
     /// # Arguments
     /// * `session` - The session to save
@@ -81,6 +127,16 @@ impl SessionManager {
     pub fn save_session(&self, session: &LastFmEditSession) -> Result<()> {
         let session_path = self.get_session_path(&session.username)?;
 
+        if !self.encrypts_sessions()
+            && fs::read_to_string(&session_path)
+                .is_ok_and(|contents| is_encrypted_session(&contents))
+        {
+            return Err(LastFmError::Auth(format!(
+                "The saved session for {} is encrypted; a passphrase is required to replace it",
+                session.username
+            )));
+        }
+
         // Create parent directories if they don't exist
         if let Some(parent) = session_path.parent() {
             fs::create_dir_all(parent).map_err(|e| {
@@ -88,10 +144,7 @@ impl SessionManager {
             })?;
         }
 
-        // Serialize session to JSON
-        let session_json = session
-            .to_json()
-            .map_err(|e| LastFmError::Http(format!("Failed to serialize session: {e}")))?;
+        let contents = self.serialize_session(session)?;
 
         // Session cookies grant account access, so never create a world-readable file.
         let mut options = OpenOptions::new();
@@ -101,7 +154,7 @@ impl SessionManager {
         let mut file = options
             .open(&session_path)
             .map_err(|e| LastFmError::Http(format!("Failed to open session file: {e}")))?;
-        file.write_all(session_json.as_bytes())
+        file.write_all(contents.as_bytes())
             .map_err(|e| LastFmError::Http(format!("Failed to write session file: {e}")))?;
 
         #[cfg(unix)]
@@ -135,16 +188,104 @@ impl SessionManager {
         }
 
         // Read and parse session file
-        let session_json = fs::read_to_string(&session_path)
+        let contents = fs::read_to_string(&session_path)
             .map_err(|e| LastFmError::Http(format!("Failed to read session file: {e}")))?;
 
-        let session = LastFmEditSession::from_json(&session_json)
-            .map_err(|e| LastFmError::Http(format!("Failed to parse session JSON: {e}")))?;
+        if is_encrypted_session(&contents) {
+            let session = self.decrypt_session(username, &contents)?;
+            log::debug!("Encrypted session loaded from: {}", session_path.display());
+            return Ok(session);
+        }
 
+        let session = LastFmEditSession::from_json(&contents)
+            .map_err(|e| LastFmError::Http(format!("Failed to parse session JSON: {e}")))?;
         log::debug!("Session loaded from: {}", session_path.display());
+
+        if self.encrypts_sessions() {
+            match self.save_session(&session) {
+                Ok(()) => log::info!("Encrypted the plaintext session saved for {username}"),
+                Err(e) => log::warn!("Failed to encrypt the saved session for {username}: {e}"),
+            }
+        }
         Ok(session)
     }
 
+    /// Check whether the session saved for `username` is encrypted.
+    pub fn session_is_encrypted(&self, username: &str) -> Result<bool> {
+        let session_path = self.get_session_path(username)?;
+        let contents = fs::read_to_string(&session_path)
+            .map_err(|e| LastFmError::Http(format!("Failed to read session file: {e}")))?;
+        Ok(is_encrypted_session(&contents))
+    }
+
+    /// Encrypt every plaintext session saved under this manager's directory.
+    ///
+    /// Returns the usernames whose sessions were migrated; sessions that are already
+    /// encrypted are left untouched.
+    #[cfg(feature = "session-encryption")]
+    pub fn migrate_sessions(&self) -> Result<Vec<String>> {
+        if !self.encrypts_sessions() {
+            return Err(LastFmError::Auth(
+                "A passphrase is required to encrypt saved sessions".to_string(),
+            ));
+        }
+
+        let mut migrated = Vec::new();
+        for username in self.list_saved_users()? {
+            if !self.session_is_encrypted(&username)? {
+                // Loading a plaintext session with a passphrase re-saves it encrypted.
+                self.load_session(&username)?;
+                if self.session_is_encrypted(&username)? {
+                    migrated.push(username);
+                }
+            }
+        }
+        Ok(migrated)
+    }
+
+    #[cfg(feature = "session-encryption")]
+    fn serialize_session(&self, session: &LastFmEditSession) -> Result<String> {
+        match &self.passphrase {
+            Some(passphrase) => {
+                let sealed = EncryptedSessionFile::seal(session, passphrase)?;
+                serde_json::to_string_pretty(&sealed).map_err(|e| {
+                    LastFmError::Http(format!("Failed to serialize encrypted session: {e}"))
+                })
+            }
+            None => session
+                .to_json()
+                .map_err(|e| LastFmError::Http(format!("Failed to serialize session: {e}"))),
+        }
+    }
+
+    #[cfg(not(feature = "session-encryption"))]
+    fn serialize_session(&self, session: &LastFmEditSession) -> Result<String> {
+        session
+            .to_json()
+            .map_err(|e| LastFmError::Http(format!("Failed to serialize session: {e}")))
+    }
+
+    #[cfg(feature = "session-encryption")]
+    fn decrypt_session(&self, username: &str, contents: &str) -> Result<LastFmEditSession> {
+        let Some(passphrase) = &self.passphrase else {
+            return Err(LastFmError::Auth(format!(
+                "The saved session for {username} is encrypted; a passphrase is required"
+            )));
+        };
+        let sealed = EncryptedSessionFile::from_file_contents(contents).ok_or_else(|| {
+            LastFmError::Parse(format!("Malformed encrypted session file for {username}"))
+        })?;
+        sealed.open(username, passphrase)
+    }
+
+    #[cfg(not(feature = "session-encryption"))]
+    fn decrypt_session(&self, username: &str, _contents: &str) -> Result<LastFmEditSession> {
+        Err(LastFmError::Auth(format!(
+            "The saved session for {username} is encrypted, but lastfm-edit was built without \
+             the session-encryption feature"
+        )))
+    }
+
     /// Check if a saved session exists for the given username.
     ///
     /// # Arguments
@@ -222,6 +363,19 @@ impl SessionManager {
     }
 }
 
+/// Whether session file `contents` are in the encrypted format.
+fn is_encrypted_session(contents: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(contents)
+        .ok()
+        .and_then(|value| {
+            value
+                .get("format")
+                .and_then(|format| format.as_str())
+                .map(|format| format == ENCRYPTED_SESSION_FORMAT)
+        })
+        .unwrap_or(false)
+}
+
 /// Session persistence utilities for managing session data in XDG directories.
 ///
 /// This module provides functionality to save and load Last.fm session data
@@ -343,4 +497,80 @@ mod tests {
             private_files.join("lastfm-edit/users/mobile-user/session.json")
         );
     }
+
+    #[cfg(feature = "session-encryption")]
+    #[test]
+    fn plaintext_sessions_are_migrated_to_the_encrypted_format() {
+        let data_dir = std::env::temp_dir().join(format!(
+            "lastfm-edit-session-encryption-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&data_dir);
+        let session = LastFmEditSession::new(
+            "crypt_user".to_string(),
+            vec!["sessionid=.secret".to_string()],
+            Some("token".to_string()),
+            "https://www.last.fm".to_string(),
+        );
+
+        let plain = SessionManager::with_data_dir("lastfm-edit-test", &data_dir);
+        plain.save_session(&session).unwrap();
+        assert!(!plain.session_is_encrypted("crypt_user").unwrap());
+
+        let encrypted = plain.clone().with_passphrase("correct horse");
+        assert_eq!(encrypted.migrate_sessions().unwrap(), vec!["crypt_user"]);
+        assert!(encrypted.session_is_encrypted("crypt_user").unwrap());
+        let contents = fs::read_to_string(plain.get_session_path("crypt_user").unwrap()).unwrap();
+        assert!(!contents.contains("secret"));
+        assert_eq!(encrypted.load_session("crypt_user").unwrap(), session);
+
+        let err = plain.load_session("crypt_user").unwrap_err();
+        assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+        let err = plain
+            .clone()
+            .with_passphrase("battery staple")
+            .load_session("crypt_user")
+            .unwrap_err();
+        assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[cfg(feature = "session-encryption")]
+    #[test]
+    fn encrypted_sessions_are_not_overwritten_without_a_passphrase() {
+        let data_dir = std::env::temp_dir().join(format!(
+            "lastfm-edit-session-overwrite-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&data_dir);
+        let session = |cookie: &str| {
+            LastFmEditSession::new(
+                "crypt_user".to_string(),
+                vec![format!("sessionid={cookie}")],
+                Some("token".to_string()),
+                "https://www.last.fm".to_string(),
+            )
+        };
+
+        let plain = SessionManager::with_data_dir("lastfm-edit-test", &data_dir);
+        let encrypted = plain.clone().with_passphrase("correct horse");
+        encrypted.save_session(&session(".old")).unwrap();
+
+        let err = plain.save_session(&session(".new")).unwrap_err();
+        assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+        assert!(plain.session_is_encrypted("crypt_user").unwrap());
+        assert_eq!(
+            encrypted.load_session("crypt_user").unwrap(),
+            session(".old")
+        );
+
+        encrypted.save_session(&session(".new")).unwrap();
+        assert_eq!(
+            encrypted.load_session("crypt_user").unwrap(),
+            session(".new")
+        );
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
 }
@@ -0,0 +1,194 @@
+//! Rate-limit budget shared by every process using the same account.
+//!
+//! Last.fm's limits apply to the account, not to a process, so tools running side by side
+//! (the `lastfm-edit` CLI, `scrobble-store sync --via scrape`, `scrobble-scrubber execute`)
+//! trip them together when each paces itself alone. A [`SharedRateLimiter`] keeps the
+//! budget in a small JSON file next to the saved session, guarded by an exclusive file
+//! lock: each request claims the next send slot, and a detected rate limit starts a
+//! cool-down that every process — including ones started later — waits out.
+
+use crate::types::{LastFmError, RateLimitType};
+use crate::SessionManager;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The most recent rate limit any process reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedRateLimitRecord {
+    /// When the rate limit was detected (milliseconds since Unix epoch)
+    pub detected_at_unix_ms: u64,
+    /// How long the detecting process was told to wait, in seconds
+    pub delay_seconds: u64,
+    /// How the rate limit was detected
+    pub rate_limit_type: RateLimitType,
+    /// Process id of the process that detected it
+    pub pid: u32,
+}
+
+/// Contents of the shared state file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedRateLimitState {
+    /// Earliest time the next request may be sent (milliseconds since Unix epoch)
+    #[serde(default)]
+    pub next_slot_unix_ms: u64,
+    /// End of the current cool-down (milliseconds since Unix epoch)
+    #[serde(default)]
+    pub cooldown_until_unix_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_rate_limit: Option<SharedRateLimitRecord>,
+}
+
+/// File-lock-based request budget shared across processes.
+#[derive(Debug, Clone)]
+pub struct SharedRateLimiter {
+    path: PathBuf,
+    min_interval_ms: u64,
+}
+
+fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl SharedRateLimiter {
+    /// Default spacing between requests from all processes combined.
+    pub const DEFAULT_MIN_INTERVAL_MS: u64 = 500;
+
+    /// Use (or create on first use) the state file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            min_interval_ms: Self::DEFAULT_MIN_INTERVAL_MS,
+        }
+    }
+
+    /// The default per-user state file next to the saved session:
+    /// `~/.local/share/{app_name}/users/{username}/rate_limit.json`
+    pub fn for_user(manager: &SessionManager, username: &str) -> crate::Result<Self> {
+        Ok(Self::new(
+            manager.get_user_dir(username)?.join("rate_limit.json"),
+        ))
+    }
+
+    /// The state file shared by the tools that use the `lastfm-edit` session store
+    /// (`~/.local/share/lastfm-edit/users/{username}/rate_limit.json`).
+    pub fn for_lastfm_edit_user(username: &str) -> crate::Result<Self> {
+        Self::for_user(&SessionManager::new("lastfm-edit"), username)
+    }
+
+    /// Set the spacing between requests from all processes combined.
+    pub fn with_min_interval_ms(mut self, min_interval_ms: u64) -> Self {
+        self.min_interval_ms = min_interval_ms;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Claim the next send slot and return how long to wait for it.
+    ///
+    /// The wait covers both the spacing after other processes' requests and any
+    /// cool-down still running from a reported rate limit.
+    pub fn reserve(&self) -> crate::Result<Duration> {
+        self.update(|state, now| {
+            let slot = now
+                .max(state.next_slot_unix_ms)
+                .max(state.cooldown_until_unix_ms);
+            state.next_slot_unix_ms = slot + self.min_interval_ms;
+            Duration::from_millis(slot - now)
+        })
+    }
+
+    /// Time left in the current cool-down, if one is running.
+    pub fn cooldown_remaining(&self) -> crate::Result<Option<Duration>> {
+        let state = self.state()?;
+        let now = now_unix_ms();
+        Ok((state.cooldown_until_unix_ms > now)
+            .then(|| Duration::from_millis(state.cooldown_until_unix_ms - now)))
+    }
+
+    /// Start (or extend) a cool-down of `delay_seconds` for every process.
+    pub fn record_rate_limit(
+        &self,
+        delay_seconds: u64,
+        rate_limit_type: RateLimitType,
+    ) -> crate::Result<()> {
+        self.update(|state, now| {
+            let until = now + delay_seconds * 1000;
+            state.cooldown_until_unix_ms = state.cooldown_until_unix_ms.max(until);
+            state.last_rate_limit = Some(SharedRateLimitRecord {
+                detected_at_unix_ms: now,
+                delay_seconds,
+                rate_limit_type,
+                pid: std::process::id(),
+            });
+        })
+    }
+
+    /// Snapshot of the shared state.
+    pub fn state(&self) -> crate::Result<SharedRateLimitState> {
+        if !self.path.exists() {
+            return Ok(SharedRateLimitState::default());
+        }
+        let mut file = File::open(&self.path)?;
+        file.lock_shared()?;
+        let state = read_state(&mut file, &self.path);
+        file.unlock()?;
+        state
+    }
+
+    /// Read-modify-write the state file under an exclusive lock.
+    fn update<T>(&self, f: impl FnOnce(&mut SharedRateLimitState, u64) -> T) -> crate::Result<T> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        file.lock()?;
+
+        let mut state = match read_state(&mut file, &self.path) {
+            Ok(state) => state,
+            // A crash between truncating and rewriting leaves a partial file. Starting
+            // over repairs it; failing would switch the shared budget off for every
+            // process until someone deleted the file.
+            Err(LastFmError::Parse(e)) => {
+                log::warn!("{e}; resetting the shared rate limit state");
+                SharedRateLimitState::default()
+            }
+            Err(e) => return Err(e),
+        };
+        let result = f(&mut state, now_unix_ms());
+        let json = serde_json::to_string(&state).map_err(|e| {
+            LastFmError::Parse(format!("Failed to serialize rate limit state: {e}"))
+        })?;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(json.as_bytes())?;
+        file.unlock()?;
+        Ok(result)
+    }
+}
+
+fn read_state(file: &mut File, path: &Path) -> crate::Result<SharedRateLimitState> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    if contents.trim().is_empty() {
+        return Ok(SharedRateLimitState::default());
+    }
+    serde_json::from_str(&contents).map_err(|e| {
+        LastFmError::Parse(format!(
+            "Invalid rate limit state in '{}': {e}",
+            path.display()
+        ))
+    })
+}
@@ -0,0 +1,765 @@
+//! Preview a sequence of edits without sending any of them.
+//!
+//! [`SimulatedEditClient`] reads from Last.fm as usual, but edits, deletes and (un)loves
+//! are only recorded in an in-memory overlay. Every later read through the client,
+//! including scrobble discovery for further edits, sees the overlay applied, so a
+//! multi-step script behaves as it would against the real library:
+//!
+//! ```rust,no_run
+//! # use lastfm_edit::{LastFmEditClient, ScrobbleEdit, SimulatedEditClient};
+//! # async fn example(client: lastfm_edit::LastFmEditClientImpl) -> lastfm_edit::Result<()> {
+//! let client = SimulatedEditClient::simulate(client);
+//! client.edit_artist("Beatles", "The Beatles").await?;
+//! // Discovery finds the scrobbles renamed by the previous step.
+//! let edit = ScrobbleEdit::from_track_and_artist("Help! - Remastered 2009", "The Beatles")
+//!     .with_track_name("Help!");
+//! client.edit_scrobble(&edit).await?;
+//! println!("{}", client.report());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Scrobble-level listings (recent tracks, track scrobbles) follow every change exactly.
+//! Aggregated rows only carry play counts: a track row moves when all of its scrobbles
+//! are edited, album and artist rows move when a whole album or artist is edited, and
+//! single-scrobble edits and deletes take one play off the row they leave. Rows are not
+//! added to listings that never contained them.
+
+use crate::discovery::AsyncDiscoveryIterator;
+use crate::middleware::{
+    dry_run_result, Layered, Middleware, Mutation, MutationOutcome, ReadRequest,
+};
+use crate::r#trait::LastFmEditClient;
+use crate::types::{Album, Artist, EditResponse, ExactScrobbleEdit, ScrobbleEdit, Track};
+use crate::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A client that simulates edits on top of the real library. See the [module
+/// docs](self).
+pub type SimulatedEditClient<C> = Layered<SimulationLayer, C>;
+
+impl<C: LastFmEditClient> Layered<SimulationLayer, C> {
+    /// Simulate edits on top of `inner`, which is still used for every read.
+    pub fn simulate(inner: C) -> Self {
+        Self::new(SimulationLayer::new(), inner)
+    }
+
+    /// Everything that would have changed so far.
+    pub fn report(&self) -> SimulationReport {
+        self.middleware().report()
+    }
+}
+
+/// The metadata a scrobble is filed under.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScrobbleIdentity {
+    pub artist: String,
+    pub track: String,
+    pub album: String,
+    pub album_artist: String,
+}
+
+impl ScrobbleIdentity {
+    fn original(edit: &ExactScrobbleEdit) -> Self {
+        Self {
+            artist: edit.artist_name_original.clone(),
+            track: edit.track_name_original.clone(),
+            album: edit.album_name_original.clone(),
+            album_artist: edit.album_artist_name_original.clone(),
+        }
+    }
+
+    fn edited(edit: &ExactScrobbleEdit) -> Self {
+        Self {
+            artist: edit.artist_name.clone(),
+            track: edit.track_name.clone(),
+            album: edit.album_name.clone(),
+            album_artist: edit.album_artist_name.clone(),
+        }
+    }
+
+    /// Whether scrobbles filed under this identity are matched by `edit`'s originals.
+    fn matches(&self, edit: &ScrobbleEdit) -> bool {
+        self.artist == edit.artist_name_original
+            && matches_optional(&edit.track_name_original, &self.track)
+            && matches_optional(&edit.album_name_original, &self.album)
+            && matches_optional(&edit.album_artist_name_original, &self.album_artist)
+    }
+
+    /// A scrobble filed under this identity, unchanged by the edit.
+    fn to_exact_edit(&self, timestamp: u64, edit_all: bool) -> ExactScrobbleEdit {
+        ExactScrobbleEdit::new(
+            self.track.clone(),
+            self.album.clone(),
+            self.artist.clone(),
+            self.album_artist.clone(),
+            self.track.clone(),
+            self.album.clone(),
+            self.artist.clone(),
+            self.album_artist.clone(),
+            timestamp,
+            edit_all,
+        )
+    }
+}
+
+impl fmt::Display for ScrobbleIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} - {} [{} / {}]",
+            self.artist, self.track, self.album, self.album_artist
+        )
+    }
+}
+
+fn matches_optional(pattern: &Option<String>, value: &str) -> bool {
+    pattern.as_deref().is_none_or(|pattern| pattern == value)
+}
+
+/// One change recorded by a [`SimulatedEditClient`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum SimulatedChange {
+    /// Scrobbles moved to new metadata: all of `from` with `edit_all`, otherwise only
+    /// the scrobble at `timestamp`
+    Edited {
+        from: ScrobbleIdentity,
+        to: ScrobbleIdentity,
+        timestamp: u64,
+        edit_all: bool,
+    },
+    Deleted {
+        artist: String,
+        track: String,
+        timestamp: u64,
+    },
+    Loved {
+        artist: String,
+        track: String,
+        loved: bool,
+    },
+}
+
+/// Every change a [`SimulatedEditClient`] has recorded, in order.
+///
+/// Its `Display` output is a diff of the changed fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub changes: Vec<SimulatedChange>,
+}
+
+impl SimulationReport {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        let (mut edits, mut deletes, mut loves) = (0, 0, 0);
+        for change in &self.changes {
+            match change {
+                SimulatedChange::Edited {
+                    from,
+                    to,
+                    timestamp,
+                    edit_all,
+                } => {
+                    edits += 1;
+                    let scope = if *edit_all {
+                        "all scrobbles".to_string()
+                    } else {
+                        format!("scrobble at {timestamp}")
+                    };
+                    writeln!(f, "~ {from} ({scope})")?;
+                    for (field, old, new) in [
+                        ("artist", &from.artist, &to.artist),
+                        ("track", &from.track, &to.track),
+                        ("album", &from.album, &to.album),
+                        ("album artist", &from.album_artist, &to.album_artist),
+                    ] {
+                        if old != new {
+                            writeln!(f, "  - {field}: {old}")?;
+                            writeln!(f, "  + {field}: {new}")?;
+                        }
+                    }
+                }
+                SimulatedChange::Deleted {
+                    artist,
+                    track,
+                    timestamp,
+                } => {
+                    deletes += 1;
+                    writeln!(f, "- {artist} - {track} (scrobble at {timestamp})")?;
+                }
+                SimulatedChange::Loved {
+                    artist,
+                    track,
+                    loved,
+                } => {
+                    loves += 1;
+                    let sign = if *loved { '+' } else { '-' };
+                    writeln!(f, "{sign} loved: {artist} - {track}")?;
+                }
+            }
+        }
+        writeln!(
+            f,
+            "{edits} edit(s), {deletes} deletion(s), {loves} love change(s)"
+        )
+    }
+}
+
+/// A step of the overlay: a recorded change, or a whole artist or album moved by a
+/// pattern edit, which is what album and artist rows follow.
+#[derive(Debug, Clone)]
+enum Step {
+    Change(SimulatedChange),
+    ArtistMoved {
+        from: String,
+        to: String,
+    },
+    AlbumMoved {
+        from: (String, String),
+        to: (String, String),
+    },
+}
+
+/// The middleware behind [`SimulatedEditClient`]. Clones share their overlay.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationLayer {
+    steps: Arc<Mutex<Vec<Step>>>,
+}
+
+impl SimulationLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything that would have changed so far.
+    pub fn report(&self) -> SimulationReport {
+        SimulationReport {
+            changes: changes(&self.snapshot()).cloned().collect(),
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Step> {
+        self.steps.lock().unwrap().clone()
+    }
+
+    fn push(&self, step: Step) {
+        self.steps.lock().unwrap().push(step);
+    }
+
+    fn record_edit(&self, exact_edit: &ExactScrobbleEdit) {
+        let (from, to) = (
+            ScrobbleIdentity::original(exact_edit),
+            ScrobbleIdentity::edited(exact_edit),
+        );
+        if from != to {
+            self.push(Step::Change(SimulatedChange::Edited {
+                from,
+                to,
+                timestamp: exact_edit.timestamp,
+                edit_all: exact_edit.edit_all,
+            }));
+        }
+    }
+
+    /// Record the artist or album a pattern edit moves as a whole, if any.
+    fn record_aggregate_move(&self, edit: &ScrobbleEdit) {
+        if !edit.edit_all || edit.track_name_original.is_some() {
+            return;
+        }
+        match &edit.album_name_original {
+            None if edit.artist_name != edit.artist_name_original => {
+                self.push(Step::ArtistMoved {
+                    from: edit.artist_name_original.clone(),
+                    to: edit.artist_name.clone(),
+                });
+            }
+            None => {}
+            Some(album) => {
+                let from_artist = edit
+                    .album_artist_name_original
+                    .clone()
+                    .unwrap_or_else(|| edit.artist_name_original.clone());
+                let from = (album.clone(), from_artist.clone());
+                let to = (
+                    edit.album_name.clone().unwrap_or_else(|| album.clone()),
+                    edit.album_artist_name.clone().unwrap_or(from_artist),
+                );
+                if from != to {
+                    self.push(Step::AlbumMoved { from, to });
+                }
+            }
+        }
+    }
+}
+
+fn changes(steps: &[Step]) -> impl Iterator<Item = &SimulatedChange> {
+    steps.iter().filter_map(|step| match step {
+        Step::Change(change) => Some(change),
+        _ => None,
+    })
+}
+
+/// Follow scrobbles filed under `identity` through `steps`: the whole group, or with
+/// `single` just the scrobble at `timestamp`. `None` once they are deleted.
+fn resolve(
+    steps: &[Step],
+    mut identity: ScrobbleIdentity,
+    timestamp: u64,
+    single: bool,
+) -> Option<ScrobbleIdentity> {
+    for change in changes(steps) {
+        match change {
+            SimulatedChange::Edited {
+                from,
+                to,
+                timestamp: at,
+                edit_all,
+            } if *from == identity && (*edit_all || (single && *at == timestamp)) => {
+                identity = to.clone();
+            }
+            SimulatedChange::Deleted {
+                artist,
+                track,
+                timestamp: at,
+            } if single
+                && *at == timestamp
+                && *artist == identity.artist
+                && *track == identity.track =>
+            {
+                return None;
+            }
+            _ => {}
+        }
+    }
+    Some(identity)
+}
+
+/// Where a scrobble found by the real client is filed in the simulation, as an
+/// unchanged edit, or `None` if it no longer matches `edit`.
+fn retarget(
+    steps: &[Step],
+    found: &ExactScrobbleEdit,
+    edit: &ScrobbleEdit,
+) -> Option<ExactScrobbleEdit> {
+    let identity = resolve(
+        steps,
+        ScrobbleIdentity::original(found),
+        found.timestamp,
+        false,
+    )?;
+    identity
+        .matches(edit)
+        .then(|| identity.to_exact_edit(found.timestamp, found.edit_all))
+}
+
+/// Scrobbles the simulation has moved to metadata matching `edit`, as unchanged edits.
+fn moved_in(steps: &[Step], edit: &ScrobbleEdit) -> VecDeque<ExactScrobbleEdit> {
+    steps
+        .iter()
+        .enumerate()
+        .filter_map(|(index, step)| match step {
+            Step::Change(SimulatedChange::Edited {
+                to,
+                timestamp,
+                edit_all,
+                ..
+            }) => resolve(&steps[index + 1..], to.clone(), *timestamp, !edit_all)
+                .filter(|identity| identity.matches(edit))
+                .map(|identity| identity.to_exact_edit(*timestamp, *edit_all)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Scrobble discovery through the overlay: the real client's results, retargeted,
+/// followed by the scrobbles the simulation moved into the edit's scope.
+struct SimulatedDiscovery {
+    real: Option<Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>>>,
+    steps: Vec<Step>,
+    edit: ScrobbleEdit,
+    moved_in: Option<VecDeque<ExactScrobbleEdit>>,
+    seen: HashSet<ScrobbleIdentity>,
+}
+
+impl SimulatedDiscovery {
+    fn new(
+        real: Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>>,
+        steps: Vec<Step>,
+        edit: ScrobbleEdit,
+    ) -> Self {
+        Self {
+            real: Some(real),
+            steps,
+            edit,
+            moved_in: None,
+            seen: HashSet::new(),
+        }
+    }
+
+    fn moved_in(&mut self) -> &mut VecDeque<ExactScrobbleEdit> {
+        self.moved_in
+            .get_or_insert_with(|| moved_in(&self.steps, &self.edit))
+    }
+
+    /// `found` with the edit applied, unless it was already yielded
+    fn accept(&mut self, found: ExactScrobbleEdit) -> Option<ExactScrobbleEdit> {
+        self.seen
+            .insert(ScrobbleIdentity::original(&found))
+            .then(|| self.edit.apply_to(&found))
+    }
+}
+
+#[async_trait(?Send)]
+impl AsyncDiscoveryIterator<ExactScrobbleEdit> for SimulatedDiscovery {
+    async fn next(&mut self) -> Result<Option<ExactScrobbleEdit>> {
+        while let Some(real) = self.real.as_mut() {
+            match real.next().await {
+                Ok(Some(found)) => {
+                    if let Some(found) = retarget(&self.steps, &found, &self.edit) {
+                        if let Some(result) = self.accept(found) {
+                            return Ok(Some(result));
+                        }
+                    }
+                }
+                Ok(None) => self.real = None,
+                // Last.fm has no scrobbles under metadata that exists only in the
+                // simulation, so its lookup failing is expected then.
+                Err(e) if self.seen.is_empty() && !self.moved_in().is_empty() => {
+                    log::debug!("Ignoring failed discovery of simulated scrobbles: {e}");
+                    self.real = None;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        while let Some(found) = self.moved_in().pop_front() {
+            if let Some(result) = self.accept(found) {
+                return Ok(Some(result));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[async_trait(?Send)]
+impl Middleware for SimulationLayer {
+    fn rewrite_tracks(&self, request: &ReadRequest, tracks: &mut Vec<Track>) {
+        let steps = self.snapshot();
+        tracks.retain_mut(|track| simulate_track(&steps, track));
+
+        let artist = request.arg("artist");
+        let album = request.arg("album");
+        let track_name = request.arg("track");
+        tracks.retain(|track| {
+            artist.is_none_or(|artist| same_name(&track.artist, artist))
+                && track_name.is_none_or(|name| same_name(&track.name, name))
+                && album.is_none_or(|album| {
+                    track
+                        .album
+                        .as_deref()
+                        .is_none_or(|track_album| same_name(track_album, album))
+                })
+                && (request.method != "get_loved_tracks_page" || track.loved != Some(false))
+        });
+
+        merge_rows(
+            tracks,
+            |track| {
+                track.timestamp.is_none().then(|| {
+                    (
+                        track.artist.clone(),
+                        track.name.clone(),
+                        track.album.clone(),
+                    )
+                })
+            },
+            |track| &mut track.playcount,
+        );
+    }
+
+    fn rewrite_albums(&self, request: &ReadRequest, albums: &mut Vec<Album>) {
+        let steps = self.snapshot();
+        albums.retain_mut(|album| simulate_album(&steps, album));
+        if let Some(artist) = request.arg("artist") {
+            albums.retain(|album| same_name(&album.artist, artist));
+        }
+        merge_rows(
+            albums,
+            |album| Some((album.artist.clone(), album.name.clone())),
+            |album| &mut album.playcount,
+        );
+    }
+
+    fn rewrite_artists(&self, _request: &ReadRequest, artists: &mut Vec<Artist>) {
+        let steps = self.snapshot();
+        artists.retain_mut(|artist| simulate_artist(&steps, artist));
+        merge_rows(
+            artists,
+            |artist| Some(artist.name.clone()),
+            |artist| &mut artist.playcount,
+        );
+    }
+
+    fn rewrite_variations(
+        &self,
+        request: &ReadRequest,
+        variations: Result<Vec<ExactScrobbleEdit>>,
+    ) -> Result<Vec<ExactScrobbleEdit>> {
+        let (Some(track), Some(artist)) = (request.arg("track"), request.arg("artist")) else {
+            return variations;
+        };
+        let steps = self.snapshot();
+        let edit = ScrobbleEdit::from_track_and_artist(track, artist);
+        let moved_in = moved_in(&steps, &edit);
+
+        let found = match variations {
+            Ok(found) => found,
+            Err(_) if !moved_in.is_empty() => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let mut seen = HashSet::new();
+        Ok(found
+            .iter()
+            .filter_map(|found| retarget(&steps, found, &edit))
+            .chain(moved_in)
+            .filter(|variation| seen.insert(ScrobbleIdentity::original(variation)))
+            .collect())
+    }
+
+    fn discover(
+        &self,
+        edit: &ScrobbleEdit,
+        discovery: Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>>,
+    ) -> Box<dyn AsyncDiscoveryIterator<ExactScrobbleEdit>> {
+        Box::new(SimulatedDiscovery::new(
+            discovery,
+            self.snapshot(),
+            edit.clone(),
+        ))
+    }
+
+    async fn before_mutation(
+        &self,
+        mutation: &Mutation,
+        inner: &dyn LastFmEditClient,
+    ) -> Result<Option<MutationOutcome>> {
+        let outcome = match mutation {
+            Mutation::Edit { edit } => {
+                let discovered = self
+                    .discover(edit, inner.discover_scrobbles(edit.clone()))
+                    .collect_all()
+                    .await?;
+                if discovered.is_empty() {
+                    return Err(edit.no_scrobbles_error());
+                }
+                let results = discovered
+                    .iter()
+                    .map(|found| {
+                        let exact_edit = edit.apply_to(found);
+                        self.record_edit(&exact_edit);
+                        dry_run_result(exact_edit)
+                    })
+                    .collect();
+                self.record_aggregate_move(edit);
+                MutationOutcome::Edited {
+                    response: EditResponse::from_results(results),
+                }
+            }
+            Mutation::EditSingle { edit, .. } => {
+                self.record_edit(edit);
+                MutationOutcome::Edited {
+                    response: EditResponse::from_results(vec![dry_run_result(edit.clone())]),
+                }
+            }
+            Mutation::Delete {
+                artist,
+                track,
+                timestamp,
+            } => {
+                self.push(Step::Change(SimulatedChange::Deleted {
+                    artist: artist.clone(),
+                    track: track.clone(),
+                    timestamp: *timestamp,
+                }));
+                MutationOutcome::Accepted { accepted: true }
+            }
+            Mutation::Love { artist, track } | Mutation::Unlove { artist, track } => {
+                self.push(Step::Change(SimulatedChange::Loved {
+                    artist: artist.clone(),
+                    track: track.clone(),
+                    loved: matches!(mutation, Mutation::Love { .. }),
+                }));
+                MutationOutcome::Accepted { accepted: true }
+            }
+        };
+        Ok(Some(outcome))
+    }
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// Whether a track row filed under its (partly known) metadata holds scrobbles of `from`.
+fn track_row_matches(track: &Track, from: &ScrobbleIdentity) -> bool {
+    track.artist == from.artist
+        && track.name == from.track
+        && track
+            .album
+            .as_deref()
+            .is_none_or(|album| album == from.album)
+        && track
+            .album_artist
+            .as_deref()
+            .is_none_or(|album_artist| album_artist == from.album_artist)
+}
+
+/// Apply `steps` to a track row; `false` once nothing of it is left.
+fn simulate_track(steps: &[Step], track: &mut Track) -> bool {
+    let mut emptied = false;
+    for change in changes(steps) {
+        match change {
+            SimulatedChange::Edited {
+                from,
+                to,
+                timestamp,
+                edit_all,
+            } if track_row_matches(track, from) => match track.timestamp {
+                Some(at) if !edit_all && at != *timestamp => {}
+                None if !edit_all => {
+                    track.playcount = track.playcount.saturating_sub(1);
+                    emptied = track.playcount == 0;
+                }
+                _ => {
+                    track.artist = to.artist.clone();
+                    track.name = to.track.clone();
+                    if track.album.is_some() {
+                        track.album = Some(to.album.clone());
+                    }
+                    if track.album_artist.is_some() {
+                        track.album_artist = Some(to.album_artist.clone());
+                    }
+                    track.track_mbid = None;
+                    track.artist_mbid = None;
+                    track.album_mbid = None;
+                }
+            },
+            SimulatedChange::Deleted {
+                artist,
+                track: name,
+                timestamp,
+            } if track.artist == *artist && track.name == *name => match track.timestamp {
+                Some(at) if at == *timestamp => return false,
+                Some(_) => {}
+                None => {
+                    track.playcount = track.playcount.saturating_sub(1);
+                    emptied = track.playcount == 0;
+                }
+            },
+            SimulatedChange::Loved {
+                artist,
+                track: name,
+                loved,
+            } if track.artist == *artist && track.name == *name => {
+                track.loved = Some(*loved);
+            }
+            _ => {}
+        }
+    }
+    !emptied
+}
+
+/// Apply `steps` to an album row; `false` once nothing of it is left.
+fn simulate_album(steps: &[Step], album: &mut Album) -> bool {
+    let mut emptied = false;
+    for step in steps {
+        match step {
+            Step::AlbumMoved { from, to } if album.name == from.0 && album.artist == from.1 => {
+                album.name = to.0.clone();
+                album.artist = to.1.clone();
+            }
+            Step::Change(SimulatedChange::Edited {
+                from,
+                to,
+                edit_all: false,
+                ..
+            }) if album.name == from.album
+                && (album.artist == from.album_artist || album.artist == from.artist)
+                && (from.album != to.album || from.album_artist != to.album_artist) =>
+            {
+                album.playcount = album.playcount.saturating_sub(1);
+                emptied = album.playcount == 0;
+            }
+            _ => {}
+        }
+    }
+    !emptied
+}
+
+/// Apply `steps` to an artist row; `false` once nothing of it is left.
+fn simulate_artist(steps: &[Step], artist: &mut Artist) -> bool {
+    let mut emptied = false;
+    for step in steps {
+        let leaves = match step {
+            Step::ArtistMoved { from, to } if artist.name == *from => {
+                artist.name = to.clone();
+                false
+            }
+            Step::Change(SimulatedChange::Edited {
+                from,
+                to,
+                edit_all: false,
+                ..
+            }) => artist.name == from.artist && from.artist != to.artist,
+            Step::Change(SimulatedChange::Deleted { artist: name, .. }) => artist.name == *name,
+            _ => false,
+        };
+        if leaves {
+            artist.playcount = artist.playcount.saturating_sub(1);
+            emptied = artist.playcount == 0;
+        }
+    }
+    !emptied
+}
+
+/// Merge rows with the same key (rows keyed `None` are left alone), adding up their
+/// play counts into the first.
+fn merge_rows<T, K: PartialEq>(
+    rows: &mut Vec<T>,
+    key: impl Fn(&T) -> Option<K>,
+    playcount: impl Fn(&mut T) -> &mut u32,
+) {
+    let mut merged: Vec<(K, usize)> = Vec::new();
+    let mut index = 0;
+    while index < rows.len() {
+        let Some(row_key) = key(&rows[index]) else {
+            index += 1;
+            continue;
+        };
+        match merged.iter().find(|(key, _)| *key == row_key) {
+            Some(&(_, first)) => {
+                let mut row = rows.remove(index);
+                let plays = *playcount(&mut row);
+                *playcount(&mut rows[first]) += plays;
+            }
+            None => {
+                merged.push((row_key, index));
+                index += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,1478 @@
+//! In-process fake of the Last.fm website for offline tests.
+//!
+//! [`FakeLastFm`] implements [`http_client::HttpClient`] and answers the requests the
+//! scraping client makes with HTML shaped like the real site: the login form and CSRF
+//! cookie, the recent scrobbles pages, the library artist/album/track pages and the
+//! whole-library track and album listings (including their `from`/`to`, `date_preset`
+//! and `order` parameters), the loved tracks page and love/unlove POST, the scrobble edit
+//! form and edit POST, and the delete POST. Scrobbles live in shared, mutable state,
+//! so a test can apply an edit, re-fetch a page and observe the result.
+//!
+//! With [`FakeLastFm::with_api_credentials`] it also answers the signed JSON API calls
+//! (`auth.getMobileSession`, `track.scrobble`, `track.updateNowPlaying`), verifying each
+//! request's `api_sig`, and the unsigned reads (`user.getRecentTracks` with `from`/`to`,
+//! `user.getInfo`, `user.getTop*`, `user.getLovedTracks`, `track.getInfo`,
+//! `album.getInfo`, `artist.getInfo` and `artist.getCorrection`), computed from the same
+//! scrobbles.
+//!
+//! Only available with the `testing` feature.
+//!
+//! ```rust,no_run
+//! use lastfm_edit::testing::{FakeLastFm, FakeScrobble};
+//! use lastfm_edit::{ClientConfig, LastFmEditClientImpl};
+//!
+//! let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(vec![FakeScrobble::new(
+//!     "Artist", "Track", "Album", 1_700_000_000,
+//! )]);
+//! let client = LastFmEditClientImpl::from_session_with_client_config(
+//!     Box::new(fake.clone()),
+//!     fake.session(),
+//!     ClientConfig::for_testing(),
+//! );
+//! ```
+
+use crate::api::sign_api_params;
+use crate::types::{DatePreset, LastFmEditSession, LibraryDateRange, LibrarySort};
+use crate::vcr_form_data::parse_form_data;
+use http_client::{HttpClient, Request, Response};
+use http_types::{Method, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_BASE_URL: &str = "https://www.last.fm";
+const DEFAULT_CSRF_TOKEN: &str = "fake-csrf-token";
+const DEFAULT_PAGE_SIZE: usize = 50;
+const API_SESSION_KEY: &str = "fake-api-session-key";
+
+/// Body served by [`RateLimitResponse::Interstitial`]; matches the default
+/// "you're requesting too many pages" rate-limit pattern.
+const RATE_LIMIT_BODY: &str =
+    "<html><head><title>Last.fm - Rate Limited</title></head><body><p>you're requesting too many pages</p></body></html>";
+
+/// A single scrobble stored by [`FakeLastFm`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeScrobble {
+    pub artist: String,
+    pub track: String,
+    /// Album name; empty when the scrobble has no album metadata
+    pub album: String,
+    pub album_artist: String,
+    pub timestamp: u64,
+}
+
+impl FakeScrobble {
+    /// Create a scrobble whose album artist is the track artist.
+    pub fn new(artist: &str, track: &str, album: &str, timestamp: u64) -> Self {
+        Self {
+            artist: artist.to_string(),
+            track: track.to_string(),
+            album: album.to_string(),
+            album_artist: artist.to_string(),
+            timestamp,
+        }
+    }
+
+    /// Set a distinct album artist (compilations, soundtracks).
+    pub fn with_album_artist(mut self, album_artist: &str) -> Self {
+        self.album_artist = album_artist.to_string();
+        self
+    }
+}
+
+/// Canned rate-limit response returned by [`FakeLastFm`] in place of a real page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitResponse {
+    /// HTTP 429 with an optional `Retry-After` header
+    TooManyRequests { retry_after: Option<u64> },
+    /// HTTP 503 with an optional `Retry-After` header
+    ServiceUnavailable { retry_after: Option<u64> },
+    /// The "Rate Limited" interstitial page served with the given status
+    /// (Last.fm uses 406 for POSTs after edit bursts)
+    Interstitial { status: u16 },
+    /// The JSON API's error 29 ("Rate limit exceeded") body, served with HTTP 200
+    ApiRateLimitExceeded,
+}
+
+/// A request received by [`FakeLastFm`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path plus query string, without the base URL
+    pub path: String,
+    /// Decoded form fields for POST requests
+    pub form: HashMap<String, String>,
+    /// Whether this request was answered with a rate-limit response
+    pub rate_limited: bool,
+}
+
+#[derive(Debug, Clone)]
+struct PendingRateLimit {
+    /// Matching requests still let through before this one applies
+    skip: usize,
+    remaining: usize,
+    method: Option<Method>,
+    response: RateLimitResponse,
+}
+
+#[derive(Debug)]
+struct FakeState {
+    username: String,
+    password: String,
+    base_url: String,
+    csrf_token: String,
+    page_size: usize,
+    session_counter: u64,
+    default_session: String,
+    valid_sessions: Vec<String>,
+    scrobbles: Vec<FakeScrobble>,
+    /// Loved (artist, track) pairs, oldest love first
+    loved: Vec<(String, String)>,
+    rate_limits: Vec<PendingRateLimit>,
+    requests: Vec<RecordedRequest>,
+    api_credentials: Option<(String, String)>,
+    now_playing: Option<FakeScrobble>,
+    ignored_edits: usize,
+    failed_edits: usize,
+    failed_gets: usize,
+}
+
+/// Fake Last.fm website backed by in-memory scrobbles.
+///
+/// Clones share state, so keep one handle in the test to inspect or mutate the library
+/// while another is owned by the client under test.
+#[derive(Debug, Clone)]
+pub struct FakeLastFm {
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl FakeLastFm {
+    /// Create a fake site with a single account and an empty library.
+    pub fn new(username: &str, password: &str) -> Self {
+        let mut state = FakeState {
+            username: username.to_string(),
+            password: password.to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            csrf_token: DEFAULT_CSRF_TOKEN.to_string(),
+            page_size: DEFAULT_PAGE_SIZE,
+            session_counter: 0,
+            default_session: String::new(),
+            valid_sessions: Vec::new(),
+            scrobbles: Vec::new(),
+            loved: Vec::new(),
+            rate_limits: Vec::new(),
+            requests: Vec::new(),
+            api_credentials: None,
+            now_playing: None,
+            ignored_edits: 0,
+            failed_edits: 0,
+            failed_gets: 0,
+        };
+        // Pre-issue a session so tests can skip the login flow via `session()`.
+        state.default_session = state.issue_session();
+        state.valid_sessions.push(state.default_session.clone());
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Seed the library with scrobbles.
+    pub fn with_scrobbles(self, scrobbles: impl IntoIterator<Item = FakeScrobble>) -> Self {
+        self.state.lock().unwrap().scrobbles.extend(scrobbles);
+        self
+    }
+
+    /// Mark tracks as loved, given as (artist, track) pairs from oldest to newest love.
+    pub fn with_loved<'a>(self, tracks: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        self.state.lock().unwrap().loved.extend(
+            tracks
+                .into_iter()
+                .map(|(artist, track)| (artist.to_string(), track.to_string())),
+        );
+        self
+    }
+
+    /// Set the number of rows rendered per page (default 50).
+    pub fn with_page_size(self, page_size: usize) -> Self {
+        self.state.lock().unwrap().page_size = page_size.max(1);
+        self
+    }
+
+    /// Set the base URL used for sessions and redirects (default `https://www.last.fm`).
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        self.state.lock().unwrap().base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Accept signed API calls made with this API key and shared secret.
+    pub fn with_api_credentials(self, api_key: &str, api_secret: &str) -> Self {
+        self.state.lock().unwrap().api_credentials =
+            Some((api_key.to_string(), api_secret.to_string()));
+        self
+    }
+
+    /// The API session key `auth.getMobileSession` hands out.
+    pub fn api_session_key(&self) -> String {
+        API_SESSION_KEY.to_string()
+    }
+
+    /// The track most recently sent to `track.updateNowPlaying` (with a zero timestamp).
+    pub fn now_playing(&self) -> Option<FakeScrobble> {
+        self.state.lock().unwrap().now_playing.clone()
+    }
+
+    /// A logged-in session for the fake account, equivalent to what the login flow returns.
+    pub fn session(&self) -> LastFmEditSession {
+        let state = self.state.lock().unwrap();
+        LastFmEditSession::new(
+            state.username.clone(),
+            vec![
+                format!("csrftoken={}", state.csrf_token),
+                state.default_session.clone(),
+            ],
+            Some(state.csrf_token.clone()),
+            state.base_url.clone(),
+        )
+    }
+
+    /// Snapshot of the library, newest scrobble first.
+    pub fn scrobbles(&self) -> Vec<FakeScrobble> {
+        self.state.lock().unwrap().sorted_scrobbles()
+    }
+
+    /// Loved (artist, track) pairs, most recently loved first.
+    pub fn loved(&self) -> Vec<(String, String)> {
+        self.state
+            .lock()
+            .unwrap()
+            .loved
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    /// Add a scrobble to the library.
+    pub fn add_scrobble(&self, scrobble: FakeScrobble) {
+        self.state.lock().unwrap().scrobbles.push(scrobble);
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Answer the next `count` requests (of any method) with `response`.
+    pub fn rate_limit_next(&self, count: usize, response: RateLimitResponse) {
+        self.push_rate_limit(count, None, response);
+    }
+
+    /// Let the next `skip` requests through, then answer the `count` after them with
+    /// `response`.
+    pub fn rate_limit_after(&self, skip: usize, count: usize, response: RateLimitResponse) {
+        if count > 0 {
+            self.state
+                .lock()
+                .unwrap()
+                .rate_limits
+                .push(PendingRateLimit {
+                    skip,
+                    remaining: count,
+                    method: None,
+                    response,
+                });
+        }
+    }
+
+    /// Answer the next `count` POST requests (edits, deletes, login) with `response`.
+    pub fn rate_limit_next_posts(&self, count: usize, response: RateLimitResponse) {
+        self.push_rate_limit(count, Some(Method::Post), response);
+    }
+
+    /// Report the next `count` valid edits as successful without applying them, the way
+    /// Last.fm occasionally acknowledges an edit that never takes effect.
+    pub fn ignore_next_edits(&self, count: usize) {
+        self.state.lock().unwrap().ignored_edits += count;
+    }
+
+    /// Apply the next `count` valid edits but answer them with a 502, the way Last.fm
+    /// sometimes fails after the change has already been saved.
+    pub fn fail_next_edits_after_applying(&self, count: usize) {
+        self.state.lock().unwrap().failed_edits += count;
+    }
+
+    /// Answer the next `count` GET requests with a 502.
+    pub fn fail_next_gets(&self, count: usize) {
+        self.state.lock().unwrap().failed_gets += count;
+    }
+
+    /// Invalidate every issued session, as if Last.fm logged the account out.
+    pub fn expire_sessions(&self) {
+        self.state.lock().unwrap().valid_sessions.clear();
+    }
+
+    /// Replace the CSRF token; forms submitted with the old token are rejected.
+    pub fn rotate_csrf_token(&self, token: &str) {
+        self.state.lock().unwrap().csrf_token = token.to_string();
+    }
+
+    fn push_rate_limit(&self, count: usize, method: Option<Method>, response: RateLimitResponse) {
+        if count == 0 {
+            return;
+        }
+        self.state
+            .lock()
+            .unwrap()
+            .rate_limits
+            .push(PendingRateLimit {
+                skip: 0,
+                remaining: count,
+                method,
+                response,
+            });
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for FakeLastFm {
+    async fn send(&self, mut req: Request) -> std::result::Result<Response, http_types::Error> {
+        let method = req.method();
+        let url = req.url().clone();
+        let cookies = req
+            .header("cookie")
+            .map(|values| values.as_str().to_string())
+            .unwrap_or_default();
+        let body = if method == Method::Post {
+            req.body_string().await?
+        } else {
+            String::new()
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let form = parse_form_data(&body);
+
+        if let Some(rate_limit) = state.take_rate_limit(method) {
+            state.requests.push(RecordedRequest {
+                method: method.to_string(),
+                path,
+                form,
+                rate_limited: true,
+            });
+            return Ok(rate_limit_response(&rate_limit));
+        }
+
+        state.requests.push(RecordedRequest {
+            method: method.to_string(),
+            path,
+            form: form.clone(),
+            rate_limited: false,
+        });
+
+        if method == Method::Get && state.failed_gets > 0 {
+            state.failed_gets -= 1;
+            return Ok(bad_gateway());
+        }
+
+        let query: HashMap<String, String> = url
+            .query_pairs()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let page = query
+            .get("page")
+            .and_then(|p| p.parse::<usize>().ok())
+            .unwrap_or(1)
+            .max(1);
+        let range = library_range(&query);
+        let sort = match query.get("order").map(String::as_str) {
+            Some("name") => LibrarySort::Name,
+            _ => LibrarySort::Scrobbles,
+        };
+        let segments: Vec<String> = url
+            .path()
+            .trim_matches('/')
+            .split('/')
+            .map(decode_segment)
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let authenticated = state.is_authenticated(&cookies);
+
+        let response = match (method, segments.as_slice()) {
+            (Method::Get, ["login"]) => state.login_page(None),
+            (Method::Post, ["login"]) => state.login(&form),
+            (Method::Post, ["2.0"]) => state.api_call(&form),
+            (Method::Get, ["2.0"]) => state.api_read(&query, page),
+            (Method::Get, ["settings", "subscription", "automatic-edits", "tracks"]) => {
+                if authenticated {
+                    html_response(StatusCode::Ok, page_shell("Automatic edits", ""))
+                } else {
+                    state.login_redirect(url.path())
+                }
+            }
+            (_, ["user", user, ..]) if *user != state.username => not_found(),
+            (Method::Get, ["user", _, "loved"]) => state.loved_page(page),
+            (Method::Post, ["user", _, "loved"]) if !authenticated => {
+                html_response(StatusCode::Forbidden, page_shell("Forbidden", ""))
+            }
+            (Method::Post, ["user", _, "loved"]) => state.apply_love(&form),
+            (_, ["user", _, "library", ..]) if !authenticated => match method {
+                Method::Get => state.login_redirect(url.path()),
+                _ => html_response(StatusCode::Forbidden, page_shell("Forbidden", "")),
+            },
+            (Method::Get, ["user", _, "library"]) => state.recent_page(page),
+            (Method::Get, ["user", _, "library", "artists"]) => state.artists_page(range, page),
+            (Method::Get, ["user", _, "library", "tracks"]) => {
+                state.library_tracks_page(range, sort, page)
+            }
+            (Method::Get, ["user", _, "library", "albums"]) => {
+                state.library_albums_page(range, sort, page)
+            }
+            (Method::Get, ["user", _, "library", "edit"]) => state.edit_form(),
+            (Method::Post, ["user", _, "library", "edit"]) => state.apply_edit(&form),
+            (Method::Post, ["user", _, "library", "delete"]) => state.apply_delete(&form),
+            (Method::Get, ["user", _, "library", "music", "+noredirect", artist, "_", track])
+            | (Method::Get, ["user", _, "library", "music", artist, "_", track]) => {
+                state.track_page(artist, track, page)
+            }
+            (Method::Get, ["user", _, "library", "music", artist, "+tracks"]) => {
+                state.artist_tracks_page(artist, range, page)
+            }
+            (Method::Get, ["user", _, "library", "music", artist, "+albums"]) => {
+                state.artist_albums_page(artist, range, page)
+            }
+            (Method::Get, ["user", _, "library", "music", artist, album]) => {
+                state.album_tracks_page(artist, album, page)
+            }
+            _ => not_found(),
+        };
+
+        Ok(response)
+    }
+}
+
+impl FakeState {
+    fn issue_session(&mut self) -> String {
+        self.session_counter += 1;
+        // The login flow only accepts `sessionid=.` cookies longer than 50 characters.
+        format!("sessionid=.fake-session-{:0>40}", self.session_counter)
+    }
+
+    fn is_authenticated(&self, cookie_header: &str) -> bool {
+        cookie_header
+            .split(';')
+            .map(str::trim)
+            .any(|cookie| self.valid_sessions.iter().any(|valid| valid == cookie))
+    }
+
+    fn take_rate_limit(&mut self, method: Method) -> Option<RateLimitResponse> {
+        let index = self
+            .rate_limits
+            .iter()
+            .position(|pending| pending.method.is_none_or(|m| m == method))?;
+        let pending = &mut self.rate_limits[index];
+        if pending.skip > 0 {
+            pending.skip -= 1;
+            return None;
+        }
+        let response = pending.response.clone();
+        pending.remaining -= 1;
+        if pending.remaining == 0 {
+            self.rate_limits.remove(index);
+        }
+        Some(response)
+    }
+
+    fn sorted_scrobbles(&self) -> Vec<FakeScrobble> {
+        let mut scrobbles = self.scrobbles.clone();
+        scrobbles.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+        scrobbles
+    }
+
+    fn csrf_input(&self) -> String {
+        format!(
+            r#"<input type="hidden" name="csrfmiddlewaretoken" value="{}">"#,
+            escape(&self.csrf_token)
+        )
+    }
+
+    // ========================================================================
+    // Login
+    // ========================================================================
+
+    fn login_page(&self, error: Option<&str>) -> Response {
+        let error_html = error
+            .map(|message| {
+                format!(
+                    r#"<div class="alert alert-danger">{}</div>"#,
+                    escape(message)
+                )
+            })
+            .unwrap_or_default();
+        let body = format!(
+            r#"{error_html}<form action="/login" method="post">{}<input type="hidden" name="next" value="/home"><input name="username_or_email"><input name="password" type="password"></form>"#,
+            self.csrf_input()
+        );
+        let mut response = html_response(StatusCode::Ok, page_shell("Login", &body));
+        let _ = response.append_header(
+            "set-cookie",
+            format!("csrftoken={}; Path=/", self.csrf_token),
+        );
+        response
+    }
+
+    fn login(&mut self, form: &HashMap<String, String>) -> Response {
+        let field = |name: &str| form.get(name).map(String::as_str).unwrap_or_default();
+        if field("csrfmiddlewaretoken") != self.csrf_token {
+            return html_response(
+                StatusCode::Forbidden,
+                page_shell(
+                    "Forbidden",
+                    r#"<div class="alert alert-danger">CSRF verification failed.</div>"#,
+                ),
+            );
+        }
+        if field("username_or_email") != self.username || field("password") != self.password {
+            return self.login_page(Some("Please enter a correct username and password."));
+        }
+
+        let session = self.issue_session();
+        self.valid_sessions.push(session.clone());
+        let mut response = Response::new(StatusCode::Found);
+        let _ = response.insert_header("location", format!("{}/home", self.base_url));
+        let _ = response.append_header("set-cookie", format!("{session}; Path=/; HttpOnly"));
+        response
+    }
+
+    fn login_redirect(&self, next: &str) -> Response {
+        let mut response = Response::new(StatusCode::Found);
+        let _ = response.insert_header(
+            "location",
+            format!("{}/login?next={}", self.base_url, urlencoding::encode(next)),
+        );
+        response
+    }
+
+    // ========================================================================
+    // Library pages
+    // ========================================================================
+
+    fn recent_page(&self, page: usize) -> Response {
+        let scrobbles = self.sorted_scrobbles();
+        let (rows, total_pages) = paginate(&scrobbles, page, self.page_size);
+        let rows: String = rows.iter().map(|s| self.scrobble_row(s)).collect();
+        let body = format!(
+            r#"{}<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            self.csrf_input(),
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, page_shell("Library", &body))
+    }
+
+    fn track_page(&self, artist: &str, track: &str, page: usize) -> Response {
+        let scrobbles: Vec<FakeScrobble> = self
+            .sorted_scrobbles()
+            .into_iter()
+            .filter(|s| s.artist == artist && s.track == track)
+            .collect();
+        if scrobbles.is_empty() {
+            return not_found();
+        }
+        let (rows, total_pages) = paginate(&scrobbles, page, self.page_size);
+        let rows: String = rows.iter().map(|s| self.scrobble_row(s)).collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, page_shell(track, &body))
+    }
+
+    fn artists_page(&self, range: LibraryDateRange, page: usize) -> Response {
+        let counts = count_by(
+            self.scrobbles
+                .iter()
+                .filter(|s| range.contains_timestamp(s.timestamp))
+                .map(|s| s.artist.clone()),
+        );
+        let (rows, total_pages) = paginate(&counts, page, self.page_size);
+        let rows: String = rows
+            .iter()
+            .map(|(artist, count)| {
+                format!(
+                    r#"<tr class="chartlist-row js-link-block"><td class="chartlist-name"><a href="/music/{}">{}</a></td><td class="chartlist-bar"><span class="chartlist-count-bar">{} scrobbles</span></td></tr>"#,
+                    encode_path(artist),
+                    escape(artist),
+                    count
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, page_shell("Artists", &body))
+    }
+
+    fn artist_tracks_page(&self, artist: &str, range: LibraryDateRange, page: usize) -> Response {
+        let counts = count_by(
+            self.scrobbles
+                .iter()
+                .filter(|s| s.artist == artist && range.contains_timestamp(s.timestamp))
+                .map(|s| s.track.clone()),
+        );
+        self.aggregate_tracks_response(artist, &counts, page, self.page_size)
+    }
+
+    fn library_tracks_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: usize,
+    ) -> Response {
+        let mut counts = count_by(
+            self.scrobbles
+                .iter()
+                .filter(|s| range.contains_timestamp(s.timestamp))
+                .map(|s| (s.artist.clone(), s.track.clone())),
+        );
+        if sort == LibrarySort::Name {
+            counts.sort_by(|a, b| (&a.0 .1, &a.0 .0).cmp(&(&b.0 .1, &b.0 .0)));
+        }
+        let (rows, total_pages) = paginate(&counts, page, self.page_size);
+        let rows: String = rows
+            .iter()
+            .map(|((artist, track), count)| {
+                format!(
+                    r#"<tr class="chartlist-row"><td class="chartlist-name"><a href="/music/{}/_/{}">{}</a></td><td class="chartlist-artist"><a href="/music/{}">{}</a></td><td class="chartlist-bar"><span class="chartlist-count-bar"><span class="chartlist-count-bar-value">{} scrobbles</span></span></td></tr>"#,
+                    encode_path(artist),
+                    encode_path(track),
+                    escape(track),
+                    encode_path(artist),
+                    escape(artist),
+                    count
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, page_shell("Tracks", &body))
+    }
+
+    fn library_albums_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: usize,
+    ) -> Response {
+        let mut counts = count_by(
+            self.scrobbles
+                .iter()
+                .filter(|s| !s.album.is_empty() && range.contains_timestamp(s.timestamp))
+                .map(|s| (s.album_artist.clone(), s.album.clone())),
+        );
+        if sort == LibrarySort::Name {
+            counts.sort_by(|a, b| (&a.0 .1, &a.0 .0).cmp(&(&b.0 .1, &b.0 .0)));
+        }
+        let (rows, total_pages) = paginate(&counts, page, self.page_size);
+        let rows: String = rows
+            .iter()
+            .map(|((artist, album), count)| {
+                format!(
+                    r#"<tr class="chartlist-row"><td class="chartlist-name"><a href="/music/{}/{}">{}</a></td><td class="chartlist-artist"><a href="/music/{}">{}</a></td><td class="chartlist-bar"><span class="chartlist-count-bar"><span class="chartlist-count-bar-value">{} scrobbles</span></span></td></tr>"#,
+                    encode_path(artist),
+                    encode_path(album),
+                    escape(album),
+                    encode_path(artist),
+                    escape(artist),
+                    count
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, page_shell("Albums", &body))
+    }
+
+    /// Album pages list every track at once; the client never asks for page 2.
+    fn album_tracks_page(&self, artist: &str, album: &str, page: usize) -> Response {
+        let counts = count_by(
+            self.scrobbles
+                .iter()
+                .filter(|s| s.artist == artist && s.album == album)
+                .map(|s| s.track.clone()),
+        );
+        self.aggregate_tracks_response(artist, &counts, page, counts.len().max(1))
+    }
+
+    fn aggregate_tracks_response(
+        &self,
+        artist: &str,
+        counts: &[(String, usize)],
+        page: usize,
+        page_size: usize,
+    ) -> Response {
+        let (rows, total_pages) = paginate(counts, page, page_size);
+        let rows: String = rows
+            .iter()
+            .map(|(track, count)| {
+                format!(
+                    r#"<tr class="chartlist-row"><td class="chartlist-name"><a href="/music/{}/_/{}">{}</a></td><td class="chartlist-artist"><a href="/music/{}">{}</a></td><td class="chartlist-bar"><span class="chartlist-count-bar"><span class="chartlist-count-bar-value">{} scrobbles</span></span></td></tr>"#,
+                    encode_path(artist),
+                    encode_path(track),
+                    escape(track),
+                    encode_path(artist),
+                    escape(artist),
+                    count
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, body)
+    }
+
+    fn artist_albums_page(&self, artist: &str, range: LibraryDateRange, page: usize) -> Response {
+        let counts = count_by(
+            self.scrobbles
+                .iter()
+                .filter(|s| {
+                    s.artist == artist
+                        && !s.album.is_empty()
+                        && range.contains_timestamp(s.timestamp)
+                })
+                .map(|s| s.album.clone()),
+        );
+        let (rows, total_pages) = paginate(&counts, page, self.page_size);
+        let rows: String = rows
+            .iter()
+            .map(|(album, count)| {
+                format!(
+                    r#"<tr class="chartlist-row"><td class="chartlist-name"><a href="/music/{}/{}">{}</a></td><td class="chartlist-bar"><span class="chartlist-count-bar"><span class="chartlist-count-bar-value">{} scrobbles</span></span></td></tr>"#,
+                    encode_path(artist),
+                    encode_path(album),
+                    escape(album),
+                    count
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, body)
+    }
+
+    fn loved_page(&self, page: usize) -> Response {
+        let loved: Vec<&(String, String)> = self.loved.iter().rev().collect();
+        let (rows, total_pages) = paginate(&loved, page, self.page_size);
+        let rows: String = rows
+            .iter()
+            .map(|(artist, track)| {
+                format!(
+                    r#"<tr class="chartlist-row">{}<td class="chartlist-name"><a href="/music/{}/_/{}">{}</a></td><td class="chartlist-artist"><a href="/music/{}">{}</a></td></tr>"#,
+                    self.love_cell(artist, track),
+                    encode_path(artist),
+                    encode_path(track),
+                    escape(track),
+                    encode_path(artist),
+                    escape(artist),
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"<table class="chartlist"><tbody>{rows}</tbody></table>{}"#,
+            pagination(page, total_pages)
+        );
+        html_response(StatusCode::Ok, page_shell("Loved Tracks", &body))
+    }
+
+    fn is_loved(&self, artist: &str, track: &str) -> bool {
+        self.loved.iter().any(|(a, t)| a == artist && t == track)
+    }
+
+    /// The love toggle cell, in the state Last.fm renders for this track.
+    fn love_cell(&self, artist: &str, track: &str) -> String {
+        let (state, action) = if self.is_loved(artist, track) {
+            ("loved", "unlove")
+        } else {
+            ("unloved", "love")
+        };
+        format!(
+            r#"<td class="chartlist-loved"><div data-toggle-button data-toggle-button-current-state="{state}"><form action="/user/{user}/loved" method="post">{csrf}<input type="hidden" name="action" value="{action}"><input type="hidden" name="track" value="{track}"><input type="hidden" name="artist" value="{artist}"></form></div></td>"#,
+            user = escape(&self.username),
+            csrf = self.csrf_input(),
+            track = escape(track),
+            artist = escape(artist),
+        )
+    }
+
+    /// A scrobble row with its hidden edit form, as on the recent scrobbles and track pages.
+    fn scrobble_row(&self, scrobble: &FakeScrobble) -> String {
+        let input = |name: &str, value: &str| {
+            format!(
+                r#"<input type="hidden" name="{name}" value="{}">"#,
+                escape(value)
+            )
+        };
+        format!(
+            r#"<tr class="chartlist-row" data-timestamp="{ts}">{love}<td class="chartlist-name"><a href="/music/{artist_path}/_/{track_path}">{track}</a></td><td class="chartlist-artist"><a href="/music/{artist_path}">{artist}</a></td><td class="chartlist-more"><form data-edit-scrobble method="post" action="/user/{user}/library/edit">{csrf}{}{}{}{}{}</form></td></tr>"#,
+            input("track_name", &scrobble.track),
+            input("artist_name", &scrobble.artist),
+            input("album_name", &scrobble.album),
+            input("album_artist_name", &scrobble.album_artist),
+            input("timestamp", &scrobble.timestamp.to_string()),
+            ts = scrobble.timestamp,
+            artist_path = encode_path(&scrobble.artist),
+            track_path = encode_path(&scrobble.track),
+            track = escape(&scrobble.track),
+            artist = escape(&scrobble.artist),
+            user = escape(&self.username),
+            csrf = self.csrf_input(),
+            love = self.love_cell(&scrobble.artist, &scrobble.track),
+        )
+    }
+
+    // ========================================================================
+    // Edit and delete
+    // ========================================================================
+
+    fn edit_form(&self) -> Response {
+        let body = format!(
+            r#"<form data-edit-scrobble method="post">{}</form>"#,
+            self.csrf_input()
+        );
+        html_response(StatusCode::Ok, body)
+    }
+
+    fn apply_edit(&mut self, form: &HashMap<String, String>) -> Response {
+        let field = |name: &str| form.get(name).cloned().unwrap_or_default();
+        if field("csrfmiddlewaretoken") != self.csrf_token {
+            return html_response(
+                StatusCode::Forbidden,
+                "CSRF verification failed.".to_string(),
+            );
+        }
+
+        let track = field("track_name");
+        let artist = field("artist_name");
+        if track.trim().is_empty() || artist.trim().is_empty() {
+            return edit_error("Track name and artist name cannot be empty.");
+        }
+
+        let Ok(timestamp) = field("timestamp").parse::<u64>() else {
+            return edit_error("Invalid timestamp.");
+        };
+        let edit_all = form.contains_key("edit_all");
+        let original = (
+            field("track_name_original"),
+            field("artist_name_original"),
+            field("album_name_original"),
+            field("album_artist_name_original"),
+        );
+        let matches_original = |s: &FakeScrobble| {
+            s.track == original.0
+                && s.artist == original.1
+                && s.album == original.2
+                && s.album_artist == original.3
+        };
+
+        if !self
+            .scrobbles
+            .iter()
+            .any(|s| matches_original(s) && s.timestamp == timestamp)
+        {
+            return edit_error("The scrobble you are trying to edit could not be found.");
+        }
+
+        let album = field("album_name");
+        let album_artist = field("album_artist_name");
+        let ignored = self.ignored_edits > 0;
+        self.ignored_edits = self.ignored_edits.saturating_sub(1);
+        for scrobble in self
+            .scrobbles
+            .iter_mut()
+            .filter(|s| !ignored && matches_original(s) && (edit_all || s.timestamp == timestamp))
+        {
+            scrobble.track = track.clone();
+            scrobble.artist = artist.clone();
+            scrobble.album = album.clone();
+            scrobble.album_artist = album_artist.clone();
+        }
+
+        if self.failed_edits > 0 {
+            self.failed_edits -= 1;
+            return bad_gateway();
+        }
+
+        let body = format!(
+            r#"<div class="alert alert-success">Your scrobble has been edited.</div><table class="chartlist"><tbody><tr><td class="chartlist-name"><a href="/music/{}/_/{}">{}</a></td><td class="chartlist-album"><a href="/music/{}/{}">{}</a></td></tr></tbody></table>"#,
+            encode_path(&artist),
+            encode_path(&track),
+            escape(&track),
+            encode_path(&album_artist),
+            encode_path(&album),
+            escape(&album)
+        );
+        html_response(StatusCode::Ok, body)
+    }
+
+    fn apply_delete(&mut self, form: &HashMap<String, String>) -> Response {
+        let field = |name: &str| form.get(name).cloned().unwrap_or_default();
+        if field("csrfmiddlewaretoken") != self.csrf_token {
+            return html_response(
+                StatusCode::Forbidden,
+                "CSRF verification failed.".to_string(),
+            );
+        }
+
+        let artist = field("artist_name");
+        let track = field("track_name");
+        let timestamp = field("timestamp").parse::<u64>().ok();
+        let position = self
+            .scrobbles
+            .iter()
+            .position(|s| s.artist == artist && s.track == track && Some(s.timestamp) == timestamp);
+
+        match position {
+            Some(index) => {
+                self.scrobbles.remove(index);
+                json_response(StatusCode::Ok, r#"{"result": true}"#)
+            }
+            None => json_response(StatusCode::NotFound, r#"{"result": false}"#),
+        }
+    }
+
+    fn apply_love(&mut self, form: &HashMap<String, String>) -> Response {
+        let field = |name: &str| form.get(name).cloned().unwrap_or_default();
+        if field("csrfmiddlewaretoken") != self.csrf_token {
+            return html_response(
+                StatusCode::Forbidden,
+                "CSRF verification failed.".to_string(),
+            );
+        }
+
+        let artist = field("artist");
+        let track = field("track");
+        match field("action").as_str() {
+            "love" => {
+                if !self.is_loved(&artist, &track) {
+                    self.loved.push((artist, track));
+                }
+            }
+            "unlove" => self.loved.retain(|(a, t)| *a != artist || *t != track),
+            _ => return json_response(StatusCode::BadRequest, r#"{"result": false}"#),
+        }
+        json_response(StatusCode::Ok, r#"{"result": true}"#)
+    }
+
+    fn api_call(&mut self, form: &HashMap<String, String>) -> Response {
+        let Some((api_key, api_secret)) = self.api_credentials.clone() else {
+            return api_error(10, "Invalid API key");
+        };
+        let field = |name: &str| form.get(name).cloned().unwrap_or_default();
+        if field("api_key") != api_key {
+            return api_error(10, "Invalid API key");
+        }
+
+        let params: Vec<(String, String)> = form
+            .iter()
+            .filter(|(name, _)| *name != "api_sig")
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        if field("api_sig") != sign_api_params(&params, &api_secret) {
+            return api_error(13, "Invalid method signature supplied");
+        }
+
+        let method = field("method");
+        if method != "auth.getMobileSession" && field("sk") != API_SESSION_KEY {
+            return api_error(9, "Invalid session key - Please re-authenticate");
+        }
+
+        match method.as_str() {
+            "auth.getMobileSession" => {
+                if field("username") != self.username || field("password") != self.password {
+                    return api_error(
+                        4,
+                        "Authentication Failed - You do not have permissions to access the service",
+                    );
+                }
+                let body = serde_json::json!({
+                    "session": { "name": self.username, "key": API_SESSION_KEY, "subscriber": 0 }
+                });
+                json_response(StatusCode::Ok, &body.to_string())
+            }
+            "track.scrobble" => self.api_scrobble(form),
+            "track.updateNowPlaying" => {
+                let scrobble =
+                    FakeScrobble::new(&field("artist"), &field("track"), &field("album"), 0);
+                self.now_playing = Some(scrobble);
+                json_response(StatusCode::Ok, r#"{"nowplaying": {}}"#)
+            }
+            _ => api_error(
+                3,
+                "Invalid Method - No method with that name in this package",
+            ),
+        }
+    }
+
+    fn api_scrobble(&mut self, form: &HashMap<String, String>) -> Response {
+        let mut results = Vec::new();
+        let mut accepted = 0;
+
+        for i in 0.. {
+            let field = |name: &str| form.get(&format!("{name}[{i}]")).cloned();
+            let Some(artist) = field("artist") else {
+                break;
+            };
+            let track = field("track").unwrap_or_default();
+            let timestamp = field("timestamp").and_then(|t| t.parse::<u64>().ok());
+
+            let (code, message) = if artist.trim().is_empty() {
+                (1, "Artist was ignored")
+            } else if track.trim().is_empty() {
+                (2, "Track was ignored")
+            } else if timestamp.is_none() {
+                (3, "Timestamp failed filter")
+            } else {
+                (0, "")
+            };
+
+            if code == 0 {
+                let album = field("album").unwrap_or_default();
+                let mut scrobble =
+                    FakeScrobble::new(&artist, &track, &album, timestamp.unwrap_or_default());
+                if let Some(album_artist) = field("albumArtist") {
+                    scrobble = scrobble.with_album_artist(&album_artist);
+                }
+                self.scrobbles.push(scrobble);
+                accepted += 1;
+            }
+
+            results.push(serde_json::json!({
+                "artist": { "corrected": "0", "#text": artist },
+                "track": { "corrected": "0", "#text": track },
+                "ignoredMessage": { "code": code.to_string(), "#text": message },
+            }));
+        }
+
+        let ignored = results.len() - accepted;
+        let body = serde_json::json!({
+            "scrobbles": {
+                "scrobble": results,
+                "@attr": { "accepted": accepted, "ignored": ignored },
+            }
+        });
+        json_response(StatusCode::Ok, &body.to_string())
+    }
+
+    /// Unsigned API reads; only the API key is checked.
+    fn api_read(&self, query: &HashMap<String, String>, page: usize) -> Response {
+        let field = |name: &str| query.get(name).cloned().unwrap_or_default();
+        match &self.api_credentials {
+            Some((api_key, _)) if field("api_key") == *api_key => {}
+            _ => return api_error(10, "Invalid API key"),
+        }
+        let limit = query
+            .get("limit")
+            .and_then(|l| l.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .max(1);
+        let user_matches = |name: &str| field(name) == self.username;
+
+        let method = field("method");
+        let body = match method.as_str() {
+            // `from` is inclusive and `to` exclusive, as observed on the live API.
+            "user.getrecenttracks" | "user.getRecentTracks" => {
+                if !user_matches("user") {
+                    return api_error(6, "User not found");
+                }
+                let bound = |name: &str| query.get(name).and_then(|t| t.parse::<u64>().ok());
+                let (from, to) = (bound("from"), bound("to"));
+                let scrobbles: Vec<FakeScrobble> = self
+                    .sorted_scrobbles()
+                    .into_iter()
+                    .filter(|s| from.is_none_or(|from| s.timestamp >= from))
+                    .filter(|s| to.is_none_or(|to| s.timestamp < to))
+                    .collect();
+                let (rows, total_pages) = paginate(&scrobbles, page, limit);
+                let tracks: Vec<_> = rows
+                    .iter()
+                    .map(|s| {
+                        serde_json::json!({
+                            "name": s.track,
+                            "mbid": "",
+                            "artist": { "name": s.artist, "mbid": "" },
+                            "album": { "#text": s.album, "mbid": "" },
+                            "date": { "uts": s.timestamp.to_string() },
+                            "loved": u8::from(self.is_loved(&s.artist, &s.track)).to_string(),
+                        })
+                    })
+                    .collect();
+                serde_json::json!({ "recenttracks": {
+                    "track": tracks,
+                    "@attr": listing_attr(&self.username, page, total_pages),
+                }})
+            }
+            "user.getInfo" => {
+                if !user_matches("user") {
+                    return api_error(6, "User not found");
+                }
+                let distinct = |key: fn(&FakeScrobble) -> (String, String)| {
+                    count_by(self.scrobbles.iter().map(key)).len()
+                };
+                serde_json::json!({ "user": {
+                    "name": self.username,
+                    "url": format!("{}/user/{}", self.base_url, self.username),
+                    "playcount": self.scrobbles.len().to_string(),
+                    "artist_count": count_by(self.scrobbles.iter().map(|s| &s.artist)).len().to_string(),
+                    "album_count": distinct(|s| (s.album_artist.clone(), s.album.clone())).to_string(),
+                    "track_count": distinct(|s| (s.artist.clone(), s.track.clone())).to_string(),
+                    "subscriber": "0",
+                }})
+            }
+            "user.getTopArtists" | "user.getTopTracks" | "user.getTopAlbums" => {
+                if !user_matches("user") {
+                    return api_error(6, "User not found");
+                }
+                let range = match field("period").as_str() {
+                    "" | "overall" => LibraryDateRange::AllTime,
+                    "7day" => LibraryDateRange::Preset(DatePreset::Last7Days),
+                    "1month" => LibraryDateRange::Preset(DatePreset::Last30Days),
+                    "3month" => LibraryDateRange::Preset(DatePreset::Last90Days),
+                    "6month" => LibraryDateRange::Preset(DatePreset::Last180Days),
+                    "12month" => LibraryDateRange::Preset(DatePreset::Last365Days),
+                    _ => return api_error(6, "Invalid period"),
+                };
+                let in_range = self
+                    .scrobbles
+                    .iter()
+                    .filter(|s| range.contains_timestamp(s.timestamp));
+                let (list, item, counts) = match method.as_str() {
+                    "user.getTopArtists" => (
+                        "topartists",
+                        "artist",
+                        count_by(in_range.map(|s| (s.artist.clone(), String::new()))),
+                    ),
+                    "user.getTopTracks" => (
+                        "toptracks",
+                        "track",
+                        count_by(in_range.map(|s| (s.artist.clone(), s.track.clone()))),
+                    ),
+                    _ => (
+                        "topalbums",
+                        "album",
+                        count_by(
+                            in_range
+                                .filter(|s| !s.album.is_empty())
+                                .map(|s| (s.album_artist.clone(), s.album.clone())),
+                        ),
+                    ),
+                };
+                let (rows, total_pages) = paginate(&counts, page, limit);
+                let items: Vec<_> = rows
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ((artist, name), count))| {
+                        let rank = ((page - 1) * limit + i + 1).to_string();
+                        if item == "artist" {
+                            serde_json::json!({
+                                "name": artist, "mbid": "", "playcount": count.to_string(),
+                                "@attr": { "rank": rank },
+                            })
+                        } else {
+                            serde_json::json!({
+                                "name": name, "mbid": "", "playcount": count.to_string(),
+                                "artist": { "name": artist, "mbid": "" },
+                                "@attr": { "rank": rank },
+                            })
+                        }
+                    })
+                    .collect();
+                serde_json::json!({ list: {
+                    item: items,
+                    "@attr": listing_attr(&self.username, page, total_pages),
+                }})
+            }
+            "user.getLovedTracks" => {
+                if !user_matches("user") {
+                    return api_error(6, "User not found");
+                }
+                let loved: Vec<&(String, String)> = self.loved.iter().rev().collect();
+                let (rows, total_pages) = paginate(&loved, page, limit);
+                let items: Vec<_> = rows
+                    .iter()
+                    .map(|(artist, track)| {
+                        serde_json::json!({
+                            "name": track, "mbid": "", "artist": { "name": artist, "mbid": "" },
+                        })
+                    })
+                    .collect();
+                serde_json::json!({ "lovedtracks": {
+                    "track": items,
+                    "@attr": listing_attr(&self.username, page, total_pages),
+                }})
+            }
+            "track.getInfo" => {
+                let (artist, track) = (field("artist"), field("track"));
+                let plays: Vec<&FakeScrobble> = self
+                    .scrobbles
+                    .iter()
+                    .filter(|s| s.artist == artist && s.track == track)
+                    .collect();
+                let Some(first) = plays.first() else {
+                    return api_error(6, "Track not found");
+                };
+                let mut info = serde_json::json!({
+                    "name": track,
+                    "artist": { "name": artist, "mbid": "" },
+                    "listeners": "1",
+                    "playcount": plays.len().to_string(),
+                });
+                if !first.album.is_empty() {
+                    info["album"] = serde_json::json!({ "title": first.album, "mbid": "" });
+                }
+                if user_matches("username") {
+                    info["userplaycount"] = plays.len().to_string().into();
+                    info["userloved"] = u8::from(self.is_loved(&artist, &track)).to_string().into();
+                }
+                serde_json::json!({ "track": info })
+            }
+            "album.getInfo" => {
+                let (artist, album) = (field("artist"), field("album"));
+                let plays: Vec<&FakeScrobble> = self
+                    .scrobbles
+                    .iter()
+                    .filter(|s| s.album_artist == artist && s.album == album)
+                    .collect();
+                if plays.is_empty() {
+                    return api_error(6, "Album not found");
+                }
+                let mut tracks: Vec<(&str, &str)> = Vec::new();
+                for s in &plays {
+                    if !tracks.contains(&(s.artist.as_str(), s.track.as_str())) {
+                        tracks.push((&s.artist, &s.track));
+                    }
+                }
+                let tracks: Vec<_> = tracks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (track_artist, track))| {
+                        serde_json::json!({
+                            "name": track,
+                            "artist": { "name": track_artist, "mbid": "" },
+                            "@attr": { "rank": i + 1 },
+                        })
+                    })
+                    .collect();
+                let mut info = serde_json::json!({
+                    "name": album,
+                    "artist": artist,
+                    "listeners": "1",
+                    "playcount": plays.len().to_string(),
+                    "tracks": { "track": tracks },
+                });
+                if user_matches("username") {
+                    info["userplaycount"] = plays.len().to_string().into();
+                }
+                serde_json::json!({ "album": info })
+            }
+            "artist.getInfo" => {
+                let artist = field("artist");
+                let plays = self.scrobbles.iter().filter(|s| s.artist == artist).count();
+                if plays == 0 {
+                    return api_error(6, "The artist you supplied could not be found");
+                }
+                let mut stats = serde_json::json!({
+                    "listeners": "1",
+                    "playcount": plays.to_string(),
+                });
+                if user_matches("username") {
+                    stats["userplaycount"] = plays.to_string().into();
+                }
+                serde_json::json!({ "artist": { "name": artist, "stats": stats } })
+            }
+            // Corrects names that differ from a library artist only in case.
+            "artist.getCorrection" => {
+                let artist = field("artist");
+                let correction = self
+                    .scrobbles
+                    .iter()
+                    .map(|s| &s.artist)
+                    .find(|name| **name != artist && name.eq_ignore_ascii_case(&artist));
+                match correction {
+                    Some(name) => serde_json::json!({ "corrections": { "correction": {
+                        "artist": { "name": name, "mbid": "" },
+                        "@attr": { "index": "0" },
+                    }}}),
+                    None => serde_json::json!({ "corrections": "\n            " }),
+                }
+            }
+            _ => {
+                return api_error(
+                    3,
+                    "Invalid Method - No method with that name in this package",
+                )
+            }
+        };
+        json_response(StatusCode::Ok, &body.to_string())
+    }
+}
+
+/// The `@attr` block of a paginated API listing.
+fn listing_attr(user: &str, page: usize, total_pages: usize) -> serde_json::Value {
+    serde_json::json!({
+        "user": user,
+        "page": page.to_string(),
+        "totalPages": total_pages.to_string(),
+    })
+}
+
+fn rate_limit_response(rate_limit: &RateLimitResponse) -> Response {
+    let (status, retry_after) = match rate_limit {
+        RateLimitResponse::ApiRateLimitExceeded => {
+            return api_error(29, "Rate limit exceeded");
+        }
+        RateLimitResponse::TooManyRequests { retry_after } => (429, *retry_after),
+        RateLimitResponse::ServiceUnavailable { retry_after } => (503, *retry_after),
+        RateLimitResponse::Interstitial { status } => (*status, None),
+    };
+    let status = StatusCode::try_from(status).unwrap_or(StatusCode::ServiceUnavailable);
+    let mut response = html_response(status, RATE_LIMIT_BODY.to_string());
+    if let Some(retry_after) = retry_after {
+        let _ = response.insert_header("retry-after", retry_after.to_string());
+    }
+    response
+}
+
+fn api_error(code: u32, message: &str) -> Response {
+    json_response(
+        StatusCode::Ok,
+        &serde_json::json!({ "error": code, "message": message }).to_string(),
+    )
+}
+
+fn edit_error(message: &str) -> Response {
+    html_response(
+        StatusCode::Ok,
+        format!(
+            r#"<div class="alert alert-danger">{}</div>"#,
+            escape(message)
+        ),
+    )
+}
+
+fn bad_gateway() -> Response {
+    html_response(
+        StatusCode::BadGateway,
+        "<html><body><h1>502 Bad Gateway</h1></body></html>".to_string(),
+    )
+}
+
+fn not_found() -> Response {
+    html_response(StatusCode::NotFound, page_shell("Page Not Found", "404"))
+}
+
+fn html_response(status: StatusCode, body: String) -> Response {
+    let mut response = Response::new(status);
+    let _ = response.insert_header("content-type", "text/html; charset=utf-8");
+    response.set_body(body);
+    response
+}
+
+fn json_response(status: StatusCode, body: &str) -> Response {
+    let mut response = Response::new(status);
+    let _ = response.insert_header("content-type", "application/json");
+    response.set_body(body.to_string());
+    response
+}
+
+fn page_shell(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><title>{} | Last.fm</title></head><body>{body}</body></html>",
+        escape(title)
+    )
+}
+
+/// Pagination markup matching both `.pagination-list` and `.pagination .pagination-next`.
+fn pagination(page: usize, total_pages: usize) -> String {
+    if total_pages <= 1 {
+        return String::new();
+    }
+    let pages: String = (1..=total_pages)
+        .map(|p| format!(r#"<li class="pagination-page"><a href="?page={p}">{p}</a></li>"#))
+        .collect();
+    let next = if page < total_pages {
+        format!(
+            r#"<li class="pagination-next"><a href="?page={}">Next page</a></li>"#,
+            page + 1
+        )
+    } else {
+        String::new()
+    };
+    format!(r#"<nav class="pagination"><ul class="pagination-list">{pages}{next}</ul></nav>"#)
+}
+
+fn paginate<T>(items: &[T], page: usize, page_size: usize) -> (&[T], usize) {
+    let total_pages = items.len().div_ceil(page_size).max(1);
+    let start = ((page - 1) * page_size).min(items.len());
+    let end = (start + page_size).min(items.len());
+    (&items[start..end], total_pages)
+}
+
+/// Count occurrences, ordered by count (highest first) then name, like library listings.
+fn count_by<K: Ord>(names: impl Iterator<Item = K>) -> Vec<(K, usize)> {
+    let mut counts: BTreeMap<K, usize> = BTreeMap::new();
+    for name in names {
+        *counts.entry(name).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// The library date filter selected by a page's query parameters.
+fn library_range(query: &HashMap<String, String>) -> LibraryDateRange {
+    let preset = match query.get("date_preset").map(String::as_str) {
+        Some("LAST_7_DAYS") => Some(DatePreset::Last7Days),
+        Some("LAST_30_DAYS") => Some(DatePreset::Last30Days),
+        Some("LAST_90_DAYS") => Some(DatePreset::Last90Days),
+        Some("LAST_180_DAYS") => Some(DatePreset::Last180Days),
+        Some("LAST_365_DAYS") => Some(DatePreset::Last365Days),
+        _ => None,
+    };
+    let date = |name: &str| {
+        query
+            .get(name)
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    };
+    match (preset, date("from"), date("to")) {
+        (Some(preset), _, _) => LibraryDateRange::Preset(preset),
+        (None, Some(from), Some(to)) => LibraryDateRange::between(from, to),
+        _ => LibraryDateRange::AllTime,
+    }
+}
+
+fn decode_segment(segment: &str) -> String {
+    urlencoding::decode(segment)
+        .map(|s| s.into_owned())
+        .unwrap_or_else(|_| segment.to_string())
+}
+
+fn encode_path(segment: &str) -> String {
+    urlencoding::encode(segment).into_owned()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
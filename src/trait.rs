@@ -1,7 +1,8 @@
 use crate::iterator::AsyncPaginatedIterator;
 use crate::types::{
     Album, Artist, ArtistPage, ClientEvent, ClientEventReceiver, EditResponse, ExactScrobbleEdit,
-    LastFmEditSession, RateLimitState, RateLimitStateWatcher, ScrobbleEdit, Track,
+    LastFmEditSession, LibraryDateRange, LibrarySort, RateLimitState, RateLimitStateWatcher,
+    ScrobbleEdit, Track,
 };
 use crate::Result;
 use async_trait::async_trait;
@@ -33,6 +34,50 @@ pub trait LastFmBaseClient {
     /// Get a page of albums from the user's library for the specified artist.
     async fn get_artist_albums_page(&self, artist: &str, page: u32) -> Result<crate::AlbumPage>;
 
+    /// Get a page of the user's loved tracks, most recently loved first.
+    async fn get_loved_tracks_page(&self, page: u32) -> Result<crate::TrackPage>;
+
+    /// Get a page of every track in the user's library, across all artists.
+    async fn get_library_tracks_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<crate::TrackPage>;
+
+    /// Get a page of every album in the user's library, across all artists.
+    async fn get_library_albums_page(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+        page: u32,
+    ) -> Result<crate::AlbumPage>;
+
+    /// Get a page of artists scrobbled within `range`.
+    ///
+    /// Play counts only count scrobbles inside the range.
+    async fn get_artists_page_in_range(
+        &self,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<ArtistPage>;
+
+    /// Get a page of the specified artist's tracks scrobbled within `range`.
+    async fn get_artist_tracks_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<crate::TrackPage>;
+
+    /// Get a page of the specified artist's albums scrobbled within `range`.
+    async fn get_artist_albums_page_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+        page: u32,
+    ) -> Result<crate::AlbumPage>;
+
     /// Get a page of tracks from a specific album in the user's library.
     async fn get_album_tracks_page(
         &self,
@@ -44,6 +89,16 @@ pub trait LastFmBaseClient {
     /// Get a page of tracks from the user's recent listening history.
     async fn get_recent_tracks_page(&self, page: u32) -> Result<crate::TrackPage>;
 
+    /// Get a page of the individual, timestamped scrobbles of one track (newest first).
+    ///
+    /// A track the user never scrobbled yields an empty page.
+    async fn get_track_scrobbles_page(
+        &self,
+        artist: &str,
+        track: &str,
+        page: u32,
+    ) -> Result<crate::TrackPage>;
+
     // =============================================================================
     // SEARCH PAGES - Single page search results
     // =============================================================================
@@ -387,6 +442,16 @@ pub trait LastFmEditClient: LastFmBaseClient {
         timestamp: u64,
     ) -> Result<bool>;
 
+    /// Love a track, as the heart button on Last.fm does.
+    ///
+    /// Returns `true` if Last.fm accepted the request. Paced and retried like edits.
+    async fn love_track(&self, artist_name: &str, track_name: &str) -> Result<bool>;
+
+    /// Unlove a track.
+    ///
+    /// Returns `true` if Last.fm accepted the request.
+    async fn unlove_track(&self, artist_name: &str, track_name: &str) -> Result<bool>;
+
     /// Create an incremental discovery iterator for scrobble editing.
     ///
     /// This returns the appropriate discovery iterator based on what fields are specified
@@ -423,6 +488,55 @@ pub trait LastFmEditClient: LastFmBaseClient {
     /// Create an iterator for browsing an artist's albums from the user's library.
     fn artist_albums(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Album>>;
 
+    /// Create an iterator over every scrobble of one track, newest first.
+    ///
+    /// Unlike the aggregated listings, each item is a single scrobble with its timestamp
+    /// and album details, read from the track's library page
+    /// (`/user/{username}/library/music/{artist}/_/{track}`).
+    fn track_scrobbles(&self, artist: &str, track: &str) -> Box<dyn AsyncPaginatedIterator<Track>>;
+
+    /// Create an iterator over the user's loved tracks, most recently loved first.
+    fn loved_tracks(&self) -> Box<dyn AsyncPaginatedIterator<Track>>;
+
+    /// Create an iterator over every track in the user's library.
+    ///
+    /// Walks `/user/{username}/library/tracks` directly, which takes far fewer requests than
+    /// going through [`artists`](Self::artists) and each artist's tracks. The tracks come
+    /// without album information.
+    fn library_tracks(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    ) -> Box<dyn AsyncPaginatedIterator<Track>>;
+
+    /// Create an iterator over every album in the user's library
+    /// (`/user/{username}/library/albums`).
+    fn library_albums(
+        &self,
+        range: LibraryDateRange,
+        sort: LibrarySort,
+    ) -> Box<dyn AsyncPaginatedIterator<Album>>;
+
+    /// Create an iterator for browsing the artists scrobbled within `range`.
+    fn artists_in_range(&self, range: LibraryDateRange) -> Box<dyn AsyncPaginatedIterator<Artist>>;
+
+    /// Create an iterator for browsing an artist's tracks scrobbled within `range`.
+    ///
+    /// Uses the same paginated `+tracks` endpoint as [`artist_tracks_direct`](Self::artist_tracks_direct),
+    /// so the tracks come without album information.
+    fn artist_tracks_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+    ) -> Box<dyn AsyncPaginatedIterator<Track>>;
+
+    /// Create an iterator for browsing an artist's albums scrobbled within `range`.
+    fn artist_albums_in_range(
+        &self,
+        artist: &str,
+        range: LibraryDateRange,
+    ) -> Box<dyn AsyncPaginatedIterator<Album>>;
+
     /// Create an iterator for browsing tracks from a specific album.
     fn album_tracks(
         &self,
@@ -638,6 +752,36 @@ mockall::mock! {
         async fn get_artists_page(&self, page: u32) -> Result<ArtistPage>;
         async fn get_artist_tracks_page(&self, artist: &str, page: u32) -> Result<crate::TrackPage>;
         async fn get_artist_albums_page(&self, artist: &str, page: u32) -> Result<crate::AlbumPage>;
+        async fn get_loved_tracks_page(&self, page: u32) -> Result<crate::TrackPage>;
+        async fn get_library_tracks_page(
+            &self,
+            range: LibraryDateRange,
+            sort: LibrarySort,
+            page: u32,
+        ) -> Result<crate::TrackPage>;
+        async fn get_library_albums_page(
+            &self,
+            range: LibraryDateRange,
+            sort: LibrarySort,
+            page: u32,
+        ) -> Result<crate::AlbumPage>;
+        async fn get_artists_page_in_range(
+            &self,
+            range: LibraryDateRange,
+            page: u32,
+        ) -> Result<ArtistPage>;
+        async fn get_artist_tracks_page_in_range(
+            &self,
+            artist: &str,
+            range: LibraryDateRange,
+            page: u32,
+        ) -> Result<crate::TrackPage>;
+        async fn get_artist_albums_page_in_range(
+            &self,
+            artist: &str,
+            range: LibraryDateRange,
+            page: u32,
+        ) -> Result<crate::AlbumPage>;
         async fn get_album_tracks_page(
             &self,
             album_name: &str,
@@ -645,6 +789,12 @@ mockall::mock! {
             page: u32,
         ) -> Result<crate::TrackPage>;
         async fn get_recent_tracks_page(&self, page: u32) -> Result<crate::TrackPage>;
+        async fn get_track_scrobbles_page(
+            &self,
+            artist: &str,
+            track: &str,
+            page: u32,
+        ) -> Result<crate::TrackPage>;
         async fn search_tracks_page(&self, query: &str, page: u32) -> Result<crate::TrackPage>;
         async fn search_albums_page(&self, query: &str, page: u32) -> Result<crate::AlbumPage>;
         async fn search_artists_page(&self, query: &str, page: u32) -> Result<crate::ArtistPage>;
@@ -678,6 +828,8 @@ mockall::mock! {
             track_name: &str,
             timestamp: u64,
         ) -> Result<bool>;
+        async fn love_track(&self, artist_name: &str, track_name: &str) -> Result<bool>;
+        async fn unlove_track(&self, artist_name: &str, track_name: &str) -> Result<bool>;
         async fn get_scrobble_edit_variations(
             &self,
             track_name: &str,
@@ -691,6 +843,29 @@ mockall::mock! {
         fn artist_tracks(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Track>>;
         fn artist_tracks_direct(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Track>>;
         fn artist_albums(&self, artist: &str) -> Box<dyn AsyncPaginatedIterator<Album>>;
+        fn track_scrobbles(&self, artist: &str, track: &str) -> Box<dyn AsyncPaginatedIterator<Track>>;
+        fn loved_tracks(&self) -> Box<dyn AsyncPaginatedIterator<Track>>;
+        fn library_tracks(
+            &self,
+            range: LibraryDateRange,
+            sort: LibrarySort,
+        ) -> Box<dyn AsyncPaginatedIterator<Track>>;
+        fn library_albums(
+            &self,
+            range: LibraryDateRange,
+            sort: LibrarySort,
+        ) -> Box<dyn AsyncPaginatedIterator<Album>>;
+        fn artists_in_range(&self, range: LibraryDateRange) -> Box<dyn AsyncPaginatedIterator<Artist>>;
+        fn artist_tracks_in_range(
+            &self,
+            artist: &str,
+            range: LibraryDateRange,
+        ) -> Box<dyn AsyncPaginatedIterator<Track>>;
+        fn artist_albums_in_range(
+            &self,
+            artist: &str,
+            range: LibraryDateRange,
+        ) -> Box<dyn AsyncPaginatedIterator<Album>>;
         fn album_tracks(
             &self,
             album_name: &str,
@@ -4,7 +4,7 @@
 //! including track and album metadata, edit operations, error types, session state,
 //! configuration, and event handling.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -19,7 +19,7 @@ use tokio::sync::{broadcast, watch};
 ///
 /// This structure contains track information as parsed from Last.fm pages,
 /// including play count and optional timestamp data for scrobbles.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Track {
     /// The track name/title
     pub name: String,
@@ -46,6 +46,27 @@ pub struct Track {
     /// recent-tracks API does not supply album artist at all, so API-sourced tracks always
     /// carry `None`.
     pub album_artist: Option<String>,
+    /// Whether the user has loved this track, when the source shows it.
+    ///
+    /// Scraped scrobble and track listings show a heart on every row, and the
+    /// recent-tracks API reports it for `extended=1` requests; `None` means the source
+    /// did not say.
+    #[serde(default)]
+    pub loved: Option<bool>,
+    /// MusicBrainz ID of the track, when the source supplies one.
+    ///
+    /// Only the JSON API reports MBIDs; scraped pages leave these `None`.
+    #[serde(default)]
+    pub track_mbid: Option<String>,
+    /// MusicBrainz ID of the artist, when the source supplies one.
+    #[serde(default)]
+    pub artist_mbid: Option<String>,
+    /// MusicBrainz ID of the album, when the source supplies one.
+    #[serde(default)]
+    pub album_mbid: Option<String>,
+    /// URL of the largest cover image the source supplies.
+    #[serde(default)]
+    pub image_url: Option<String>,
 }
 
 impl fmt::Display for Track {
@@ -186,6 +207,166 @@ impl Artist {
     }
 }
 
+// ================================================================================================
+// LIBRARY LISTING OPTIONS
+// ================================================================================================
+
+/// Rolling windows offered by the Last.fm library pages (`date_preset=`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum DatePreset {
+    Last7Days,
+    Last30Days,
+    Last90Days,
+    Last180Days,
+    Last365Days,
+}
+
+impl DatePreset {
+    /// The value Last.fm expects for the `date_preset` query parameter.
+    #[must_use]
+    pub fn as_param(self) -> &'static str {
+        match self {
+            DatePreset::Last7Days => "LAST_7_DAYS",
+            DatePreset::Last30Days => "LAST_30_DAYS",
+            DatePreset::Last90Days => "LAST_90_DAYS",
+            DatePreset::Last180Days => "LAST_180_DAYS",
+            DatePreset::Last365Days => "LAST_365_DAYS",
+        }
+    }
+
+    /// Length of the window in days.
+    #[must_use]
+    pub fn days(self) -> i64 {
+        match self {
+            DatePreset::Last7Days => 7,
+            DatePreset::Last30Days => 30,
+            DatePreset::Last90Days => 90,
+            DatePreset::Last180Days => 180,
+            DatePreset::Last365Days => 365,
+        }
+    }
+}
+
+/// The time window a library listing (artists, an artist's tracks or albums) covers.
+///
+/// Play counts on the returned pages only count scrobbles inside the window, and entries
+/// without any scrobble in it are left out.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum LibraryDateRange {
+    /// The whole listening history (what the plain page methods return)
+    #[default]
+    AllTime,
+    /// One of Last.fm's rolling windows ending today
+    Preset(DatePreset),
+    /// Calendar days from `from` through `to`, both inclusive
+    Between { from: NaiveDate, to: NaiveDate },
+}
+
+impl LibraryDateRange {
+    /// Calendar days from `from` through `to`, both inclusive.
+    #[must_use]
+    pub fn between(from: NaiveDate, to: NaiveDate) -> Self {
+        LibraryDateRange::Between { from, to }
+    }
+
+    /// The calendar year `year`, or `None` for a year chrono cannot represent.
+    #[must_use]
+    pub fn year(year: i32) -> Option<Self> {
+        Some(Self::between(
+            NaiveDate::from_ymd_opt(year, 1, 1)?,
+            NaiveDate::from_ymd_opt(year, 12, 31)?,
+        ))
+    }
+
+    /// The days (in UTC) containing the Unix timestamps `from` and `to`.
+    ///
+    /// Last.fm only filters whole days, so this is the tightest range covering both
+    /// timestamps; use [`contains_timestamp`](Self::contains_timestamp) to trim it further.
+    #[must_use]
+    pub fn from_timestamps(from: u64, to: u64) -> Option<Self> {
+        let day = |ts: u64| {
+            DateTime::from_timestamp(i64::try_from(ts).ok()?, 0).map(|dt| dt.date_naive())
+        };
+        Some(Self::between(day(from)?, day(to)?))
+    }
+
+    #[must_use]
+    pub fn is_all_time(&self) -> bool {
+        matches!(self, LibraryDateRange::AllTime)
+    }
+
+    /// Query parameters selecting this range, e.g. `from=2019-01-01&to=2019-12-31`.
+    ///
+    /// Empty for [`AllTime`](Self::AllTime).
+    #[must_use]
+    pub fn query_params(&self) -> String {
+        match self {
+            LibraryDateRange::AllTime => String::new(),
+            LibraryDateRange::Preset(preset) => format!("date_preset={}", preset.as_param()),
+            LibraryDateRange::Between { from, to } => format!(
+                "from={}&to={}",
+                from.format("%Y-%m-%d"),
+                to.format("%Y-%m-%d")
+            ),
+        }
+    }
+
+    /// Whether a scrobble at Unix timestamp `timestamp` falls inside the range.
+    ///
+    /// Days are taken in UTC; presets are measured back from now.
+    #[must_use]
+    pub fn contains_timestamp(&self, timestamp: u64) -> bool {
+        let Some(at) = i64::try_from(timestamp)
+            .ok()
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        else {
+            return false;
+        };
+        match self {
+            LibraryDateRange::AllTime => true,
+            LibraryDateRange::Preset(preset) => {
+                at > Utc::now() - chrono::Duration::days(preset.days())
+            }
+            LibraryDateRange::Between { from, to } => (*from..=*to).contains(&at.date_naive()),
+        }
+    }
+}
+
+impl fmt::Display for LibraryDateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryDateRange::AllTime => f.write_str("all time"),
+            LibraryDateRange::Preset(preset) => write!(f, "last {} days", preset.days()),
+            LibraryDateRange::Between { from, to } => write!(f, "{from} to {to}"),
+        }
+    }
+}
+
+/// Order of the whole-library track and album listings.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum LibrarySort {
+    /// Most scrobbled first (Last.fm's default order)
+    #[default]
+    Scrobbles,
+    /// Alphabetical by name
+    Name,
+}
+
+impl LibrarySort {
+    /// The value Last.fm expects for the `order` query parameter.
+    #[must_use]
+    pub fn as_param(self) -> &'static str {
+        match self {
+            LibrarySort::Scrobbles => "scrobbles",
+            LibrarySort::Name => "name",
+        }
+    }
+}
+
 // ================================================================================================
 // EDIT OPERATIONS
 // ================================================================================================
@@ -304,6 +485,49 @@ pub struct SingleEditResponse {
     pub album_info: Option<String>,
     /// The exact scrobble edit that was performed
     pub exact_scrobble_edit: ExactScrobbleEdit,
+    /// Outcome of re-fetching the scrobble after the edit.
+    ///
+    /// `None` unless [`EditVerificationConfig::enabled`] is set and the edit response
+    /// reported success.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<EditVerificationStatus>,
+    /// Classified reason for the failure (`None` when the edit succeeded or the failure
+    /// could not be attributed to the edit response)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_kind: Option<EditFailureKind>,
+}
+
+/// Result of checking an edit against the scrobble as Last.fm shows it afterwards.
+///
+/// The edit POST response is only a weak signal of success, so with verification
+/// enabled the client re-fetches the scrobble and compares its metadata with the edit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum EditVerificationStatus {
+    /// The scrobble shows the new metadata and no longer shows the original
+    Verified,
+    /// The scrobble could not be found or fetched, so the edit could not be checked
+    Unverified {
+        /// Why the check was inconclusive
+        reason: String,
+    },
+    /// The scrobble was found but does not reflect the edit
+    Contradicted {
+        /// What the scrobble showed instead
+        reason: String,
+    },
+}
+
+impl EditVerificationStatus {
+    /// Whether the scrobble was confirmed to show the new metadata.
+    pub fn is_verified(&self) -> bool {
+        matches!(self, EditVerificationStatus::Verified)
+    }
+
+    /// Whether the scrobble was found without the new metadata.
+    pub fn is_contradicted(&self) -> bool {
+        matches!(self, EditVerificationStatus::Contradicted { .. })
+    }
 }
 
 /// Response from a scrobble edit operation that may affect multiple album variations.
@@ -608,6 +832,38 @@ impl ScrobbleEdit {
             true, // Edit all instances by default for album changes
         )
     }
+    /// Apply this edit's new values to `discovered`, one of the scrobbles it matches.
+    pub fn apply_to(&self, discovered: &ExactScrobbleEdit) -> ExactScrobbleEdit {
+        let mut exact_edit = discovered.clone();
+        if let Some(new_track_name) = &self.track_name {
+            exact_edit.track_name = new_track_name.clone();
+        }
+        if let Some(new_album_name) = &self.album_name {
+            exact_edit.album_name = new_album_name.clone();
+        }
+        exact_edit.artist_name = self.artist_name.clone();
+        if let Some(new_album_artist_name) = &self.album_artist_name {
+            exact_edit.album_artist_name = new_album_artist_name.clone();
+        }
+        exact_edit.edit_all = self.edit_all;
+        exact_edit
+    }
+
+    /// The error for an edit whose discovery found no scrobbles.
+    pub(crate) fn no_scrobbles_error(&self) -> LastFmError {
+        let context = match (&self.track_name_original, &self.album_name_original) {
+            (Some(track_name), _) => {
+                format!("track '{}' by '{}'", track_name, self.artist_name_original)
+            }
+            (None, Some(album_name)) => {
+                format!("album '{}' by '{}'", album_name, self.artist_name_original)
+            }
+            (None, None) => format!("artist '{}'", self.artist_name_original),
+        };
+        LastFmError::Parse(format!(
+            "No scrobbles found for {context}. Make sure the names are correct and that you have scrobbled recently."
+        ))
+    }
 }
 
 impl ExactScrobbleEdit {
@@ -692,6 +948,25 @@ impl ExactScrobbleEdit {
             self.edit_all,
         )
     }
+
+    /// Build the edit that undoes this one.
+    ///
+    /// Original and new fields are swapped; the timestamp and `edit_all` flag are kept,
+    /// so the inverse targets the same scrobble(s) under their edited metadata.
+    pub fn inverse(&self) -> Self {
+        Self {
+            track_name_original: self.track_name.clone(),
+            album_name_original: self.album_name.clone(),
+            artist_name_original: self.artist_name.clone(),
+            album_artist_name_original: self.album_artist_name.clone(),
+            track_name: self.track_name_original.clone(),
+            album_name: self.album_name_original.clone(),
+            artist_name: self.artist_name_original.clone(),
+            album_artist_name: self.album_artist_name_original.clone(),
+            timestamp: self.timestamp,
+            edit_all: self.edit_all,
+        }
+    }
 }
 
 impl EditResponse {
@@ -708,10 +983,19 @@ impl EditResponse {
                 message,
                 album_info,
                 exact_scrobble_edit,
+                verification: None,
+                failure_kind: None,
             }],
         }
     }
 
+    /// Create a new failed EditResponse from the error that ended the edit.
+    pub fn from_error(error: &LastFmError, exact_scrobble_edit: ExactScrobbleEdit) -> Self {
+        let mut response = Self::single(false, Some(error.to_string()), None, exact_scrobble_edit);
+        response.individual_results[0].failure_kind = error.edit_failure_kind();
+        response
+    }
+
     /// Create a new EditResponse from multiple results.
     pub fn from_results(results: Vec<SingleEditResponse>) -> Self {
         Self {
@@ -739,6 +1023,14 @@ impl EditResponse {
         self.individual_results.iter().filter(|r| r.success).count()
     }
 
+    /// Get the number of edits whose verification contradicted the reported success.
+    pub fn contradicted_edits(&self) -> usize {
+        self.individual_results
+            .iter()
+            .filter(|r| r.verification.as_ref().is_some_and(|v| v.is_contradicted()))
+            .count()
+    }
+
     /// Get the number of failed edit operations.
     pub fn failed_edits(&self) -> usize {
         self.individual_results
@@ -821,6 +1113,60 @@ impl EditResponse {
 // ERROR TYPES
 // ================================================================================================
 
+/// Why Last.fm rejected a scrobble edit.
+///
+/// Parsed from the edit response by
+/// [`classify_edit_failure`](crate::edit_analysis::classify_edit_failure) and surfaced on
+/// [`SingleEditResponse::failure_kind`] and [`LastFmError::EditRejected`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EditFailureKind {
+    /// No scrobble with the original metadata exists at the timestamp
+    ScrobbleNotFound,
+    /// A submitted field was rejected (empty, malformed or too long)
+    InvalidField,
+    /// The CSRF token or session was rejected
+    CsrfExpired,
+    /// The account has used up its edit allowance
+    QuotaReached,
+    /// The edit was refused because of rate limiting
+    RateLimited,
+    /// Last.fm answered with a server error
+    ServerError,
+    /// The response did not match any known failure
+    Unknown,
+}
+
+impl EditFailureKind {
+    /// Whether repeating the same edit can succeed.
+    ///
+    /// Rate limits are transient, and every attempt fetches a fresh CSRF token; the other
+    /// failures repeat until the edit itself changes. A server error is not retryable
+    /// either: the edit may have been applied before the server failed, so check the
+    /// scrobble before submitting it again.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            EditFailureKind::RateLimited | EditFailureKind::CsrfExpired
+        )
+    }
+}
+
+impl fmt::Display for EditFailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            EditFailureKind::ScrobbleNotFound => "scrobble not found",
+            EditFailureKind::InvalidField => "invalid field value",
+            EditFailureKind::CsrfExpired => "CSRF token expired",
+            EditFailureKind::QuotaReached => "edit quota reached",
+            EditFailureKind::RateLimited => "rate limited",
+            EditFailureKind::ServerError => "server error",
+            EditFailureKind::Unknown => "unknown failure",
+        };
+        f.write_str(description)
+    }
+}
+
 /// Error types for Last.fm operations.
 ///
 /// This enum covers all possible errors that can occur when interacting with Last.fm,
@@ -881,6 +1227,18 @@ pub enum LastFmError {
     #[error("Edit failed: {0}")]
     EditFailed(String),
 
+    /// Last.fm rejected a scrobble edit, with the classified reason.
+    ///
+    /// Check [`EditFailureKind::is_retryable`] (or [`LastFmError::is_retryable`]) before
+    /// resubmitting the edit.
+    #[error("Edit rejected ({kind}): {message}")]
+    EditRejected {
+        /// Classified reason for the rejection
+        kind: EditFailureKind,
+        /// Error text from the edit response
+        message: String,
+    },
+
     /// File system I/O errors.
     ///
     /// This can occur when saving debug responses or other file operations.
@@ -888,6 +1246,28 @@ pub enum LastFmError {
     Io(#[from] std::io::Error),
 }
 
+impl LastFmError {
+    /// The edit failure this error represents, if any.
+    ///
+    /// Besides [`LastFmError::EditRejected`], rate limits map to
+    /// [`EditFailureKind::RateLimited`] and a missing CSRF token to
+    /// [`EditFailureKind::CsrfExpired`].
+    pub fn edit_failure_kind(&self) -> Option<EditFailureKind> {
+        match self {
+            LastFmError::EditRejected { kind, .. } => Some(*kind),
+            LastFmError::RateLimit { .. } => Some(EditFailureKind::RateLimited),
+            LastFmError::CsrfNotFound => Some(EditFailureKind::CsrfExpired),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the operation can succeed, per [`EditFailureKind::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.edit_failure_kind()
+            .is_some_and(EditFailureKind::is_retryable)
+    }
+}
+
 // ================================================================================================
 // SESSION MANAGEMENT
 // ================================================================================================
@@ -1150,6 +1530,10 @@ pub struct ClientConfig {
     pub api_key: Option<String>,
     /// How to react when a rate limit is detected (block-and-retry vs. return an error)
     pub rate_limit_behavior: RateLimitBehavior,
+    /// Post-edit verification (disabled by default)
+    pub edit_verification: EditVerificationConfig,
+    /// Adaptive request pacing (disabled by default, in favour of the fixed operational delays)
+    pub adaptive_pacing: AdaptivePacingConfig,
 }
 
 impl ClientConfig {
@@ -1253,6 +1637,33 @@ impl ClientConfig {
         self
     }
 
+    /// Enable/disable re-fetching each edited scrobble to confirm the edit took effect
+    pub fn with_edit_verification(mut self, enabled: bool) -> Self {
+        self.edit_verification.enabled = enabled;
+        self
+    }
+
+    /// Set custom post-edit verification configuration
+    pub fn with_edit_verification_config(
+        mut self,
+        edit_verification: EditVerificationConfig,
+    ) -> Self {
+        self.edit_verification = edit_verification;
+        self
+    }
+
+    /// Enable/disable adaptive pacing with default tuning
+    pub fn with_adaptive_pacing(mut self, enabled: bool) -> Self {
+        self.adaptive_pacing.enabled = enabled;
+        self
+    }
+
+    /// Set custom adaptive pacing configuration
+    pub fn with_adaptive_pacing_config(mut self, adaptive_pacing: AdaptivePacingConfig) -> Self {
+        self.adaptive_pacing = adaptive_pacing;
+        self
+    }
+
     /// Set how the client reacts to detected rate limits.
     ///
     /// With [`RateLimitBehavior::ReturnError`] the client never sleeps or retries internally
@@ -1264,6 +1675,109 @@ impl ClientConfig {
     }
 }
 
+/// Kind of traffic paced by a separate adaptive budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TrafficClass {
+    /// Page fetches (library, search, edit forms)
+    Get,
+    /// Scrobble edit submissions
+    Edit,
+    /// Scrobble delete submissions
+    Delete,
+}
+
+/// Configuration for adaptive request pacing
+///
+/// Instead of waiting a fixed delay, the client spaces requests of each [`TrafficClass`]
+/// by a delay it tunes as it goes: every successful response shortens the delay by
+/// `speedup_step_ms`, and every detected rate limit multiplies it by `backoff_factor`
+/// (to at least `backoff_floor_ms`). The [`OperationalDelayConfig`] values are the starting
+/// delays. Changes are reported as [`ClientEvent::PacingAdjusted`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptivePacingConfig {
+    /// Whether to pace adaptively at all
+    pub enabled: bool,
+    /// Shortest delay between requests of one class (in milliseconds)
+    pub min_delay_ms: u64,
+    /// Longest delay between requests of one class (in milliseconds)
+    pub max_delay_ms: u64,
+    /// How much each successful request shortens the delay (in milliseconds)
+    pub speedup_step_ms: u64,
+    /// Factor the delay is multiplied by on a rate limit
+    pub backoff_factor: u64,
+    /// Smallest delay after a rate limit (in milliseconds)
+    pub backoff_floor_ms: u64,
+}
+
+impl Default for AdaptivePacingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_delay_ms: 100,
+            max_delay_ms: 120_000,
+            speedup_step_ms: 100,
+            backoff_factor: 2,
+            backoff_floor_ms: 2_000,
+        }
+    }
+}
+
+impl AdaptivePacingConfig {
+    /// Create config with adaptive pacing enabled and default tuning
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Set the range the delay is kept within
+    pub fn with_delay_bounds(mut self, min_delay_ms: u64, max_delay_ms: u64) -> Self {
+        self.min_delay_ms = min_delay_ms;
+        self.max_delay_ms = max_delay_ms.max(min_delay_ms);
+        self
+    }
+}
+
+/// Configuration for post-edit verification
+///
+/// When enabled, every edit whose response looks successful is followed by a re-fetch
+/// of the edited scrobble: first the recent scrobbles around its timestamp, then the
+/// track's library page. The outcome is reported in
+/// [`SingleEditResponse::verification`] and as [`ClientEvent::EditVerified`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditVerificationConfig {
+    /// Whether to verify edits at all
+    pub enabled: bool,
+    /// Maximum number of recent scrobble pages (and track library pages) to search
+    pub max_pages: u32,
+}
+
+impl Default for EditVerificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_pages: 3,
+        }
+    }
+}
+
+impl EditVerificationConfig {
+    /// Create config with verification enabled and default limits
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of pages searched for the edited scrobble
+    pub fn with_max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+}
+
 /// Configuration for retry behavior
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RetryConfig {
@@ -1438,6 +1952,8 @@ pub enum RateLimitType {
     Http503,
     /// Rate limit patterns detected in response body
     ResponsePattern,
+    /// JSON API error 29 ("Rate limit exceeded")
+    ApiError,
 }
 
 /// Reason why the client is intentionally delaying.
@@ -1449,6 +1965,10 @@ pub enum DelayReason {
     OperationalEditDelay,
     /// Intentional pacing between multiple delete operations.
     OperationalDeleteDelay,
+    /// Spacing chosen by adaptive pacing for the given class of traffic.
+    AdaptivePacing(TrafficClass),
+    /// Waiting for a slot (or a cool-down) in the budget shared with other processes.
+    SharedRateLimit,
 }
 
 /// Event type to describe internal HTTP client activity
@@ -1513,6 +2033,64 @@ pub enum ClientEvent {
         /// Duration of the edit operation in milliseconds
         duration_ms: u64,
     },
+    /// An edit reported as successful was checked against the re-fetched scrobble
+    EditVerified {
+        /// The exact scrobble edit that was verified
+        edit: ExactScrobbleEdit,
+        /// What the re-fetched scrobble showed
+        status: EditVerificationStatus,
+        /// Duration of the verification in milliseconds
+        duration_ms: u64,
+    },
+    /// Adaptive pacing changed the delay between requests of one class
+    PacingAdjusted {
+        /// The class of requests whose delay changed
+        traffic: TrafficClass,
+        /// New delay between requests in milliseconds
+        delay_ms: u64,
+        /// The resulting maximum request rate
+        requests_per_minute: u64,
+        /// Whether the change was a backoff after a rate limit (otherwise a speed-up)
+        backoff: bool,
+    },
+    /// A parsed page did not look the way its parser expects; Last.fm may have changed
+    /// its markup
+    ParseDrift {
+        /// What the parser found on the page
+        report: crate::parse_health::ParseReport,
+    },
+    /// The session expired and the client logged in again with its credential provider
+    SessionRefreshed {
+        /// The account that was logged in again
+        username: String,
+        /// Why the previous session was considered expired
+        reason: String,
+        /// When the new session was obtained (seconds since Unix epoch)
+        refresh_timestamp: u64,
+    },
+    /// A library page was served from the response cache
+    CacheHit {
+        /// The page URL
+        url: String,
+        /// What kind of page it is
+        kind: crate::parse_health::PageKind,
+        /// How long ago the cached copy was fetched, in milliseconds
+        age_ms: u64,
+    },
+    /// A cacheable library page was missing from the response cache or had expired
+    CacheMiss {
+        /// The page URL
+        url: String,
+        /// What kind of page it is
+        kind: crate::parse_health::PageKind,
+    },
+    /// Cached pages were dropped because an edit or delete changed the library
+    CacheInvalidated {
+        /// The scrobbles that changed
+        change: crate::response_cache::LibraryChange,
+        /// How many cached pages were dropped
+        dropped: usize,
+    },
 }
 
 /// Type alias for the broadcast receiver
@@ -1723,4 +2301,23 @@ mod tests {
         assert_eq!(session.csrf_token, restored_session.csrf_token);
         assert_eq!(session.base_url, restored_session.base_url);
     }
+
+    #[test]
+    fn test_library_date_range_params() {
+        assert_eq!(LibraryDateRange::AllTime.query_params(), "");
+        assert_eq!(
+            LibraryDateRange::Preset(DatePreset::Last30Days).query_params(),
+            "date_preset=LAST_30_DAYS"
+        );
+
+        let year = LibraryDateRange::year(2019).unwrap();
+        assert_eq!(year.query_params(), "from=2019-01-01&to=2019-12-31");
+        // 2019-12-31T23:59:59Z and 2020-01-01T00:00:00Z
+        assert!(year.contains_timestamp(1_577_836_799));
+        assert!(!year.contains_timestamp(1_577_836_800));
+        assert_eq!(
+            LibraryDateRange::from_timestamps(1_546_300_800, 1_577_836_799),
+            Some(year)
+        );
+    }
 }
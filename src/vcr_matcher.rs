@@ -1,5 +1,6 @@
 use http_client::Request;
 use http_client_vcr::{RequestMatcher, SerializableRequest};
+use http_types::Url;
 use std::fmt::Debug;
 
 /// Last.fm Edit VCR matcher that handles authentication flows properly
@@ -9,6 +10,7 @@ pub struct LastFmEditVcrMatcher {
     match_method: bool,
     match_url: bool,
     match_body: bool,
+    ignored_query_params: Vec<String>,
 }
 
 impl LastFmEditVcrMatcher {
@@ -17,6 +19,7 @@ impl LastFmEditVcrMatcher {
             match_method: true,
             match_url: true,
             match_body: false,
+            ignored_query_params: Vec::new(),
         }
     }
 
@@ -24,6 +27,43 @@ impl LastFmEditVcrMatcher {
         self.match_body = match_body;
         self
     }
+
+    /// Leave these query parameters out when comparing URLs, so cassettes recorded before
+    /// a parameter was added still replay.
+    pub fn with_ignored_query_params<'a>(
+        mut self,
+        params: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.ignored_query_params = params.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    fn urls_match(&self, url: &str, recorded_url: &str) -> bool {
+        if url == recorded_url {
+            return true;
+        }
+        if self.ignored_query_params.is_empty() {
+            return false;
+        }
+        match (
+            self.without_ignored_params(url),
+            self.without_ignored_params(recorded_url),
+        ) {
+            (Some(url), Some(recorded_url)) => url == recorded_url,
+            _ => false,
+        }
+    }
+
+    fn without_ignored_params(&self, url: &str) -> Option<Url> {
+        let mut url = Url::parse(url).ok()?;
+        let kept: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(name, _)| !self.ignored_query_params.iter().any(|p| p == name))
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(kept);
+        Some(url)
+    }
 }
 
 impl RequestMatcher for LastFmEditVcrMatcher {
@@ -45,7 +85,7 @@ impl RequestMatcher for LastFmEditVcrMatcher {
             return false;
         }
 
-        if self.match_url && request.url().to_string() != recorded_request.url {
+        if self.match_url && !self.urls_match(request.url().as_str(), &recorded_request.url) {
             log::debug!(
                 "URL mismatch: {} != {}",
                 request.url(),
@@ -132,7 +172,7 @@ impl RequestMatcher for LastFmEditVcrMatcher {
             return false;
         }
 
-        if self.match_url && request.url != recorded_request.url {
+        if self.match_url && !self.urls_match(&request.url, &recorded_request.url) {
             return false;
         }
 
@@ -0,0 +1,249 @@
+//! Unsigned API read calls (`user.getRecentTracks`, `user.getInfo`, `user.getTop*`,
+//! `user.getLovedTracks`, `*.getInfo`, `artist.getCorrection`) against the in-process
+//! `FakeLastFm`.
+
+use lastfm_edit::iterator::ApiRecentTracksIterator;
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble};
+use lastfm_edit::{
+    ApiPeriod, AsyncPaginatedIterator, ClientConfig, IteratorCursor, LastFmApiClientImpl,
+    LastFmError,
+};
+use std::collections::HashMap;
+
+const API_KEY: &str = "fake-api-key";
+const API_SECRET: &str = "fake-api-secret";
+
+fn api_client_for(fake: &FakeLastFm) -> LastFmApiClientImpl {
+    LastFmApiClientImpl::new(
+        Box::new(fake.clone()),
+        "test_user".to_string(),
+        API_KEY.to_string(),
+    )
+    .with_client_config(ClientConfig::for_testing())
+}
+
+fn fake_with_library() -> FakeLastFm {
+    FakeLastFm::new("test_user", "hunter2")
+        .with_api_credentials(API_KEY, API_SECRET)
+        .with_scrobbles(vec![
+            FakeScrobble::new("Radiohead", "Airbag", "OK Computer", 1_700_000_000),
+            FakeScrobble::new("Radiohead", "Airbag", "OK Computer", 1_700_000_100),
+            FakeScrobble::new("Radiohead", "Lucky", "OK Computer", 1_700_000_200),
+            FakeScrobble::new("Portishead", "Roads", "Dummy", 1_700_000_300),
+        ])
+        .with_loved([("Portishead", "Roads"), ("Radiohead", "Lucky")])
+}
+
+/// Query parameters of the most recent request the fake received.
+fn last_query(fake: &FakeLastFm) -> HashMap<String, String> {
+    let request = fake.requests().pop().expect("a request was made");
+    assert_eq!(request.method, "GET");
+    let url =
+        http_types::Url::parse(&format!("https://ws.audioscrobbler.com{}", request.path)).unwrap();
+    assert_eq!(url.path(), "/2.0/");
+    url.query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect()
+}
+
+#[tokio::test]
+async fn user_info_is_requested_for_the_client_user() {
+    let fake = fake_with_library();
+    let info = api_client_for(&fake).get_user_info().await.unwrap();
+
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "user.getInfo");
+    assert_eq!(query["user"], "test_user");
+    assert_eq!(query["api_key"], API_KEY);
+    assert_eq!(query["format"], "json");
+    assert!(!query.contains_key("api_sig"));
+
+    assert_eq!(info.name, "test_user");
+    assert_eq!(info.playcount, 4);
+    assert_eq!(info.artist_count, Some(2));
+    assert_eq!(info.track_count, Some(3));
+    assert!(!info.subscriber);
+}
+
+#[tokio::test]
+async fn top_charts_send_period_page_and_limit() {
+    let fake = fake_with_library();
+    let client = api_client_for(&fake);
+
+    let artists = client.get_top_artists(ApiPeriod::Overall, 1).await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "user.getTopArtists");
+    assert_eq!(query["user"], "test_user");
+    assert_eq!(query["period"], "overall");
+    assert_eq!(query["page"], "1");
+    assert_eq!(query["limit"], "200");
+    let names: Vec<_> = artists
+        .items
+        .iter()
+        .map(|a| (a.rank, a.name.as_str()))
+        .collect();
+    assert_eq!(names, vec![(1, "Radiohead"), (2, "Portishead")]);
+    assert_eq!(artists.items[0].playcount, 3);
+    assert!(!artists.has_next_page);
+
+    let tracks = client.get_top_tracks(ApiPeriod::Overall, 1).await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "user.getTopTracks");
+    assert_eq!(query["period"], "overall");
+    assert_eq!(tracks.items[0].name, "Airbag");
+    assert_eq!(tracks.items[0].artist, "Radiohead");
+    assert_eq!(tracks.items[0].playcount, 2);
+
+    let albums = client.get_top_albums(ApiPeriod::Overall, 1).await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "user.getTopAlbums");
+    assert_eq!(albums.items[0].name, "OK Computer");
+    assert_eq!(albums.items[0].playcount, 3);
+
+    // Scrobbles from 2023 fall outside the last seven days.
+    let recent = client.get_top_albums(ApiPeriod::SevenDay, 2).await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["period"], "7day");
+    assert_eq!(query["page"], "2");
+    assert!(recent.items.is_empty());
+}
+
+#[tokio::test]
+async fn loved_tracks_are_listed_most_recent_first() {
+    let fake = fake_with_library();
+    let loved = api_client_for(&fake).get_loved_tracks(1).await.unwrap();
+
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "user.getLovedTracks");
+    assert_eq!(query["user"], "test_user");
+    assert_eq!(query["page"], "1");
+    assert_eq!(query["limit"], "200");
+
+    let names: Vec<_> = loved.items.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["Lucky", "Roads"]);
+    assert_eq!(loved.items[0].artist, "Radiohead");
+}
+
+#[tokio::test]
+async fn info_calls_send_the_username_for_personal_counts() {
+    let fake = fake_with_library();
+    let client = api_client_for(&fake);
+
+    let track = client.get_track_info("Radiohead", "Lucky").await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "track.getInfo");
+    assert_eq!(query["artist"], "Radiohead");
+    assert_eq!(query["track"], "Lucky");
+    assert_eq!(query["username"], "test_user");
+    assert_eq!(track.album.as_deref(), Some("OK Computer"));
+    assert_eq!(track.user_playcount, Some(1));
+    assert_eq!(track.user_loved, Some(true));
+
+    let album = client
+        .get_album_info("Radiohead", "OK Computer")
+        .await
+        .unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "album.getInfo");
+    assert_eq!(query["artist"], "Radiohead");
+    assert_eq!(query["album"], "OK Computer");
+    assert_eq!(query["username"], "test_user");
+    assert_eq!(album.user_playcount, Some(3));
+    let tracks: Vec<_> = album
+        .tracks
+        .iter()
+        .map(|t| (t.rank, t.name.as_str()))
+        .collect();
+    assert_eq!(tracks, vec![(Some(1), "Airbag"), (Some(2), "Lucky")]);
+
+    let artist = client.get_artist_info("Portishead").await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "artist.getInfo");
+    assert_eq!(query["artist"], "Portishead");
+    assert_eq!(query["username"], "test_user");
+    assert_eq!(artist.user_playcount, Some(1));
+}
+
+#[tokio::test]
+async fn artist_correction_returns_the_canonical_spelling() {
+    let fake = fake_with_library();
+    let client = api_client_for(&fake);
+
+    let correction = client.get_artist_correction("radiohead").await.unwrap();
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "artist.getCorrection");
+    assert_eq!(query["artist"], "radiohead");
+    assert_eq!(correction.unwrap().name, "Radiohead");
+
+    assert_eq!(
+        client.get_artist_correction("Radiohead").await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn api_errors_surface_their_code_and_message() {
+    let fake = fake_with_library();
+    let client = api_client_for(&fake);
+
+    let err = client
+        .get_track_info("Radiohead", "Creep")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, LastFmError::Http(msg) if msg == "last.fm API error 6: Track not found"),
+        "{err}"
+    );
+
+    let wrong_key = LastFmApiClientImpl::new(
+        Box::new(fake.clone()),
+        "test_user".to_string(),
+        "not-the-key".to_string(),
+    );
+    let err = wrong_key.get_user_info().await.unwrap_err();
+    assert!(
+        matches!(&err, LastFmError::Http(msg) if msg.starts_with("last.fm API error 10:")),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn recent_tracks_resume_without_repeats_after_new_scrobbles() {
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_api_credentials(API_KEY, API_SECRET)
+        .with_scrobbles((0..300).map(|i| {
+            FakeScrobble::new("Artist", &format!("Track {i}"), "Album", 1_700_000_000 + i)
+        }));
+    let client = api_client_for(&fake);
+
+    // Stop part-way through the second 200-track page.
+    let mut iterator = client.recent_tracks();
+    let mut seen = Vec::new();
+    for _ in 0..250 {
+        seen.push(iterator.next().await.unwrap().unwrap().name);
+    }
+    let cursor = iterator.cursor().unwrap();
+    assert_eq!((cursor.page, cursor.offset), (2, 50));
+    assert_eq!(cursor.last_timestamp, Some(1_700_000_050));
+    let query = last_query(&fake);
+    assert_eq!(query["method"], "user.getrecenttracks");
+    assert_eq!(query["extended"], "1");
+
+    for i in 0..30 {
+        fake.add_scrobble(FakeScrobble::new(
+            "Artist",
+            "New",
+            "Album",
+            1_700_001_000 + i,
+        ));
+    }
+
+    let cursor: IteratorCursor =
+        serde_json::from_str(&serde_json::to_string(&cursor).unwrap()).unwrap();
+    let mut resumed = ApiRecentTracksIterator::from_cursor(client.clone(), &cursor).unwrap();
+    while let Some(track) = resumed.next().await.unwrap() {
+        seen.push(track.name);
+    }
+    let expected: Vec<String> = (0..300).rev().map(|i| format!("Track {i}")).collect();
+    assert_eq!(seen, expected);
+}
@@ -0,0 +1,177 @@
+//! Signed API write calls (`auth.getMobileSession`, `track.scrobble`,
+//! `track.updateNowPlaying`) against the in-process `FakeLastFm`.
+
+use lastfm_edit::testing::{FakeLastFm, RateLimitResponse};
+use lastfm_edit::{
+    ApiScrobble, ClientConfig, ClientEvent, LastFmApiClientImpl, LastFmError, RateLimitBehavior,
+    RateLimitType,
+};
+
+const API_KEY: &str = "fake-api-key";
+const API_SECRET: &str = "fake-api-secret";
+
+fn api_client_for(fake: &FakeLastFm) -> LastFmApiClientImpl {
+    LastFmApiClientImpl::new(
+        Box::new(fake.clone()),
+        "test_user".to_string(),
+        API_KEY.to_string(),
+    )
+    .with_api_secret(API_SECRET.to_string())
+    .with_client_config(ClientConfig::for_testing())
+}
+
+#[tokio::test]
+async fn mobile_session_is_obtained_and_stored() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials(API_KEY, API_SECRET);
+    let client = api_client_for(&fake);
+
+    let err = client.get_mobile_session("wrong").await.unwrap_err();
+    assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+    assert_eq!(client.session_key(), None);
+
+    let key = client.get_mobile_session("hunter2").await.unwrap();
+    assert_eq!(key, fake.api_session_key());
+    assert_eq!(client.clone().session_key(), Some(key));
+}
+
+#[tokio::test]
+async fn signed_calls_require_secret_and_session() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials(API_KEY, API_SECRET);
+
+    let unsigned = LastFmApiClientImpl::new(
+        Box::new(fake.clone()),
+        "test_user".to_string(),
+        API_KEY.to_string(),
+    );
+    assert!(unsigned.get_mobile_session("hunter2").await.is_err());
+
+    let no_session = api_client_for(&fake);
+    assert!(no_session
+        .update_now_playing("Artist", "Track", None)
+        .await
+        .is_err());
+
+    // A wrong secret produces a signature the server rejects.
+    let bad_secret = api_client_for(&fake).with_api_secret("not-the-secret".to_string());
+    assert!(bad_secret.get_mobile_session("hunter2").await.is_err());
+    assert!(fake.requests().iter().all(|r| r.path == "/2.0/"));
+}
+
+#[tokio::test]
+async fn scrobbles_are_batched_and_recorded() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials(API_KEY, API_SECRET);
+    let client = api_client_for(&fake).with_session_key(fake.api_session_key());
+
+    let mut scrobbles: Vec<_> = (0..120)
+        .map(|i| {
+            ApiScrobble::new("Artist", &format!("Track {i}"), 1_700_000_000 + i)
+                .with_album("Album")
+                .with_album_artist("Various Artists")
+        })
+        .collect();
+    scrobbles.push(ApiScrobble::new("", "Nameless", 1_700_001_000));
+
+    let result = client.scrobble(&scrobbles).await.unwrap();
+    assert_eq!(result.accepted, 120);
+    assert_eq!(result.ignored.len(), 1);
+    assert_eq!(result.ignored[0].code, 1);
+    assert_eq!(result.ignored[0].scrobble.track, "Nameless");
+    assert!(!result.all_accepted());
+
+    // 121 scrobbles go out as batches of 50, 50 and 21.
+    assert_eq!(fake.requests().len(), 3);
+    let library = fake.scrobbles();
+    assert_eq!(library.len(), 120);
+    assert_eq!(library[0].track, "Track 119");
+    assert_eq!(library[0].album_artist, "Various Artists");
+}
+
+#[tokio::test]
+async fn a_failed_later_batch_keeps_the_earlier_results() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials(API_KEY, API_SECRET);
+    let client = api_client_for(&fake)
+        .with_session_key(fake.api_session_key())
+        .with_client_config(ClientConfig {
+            rate_limit_behavior: RateLimitBehavior::ReturnError,
+            ..ClientConfig::for_testing()
+        });
+
+    let scrobbles: Vec<_> = (0..120)
+        .map(|i| ApiScrobble::new("Artist", &format!("Track {i}"), 1_700_000_000 + i))
+        .collect();
+    fake.rate_limit_after(1, 1, RateLimitResponse::ApiRateLimitExceeded);
+
+    let result = client.scrobble(&scrobbles).await.unwrap();
+    assert_eq!(result.accepted, 50);
+    assert!(!result.all_accepted());
+    let failed = result.failed.expect("second batch should fail");
+    assert_eq!(failed.range, 50..100);
+    assert!(
+        matches!(failed.error, LastFmError::RateLimit { .. }),
+        "{}",
+        failed.error
+    );
+
+    // The third batch is never sent.
+    assert_eq!(fake.requests().len(), 2);
+    assert_eq!(fake.scrobbles().len(), 50);
+
+    // A failing first batch has nothing to report and stays an error.
+    fake.rate_limit_next(1, RateLimitResponse::ApiRateLimitExceeded);
+    assert!(client.scrobble(&scrobbles[..10]).await.is_err());
+}
+
+#[tokio::test]
+async fn now_playing_is_updated() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials(API_KEY, API_SECRET);
+    let client = api_client_for(&fake).with_session_key(fake.api_session_key());
+
+    client
+        .update_now_playing("Portishead", "Roads", Some("Dummy"))
+        .await
+        .unwrap();
+    let now_playing = fake.now_playing().unwrap();
+    assert_eq!(now_playing.track, "Roads");
+    assert_eq!(now_playing.album, "Dummy");
+    assert!(fake.scrobbles().is_empty());
+}
+
+#[tokio::test]
+async fn api_rate_limits_are_retried_and_broadcast() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials(API_KEY, API_SECRET);
+    let client = api_client_for(&fake).with_session_key(fake.api_session_key());
+    let mut events = client.subscribe();
+
+    fake.rate_limit_next(1, RateLimitResponse::ApiRateLimitExceeded);
+    fake.rate_limit_next(
+        1,
+        RateLimitResponse::TooManyRequests {
+            retry_after: Some(0),
+        },
+    );
+    let result = client
+        .scrobble(&[ApiScrobble::new("Artist", "Track", 1_700_000_000)])
+        .await
+        .unwrap();
+    assert_eq!(result.accepted, 1);
+
+    let mut rate_limit_types = Vec::new();
+    let mut saw_end = false;
+    while let Ok(event) = events.try_recv() {
+        match event {
+            ClientEvent::RateLimited {
+                request: Some(_),
+                rate_limit_type,
+                ..
+            } => rate_limit_types.push(rate_limit_type),
+            ClientEvent::RateLimitEnded { .. } => saw_end = true,
+            _ => {}
+        }
+    }
+    assert_eq!(
+        rate_limit_types,
+        [RateLimitType::ApiError, RateLimitType::Http429]
+    );
+    assert!(saw_end);
+    assert_eq!(fake.requests().iter().filter(|r| r.rate_limited).count(), 2);
+}
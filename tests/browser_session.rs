@@ -0,0 +1,163 @@
+//! Importing a Last.fm session from Firefox and Chromium cookie databases, validated
+//! against the in-process `FakeLastFm` site.
+#![cfg(feature = "browser-import")]
+
+use lastfm_edit::browser_session::import_browser_session;
+use lastfm_edit::testing::FakeLastFm;
+use lastfm_edit::{LastFmEditSession, LastFmError, SessionManager};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lastfm-edit-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The fake's session cookies as (name, value) pairs.
+fn session_cookies(fake: &FakeLastFm) -> Vec<(String, String)> {
+    fake.session()
+        .cookies
+        .iter()
+        .filter_map(|cookie| cookie.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn write_firefox_profile(profile: &Path, cookies: &[(String, String)]) {
+    let conn = Connection::open(profile.join("cookies.sqlite")).unwrap();
+    conn.execute(
+        "CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, name TEXT, value TEXT,
+         host TEXT, path TEXT, expiry INTEGER)",
+        [],
+    )
+    .unwrap();
+    // A stale cookie from an older login and one for another site must be ignored.
+    conn.execute(
+        "INSERT INTO moz_cookies (name, value, host, path, expiry)
+         VALUES ('sessionid', 'stale', '.last.fm', '/', 1), ('sessionid', 'other', '.example.com', '/', 9999999999)",
+        [],
+    )
+    .unwrap();
+    for (name, value) in cookies {
+        conn.execute(
+            "INSERT INTO moz_cookies (name, value, host, path, expiry)
+             VALUES (?1, ?2, '.www.last.fm', '/', 9999999999)",
+            params![name, value],
+        )
+        .unwrap();
+    }
+}
+
+fn write_chromium_profile(profile: &Path, cookies: &[(String, String)], encrypted: bool) {
+    std::fs::create_dir_all(profile.join("Network")).unwrap();
+    let conn = Connection::open(profile.join("Network/Cookies")).unwrap();
+    conn.execute(
+        "CREATE TABLE cookies (host_key TEXT, name TEXT, value TEXT,
+         encrypted_value BLOB DEFAULT '', path TEXT, expires_utc INTEGER)",
+        [],
+    )
+    .unwrap();
+    for (name, value) in cookies {
+        let (value, encrypted_value) = if encrypted {
+            (String::new(), b"v10\x01\x02".to_vec())
+        } else {
+            (value.clone(), Vec::new())
+        };
+        conn.execute(
+            "INSERT INTO cookies (host_key, name, value, encrypted_value, path, expires_utc)
+             VALUES ('.last.fm', ?1, ?2, ?3, '/', 13400000000000000)",
+            params![name, value, encrypted_value],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn reads_session_from_firefox_and_chromium_profiles() {
+    let fake = FakeLastFm::new("test_user", "hunter2");
+    let cookies = session_cookies(&fake);
+    let csrf_token = fake.session().csrf_token;
+
+    let firefox = scratch_dir("browser-firefox");
+    write_firefox_profile(&firefox, &cookies);
+    let chromium = scratch_dir("browser-chromium");
+    write_chromium_profile(&chromium, &cookies, false);
+
+    for profile in [firefox.clone(), chromium.join("Network/Cookies"), chromium] {
+        let session = LastFmEditSession::from_browser_profile(&profile, "test_user").unwrap();
+        assert_eq!(session.username, "test_user");
+        assert_eq!(session.csrf_token, csrf_token);
+        assert!(session.is_valid(), "{session:?}");
+        for (name, value) in &cookies {
+            assert!(
+                session.cookies.contains(&format!("{name}={value}")),
+                "{name} missing from {:?}",
+                session.cookies
+            );
+        }
+    }
+}
+
+#[test]
+fn encrypted_chromium_cookies_are_rejected() {
+    let fake = FakeLastFm::new("test_user", "hunter2");
+    let profile = scratch_dir("browser-encrypted");
+    write_chromium_profile(&profile, &session_cookies(&fake), true);
+
+    let err = LastFmEditSession::from_browser_profile(&profile, "test_user").unwrap_err();
+    assert!(err.to_string().contains("encrypted"), "{err}");
+}
+
+#[test]
+fn failed_copies_leave_no_cookie_scratch_dir_behind() {
+    let marker = format!("scratch-marker-{}", std::process::id());
+    let profile = scratch_dir("browser-failed-copy");
+    write_firefox_profile(&profile, &[("sessionid".to_string(), marker.clone())]);
+    // A directory where the WAL file should be makes copying the sidecar fail.
+    std::fs::create_dir(profile.join("cookies.sqlite-wal")).unwrap();
+
+    let err = LastFmEditSession::from_browser_profile(&profile, "test_user").unwrap_err();
+    assert!(matches!(err, LastFmError::Io(_)), "{err}");
+
+    let prefix = format!("lastfm-edit-browser-cookies-{}-", std::process::id());
+    let leaked = std::fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|entry| std::fs::read(entry.path().join("cookies.db")).ok())
+        .any(|copy| copy.windows(marker.len()).any(|w| w == marker.as_bytes()));
+    assert!(
+        !leaked,
+        "a copy of the cookie database was left in the temp dir"
+    );
+}
+
+#[tokio::test]
+async fn imported_session_is_validated_and_saved() {
+    let fake = FakeLastFm::new("test_user", "hunter2");
+    let profile = scratch_dir("browser-import");
+    write_firefox_profile(&profile, &session_cookies(&fake));
+    let manager =
+        SessionManager::with_data_dir("lastfm-edit-test", scratch_dir("browser-import-data"));
+
+    let client = import_browser_session(Box::new(fake.clone()), &profile, "test_user", &manager)
+        .await
+        .unwrap();
+    assert_eq!(client.username(), "test_user");
+    assert_eq!(
+        manager.load_session("test_user").unwrap(),
+        client.get_session()
+    );
+
+    fake.expire_sessions();
+    let other = SessionManager::with_data_dir("lastfm-edit-test", scratch_dir("browser-expired"));
+    let Err(err) =
+        import_browser_session(Box::new(fake.clone()), &profile, "test_user", &other).await
+    else {
+        panic!("an expired browser session must not be imported");
+    };
+    assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+    assert!(!other.session_exists("test_user"));
+}
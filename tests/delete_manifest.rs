@@ -95,3 +95,124 @@ mod tests {
         assert!(attempts[1].2.success());
     }
 }
+
+mod restore {
+    use lastfm_edit::delete_manifest::{
+        read_manifest, restore_manifest, save_manifest, write_manifest, DeleteAttemptResult,
+        DeleteManifestSource, DeleteTarget, RestoreAttemptResult,
+    };
+    use lastfm_edit::testing::{FakeLastFm, RateLimitResponse};
+    use lastfm_edit::{ClientConfig, LastFmApiClientImpl, RetryConfig};
+
+    fn target(track: &str, timestamp: u64) -> DeleteTarget {
+        DeleteTarget {
+            offset: None,
+            artist: "Artist".to_string(),
+            track: track.to_string(),
+            album: Some("Album".to_string()),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn restore_resubmits_deleted_entries_and_resumes() {
+        let path = std::env::temp_dir().join(format!(
+            "lastfm-edit-restore-manifest-test-{}.json",
+            std::process::id()
+        ));
+        write_manifest(
+            &path,
+            DeleteManifestSource {
+                kind: "test".to_string(),
+                range: None,
+            },
+            &[
+                target("Deleted", 100),
+                target("Missing", 200),
+                target("Also Deleted", 300),
+            ],
+        )
+        .unwrap();
+
+        let mut manifest = read_manifest(&path).unwrap();
+        manifest.record_delete_result(1, DeleteAttemptResult::Deleted);
+        manifest.record_delete_result(
+            2,
+            DeleteAttemptResult::NotDeleted {
+                message: "missing".to_string(),
+            },
+        );
+        manifest.record_delete_result(3, DeleteAttemptResult::Deleted);
+        // Re-running a manifest must not make a deleted entry unrestorable.
+        manifest.record_delete_result(
+            3,
+            DeleteAttemptResult::NotDeleted {
+                message: "missing".to_string(),
+            },
+        );
+        save_manifest(&path, &manifest).unwrap();
+        assert_eq!(manifest.restore_candidates(false).len(), 2);
+        assert_eq!(manifest.restore_candidates(true).len(), 2);
+
+        let fake = FakeLastFm::new("test_user", "hunter2").with_api_credentials("key", "secret");
+        let client = LastFmApiClientImpl::new(
+            Box::new(fake.clone()),
+            "test_user".to_string(),
+            "key".to_string(),
+        )
+        .with_api_secret("secret".to_string())
+        .with_session_key(fake.api_session_key())
+        .with_client_config(ClientConfig::for_testing().with_retry_config(RetryConfig::disabled()));
+
+        // The first attempt fails; the failure is recorded and the entries stay pending.
+        fake.rate_limit_next(
+            1,
+            RateLimitResponse::TooManyRequests {
+                retry_after: Some(0),
+            },
+        );
+        assert!(restore_manifest(&client, &path, false, |_, _| {})
+            .await
+            .is_err());
+        let manifest = read_manifest(&path).unwrap();
+        assert!(matches!(
+            manifest.scrobbles[0].restore_result,
+            Some(RestoreAttemptResult::Error { .. })
+        ));
+        assert!(fake.scrobbles().is_empty());
+
+        let mut attempts = Vec::new();
+        let summary = restore_manifest(&client, &path, false, |entry, result| {
+            attempts.push((entry.index, result.success()));
+        })
+        .await
+        .unwrap();
+        assert_eq!(summary.total_candidates, 2);
+        assert_eq!(summary.restored, 2);
+        assert_eq!(attempts, [(1, true), (3, true)]);
+
+        let restored: Vec<_> = fake
+            .scrobbles()
+            .into_iter()
+            .map(|s| (s.track, s.album, s.timestamp))
+            .collect();
+        assert_eq!(
+            restored,
+            [
+                ("Also Deleted".to_string(), "Album".to_string(), 300),
+                ("Deleted".to_string(), "Album".to_string(), 100),
+            ]
+        );
+
+        // Everything is recorded as restored, so a rerun submits nothing.
+        let manifest = read_manifest(&path).unwrap();
+        assert!(manifest.restore_candidates(true).is_empty());
+        let rerun = restore_manifest(&client, &path, false, |_, _| {})
+            .await
+            .unwrap();
+        assert_eq!(rerun.total_candidates, 0);
+        assert_eq!(fake.scrobbles().len(), 2);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
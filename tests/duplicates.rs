@@ -0,0 +1,79 @@
+//! Duplicate scrobble detection.
+
+use lastfm_edit::duplicates::{
+    duplicate_targets, find_duplicates, scan_recent_pages, DuplicateFinderConfig,
+};
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble};
+use lastfm_edit::{ClientConfig, LastFmEditClientImpl, Track};
+
+fn play(track: &str, album: Option<&str>, timestamp: Option<u64>) -> Track {
+    Track {
+        name: track.to_string(),
+        artist: "Artist".to_string(),
+        playcount: 1,
+        timestamp,
+        album: album.map(str::to_string),
+        album_artist: None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn double_submissions_keep_the_earliest_copy() {
+    // Newest first, with a now-playing row and an interleaved scrobble from another client.
+    let tracks = vec![
+        play("Now Playing", Some("Album"), None),
+        play("Song", Some("Album"), Some(1_000_010)),
+        play("Other", Some("Album"), Some(1_000_005)),
+        play("Song", Some("Album"), Some(1_000_002)),
+        play("Song", Some("Album"), Some(1_000_000)),
+        // Same track and album, but a genuine replay outside the window.
+        play("Song", Some("Album"), Some(999_700)),
+        // Same track on a different album is a different play.
+        play("Song", Some("Single"), Some(999_699)),
+    ];
+
+    let groups = find_duplicates(&tracks, &DuplicateFinderConfig::default());
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].kept.timestamp, 1_000_000);
+    assert_eq!(groups[0].kept.offset, Some(4));
+    let offsets: Vec<_> = groups[0].duplicates.iter().map(|d| d.offset).collect();
+    assert_eq!(offsets, [Some(1), Some(3)]);
+
+    let narrow = find_duplicates(&tracks, &DuplicateFinderConfig { window_secs: 1 });
+    assert!(narrow.is_empty());
+}
+
+#[tokio::test]
+async fn scan_finds_duplicates_across_pages() {
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(vec![
+            FakeScrobble::new("Artist", "A", "Album", 1_000_400),
+            FakeScrobble::new("Artist", "B", "Album", 1_000_300),
+            FakeScrobble::new("Artist", "B", "Album", 1_000_298),
+            FakeScrobble::new("Artist", "C", "Album", 1_000_000),
+            FakeScrobble::new("Artist", "C", "Album", 999_990),
+        ])
+        .with_page_size(2);
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing(),
+    );
+
+    let groups = scan_recent_pages(&client, 10, &DuplicateFinderConfig::default())
+        .await
+        .unwrap();
+    let targets = duplicate_targets(&groups);
+    let deleted: Vec<_> = targets
+        .iter()
+        .map(|t| (t.track.as_str(), t.timestamp))
+        .collect();
+    assert_eq!(deleted, [("B", 1_000_300), ("C", 1_000_000)]);
+
+    // Only the first page is scanned when asked for one page.
+    let first_page = scan_recent_pages(&client, 1, &DuplicateFinderConfig::default())
+        .await
+        .unwrap();
+    assert!(first_page.is_empty());
+}
@@ -0,0 +1,117 @@
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble};
+use lastfm_edit::{
+    ClientConfig, EditJournal, EditJournalFilter, ExactScrobbleEdit, FileEditJournal,
+    InMemoryEditJournal, LastFmEditClientImpl, ScrobbleEdit,
+};
+use std::sync::Arc;
+
+fn sample_edit(artist: &str, new_artist: &str) -> ExactScrobbleEdit {
+    ExactScrobbleEdit::new(
+        "Track".to_string(),
+        "Album".to_string(),
+        artist.to_string(),
+        artist.to_string(),
+        "Track".to_string(),
+        "Album".to_string(),
+        new_artist.to_string(),
+        new_artist.to_string(),
+        1_700_000_000,
+        false,
+    )
+}
+
+#[test]
+fn inverse_swaps_original_and_new_fields() {
+    let edit = sample_edit("Beatles", "The Beatles");
+    let inverse = edit.inverse();
+
+    assert_eq!(inverse.artist_name_original, "The Beatles");
+    assert_eq!(inverse.artist_name, "Beatles");
+    assert_eq!(inverse.album_artist_name, "Beatles");
+    assert_eq!(inverse.timestamp, edit.timestamp);
+    assert_eq!(inverse.inverse(), edit);
+}
+
+#[test]
+fn file_journal_persists_and_filters_entries() {
+    let path = std::env::temp_dir().join(format!(
+        "lastfm-edit-journal-test-{}.jsonl",
+        std::process::id()
+    ));
+    std::fs::remove_file(&path).ok();
+
+    let journal = FileEditJournal::new(&path);
+    let first = journal
+        .record(&sample_edit("Beatles", "The Beatles"))
+        .unwrap();
+    let second = journal
+        .record(&sample_edit("Stones", "The Rolling Stones"))
+        .unwrap();
+    assert_eq!((first.id, second.id), (1, 2));
+
+    // A fresh handle sees the same entries.
+    let reopened = FileEditJournal::new(&path);
+    assert_eq!(reopened.list(&EditJournalFilter::all()).unwrap().len(), 2);
+
+    let by_artist = reopened
+        .list(&EditJournalFilter::default().with_artist("the beatles"))
+        .unwrap();
+    assert_eq!(by_artist.len(), 1);
+    assert_eq!(by_artist[0].id, 1);
+
+    let future = reopened
+        .list(&EditJournalFilter::default().with_since(first.applied_at_unix + 3600))
+        .unwrap();
+    assert!(future.is_empty());
+
+    reopened.mark_reverted(2, 42).unwrap();
+    let active = reopened.list(&EditJournalFilter::default()).unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(reopened.get(2).unwrap().unwrap().reverted_at_unix, Some(42));
+    assert!(reopened.mark_reverted(99, 42).is_err());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn applied_edits_are_journaled_and_revertible() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(vec![
+        FakeScrobble::new("Beatles", "Help!", "Help!", 1_700_000_100),
+        FakeScrobble::new("Beatles", "Yesterday", "Help!", 1_700_000_000),
+    ]);
+    let journal = Arc::new(InMemoryEditJournal::new());
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing(),
+    )
+    .with_edit_journal(journal.clone());
+
+    let edit =
+        ScrobbleEdit::from_track_and_artist("Yesterday", "Beatles").with_artist_name("The Beatles");
+    assert!(client.edit_scrobble(&edit).await.unwrap().all_successful());
+    assert_eq!(fake.scrobbles()[1].artist, "The Beatles");
+
+    let entries = journal.list(&EditJournalFilter::default()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].edit.artist_name_original, "Beatles");
+    assert_eq!(entries[0].edit.artist_name, "The Beatles");
+
+    let response = client.revert_edit(entries[0].id).await.unwrap();
+    assert!(response.all_successful());
+    assert_eq!(fake.scrobbles()[1].artist, "Beatles");
+    assert_eq!(fake.scrobbles()[1].album_artist, "Beatles");
+
+    // The revert itself is journaled, and the original entry can't be reverted twice.
+    let all = journal.list(&EditJournalFilter::all()).unwrap();
+    assert_eq!(all.len(), 2);
+    assert!(all[0].is_reverted());
+    assert!(client.revert_edit(entries[0].id).await.is_err());
+}
+
+#[tokio::test]
+async fn revert_requires_a_journal() {
+    let fake = FakeLastFm::new("test_user", "hunter2");
+    let client = LastFmEditClientImpl::from_session(Box::new(fake.clone()), fake.session());
+    assert!(client.revert_edit(1).await.is_err());
+}
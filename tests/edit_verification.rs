@@ -0,0 +1,154 @@
+//! Post-edit verification against the in-process `FakeLastFm` site.
+
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble};
+use lastfm_edit::{
+    ClientConfig, ClientEvent, EditVerificationConfig, EditVerificationStatus, ExactScrobbleEdit,
+    LastFmEditClientImpl, ScrobbleEdit,
+};
+
+fn sample_library() -> Vec<FakeScrobble> {
+    vec![
+        FakeScrobble::new("Radiohead", "Creep", "Pablo Honey", 1_700_000_300),
+        FakeScrobble::new("Radiohead", "Karma Police", "OK Computer", 1_700_000_200),
+        FakeScrobble::new("Portishead", "Roads", "Dummy", 1_700_000_100),
+        FakeScrobble::new("Portishead", "Glory Box", "Dummy", 1_700_000_000),
+    ]
+}
+
+fn verifying_client(fake: &FakeLastFm, config: EditVerificationConfig) -> LastFmEditClientImpl {
+    LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing().with_edit_verification_config(config),
+    )
+}
+
+fn rename(
+    artist: &str,
+    track: &str,
+    album: &str,
+    timestamp: u64,
+    new_track: &str,
+) -> ExactScrobbleEdit {
+    ExactScrobbleEdit::new(
+        track.to_string(),
+        album.to_string(),
+        artist.to_string(),
+        artist.to_string(),
+        new_track.to_string(),
+        album.to_string(),
+        artist.to_string(),
+        artist.to_string(),
+        timestamp,
+        false,
+    )
+}
+
+#[tokio::test]
+async fn applied_edit_is_verified_and_broadcast() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = verifying_client(&fake, EditVerificationConfig::enabled());
+    let mut events = client.subscribe();
+
+    let edit = ScrobbleEdit::from_track_and_artist("Creep", "Radiohead")
+        .with_track_name("Creep (Acoustic)");
+    let response = client.edit_scrobble(&edit).await.unwrap();
+
+    assert!(response.all_successful());
+    assert_eq!(
+        response.individual_results[0].verification,
+        Some(EditVerificationStatus::Verified)
+    );
+
+    let mut verified = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let ClientEvent::EditVerified { edit, status, .. } = event {
+            verified.push((edit.track_name, status));
+        }
+    }
+    assert_eq!(
+        verified,
+        [(
+            "Creep (Acoustic)".to_string(),
+            EditVerificationStatus::Verified
+        )]
+    );
+}
+
+#[tokio::test]
+async fn acknowledged_but_dropped_edit_is_contradicted() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = verifying_client(&fake, EditVerificationConfig::enabled());
+    fake.ignore_next_edits(1);
+
+    let edit = rename(
+        "Radiohead",
+        "Karma Police",
+        "OK Computer",
+        1_700_000_200,
+        "Karma Police (Live)",
+    );
+    let response = client.edit_scrobble_single(&edit, 0).await.unwrap();
+
+    // The edit response still claims success; verification catches it.
+    assert!(response.all_successful());
+    assert_eq!(response.contradicted_edits(), 1);
+    assert!(matches!(
+        response.individual_results[0].verification,
+        Some(EditVerificationStatus::Contradicted { .. })
+    ));
+}
+
+#[tokio::test]
+async fn older_scrobbles_are_checked_on_the_track_page() {
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(sample_library())
+        .with_page_size(1);
+    let client = verifying_client(&fake, EditVerificationConfig::enabled().with_max_pages(1));
+
+    let edit = rename(
+        "Portishead",
+        "Glory Box",
+        "Dummy",
+        1_700_000_000,
+        "Glory Box (Edit)",
+    );
+    let response = client.edit_scrobble_single(&edit, 0).await.unwrap();
+    assert_eq!(
+        response.individual_results[0].verification,
+        Some(EditVerificationStatus::Verified)
+    );
+
+    let requested: Vec<_> = fake.requests().into_iter().map(|r| r.path).collect();
+    assert!(requested
+        .iter()
+        .any(|path| path
+            .contains("/library/music/+noredirect/Portishead/_/Glory%20Box%20%28Edit%29")));
+
+    // An edit at a timestamp nobody scrobbled cannot be found anywhere.
+    let missing = client
+        .verify_edit(&rename(
+            "Portishead",
+            "Roads",
+            "Dummy",
+            1_600_000_000,
+            "Roads",
+        ))
+        .await;
+    assert!(matches!(missing, EditVerificationStatus::Unverified { .. }));
+}
+
+#[tokio::test]
+async fn verification_is_off_by_default() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing(),
+    );
+
+    let edit = rename("Radiohead", "Creep", "Pablo Honey", 1_700_000_300, "Creep!");
+    let response = client.edit_scrobble_single(&edit, 0).await.unwrap();
+    assert!(response.all_successful());
+    assert_eq!(response.individual_results[0].verification, None);
+}
@@ -0,0 +1,809 @@
+//! End-to-end tests against the in-process `FakeLastFm` site: login, edit, re-fetch and
+//! delete flows without network access or recorded cassettes.
+
+use futures::StreamExt;
+use lastfm_edit::iterator::RecentTracksIterator;
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble, RateLimitResponse};
+use lastfm_edit::{
+    AdaptivePacingConfig, AsyncPaginatedIterator, AsyncPaginatedIteratorExt, CachePolicy,
+    ClientConfig, ClientEvent, EditFailureKind, ExactScrobbleEdit, InMemoryResponseCache,
+    IteratorCursor, LastFmBaseClient, LastFmEditClient, LastFmEditClientImpl, LastFmError,
+    LibraryDateRange, LibrarySort, LoginManager, PageKind, PagedIteratorExt, ScrobbleEdit,
+    SessionManager, SimulatedEditClient, StaticCredentials, TrafficClass,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn sample_library() -> Vec<FakeScrobble> {
+    vec![
+        FakeScrobble::new("Radiohead", "Creep", "Pablo Honey", 1_700_000_300),
+        FakeScrobble::new("Radiohead", "Creep", "Pablo Honey", 1_700_000_200),
+        FakeScrobble::new("Radiohead", "Karma Police", "OK Computer", 1_700_000_100),
+        FakeScrobble::new("Portishead", "Roads", "Dummy", 1_700_000_000),
+    ]
+}
+
+fn client_for(fake: &FakeLastFm) -> LastFmEditClientImpl {
+    LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing(),
+    )
+}
+
+#[tokio::test]
+async fn login_flow_returns_a_session_the_fake_accepts() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let login = LoginManager::new(Arc::new(fake.clone()), "https://www.last.fm".to_string());
+
+    assert!(login.login("test_user", "wrong").await.is_err());
+
+    let session = login
+        .login("test_user", "hunter2")
+        .await
+        .expect("login should succeed");
+    assert_eq!(session.username, "test_user");
+
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        session,
+        ClientConfig::for_testing(),
+    );
+    assert!(client.validate_session().await);
+
+    fake.expire_sessions();
+    assert!(!client.validate_session().await);
+}
+
+#[tokio::test]
+async fn edit_is_visible_on_refetch() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+
+    let edit = ScrobbleEdit::from_track_and_artist("Creep", "Radiohead")
+        .with_track_name("Creep (Acoustic)");
+    let response = client.edit_scrobble(&edit).await.expect("edit should run");
+    assert!(
+        response.all_successful(),
+        "{:?}",
+        response.detailed_messages()
+    );
+
+    let page = client.get_recent_tracks_page(1).await.unwrap();
+    let names: Vec<_> = page.tracks.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "Creep (Acoustic)",
+            "Creep (Acoustic)",
+            "Karma Police",
+            "Roads"
+        ]
+    );
+    assert_eq!(page.tracks[0].album.as_deref(), Some("Pablo Honey"));
+    assert_eq!(page.tracks[0].timestamp, Some(1_700_000_300));
+
+    let tracks = client
+        .artist_tracks("Radiohead")
+        .collect_all()
+        .await
+        .unwrap();
+    assert!(tracks.iter().any(|t| t.name == "Creep (Acoustic)"));
+    assert!(!tracks.iter().any(|t| t.name == "Creep"));
+}
+
+#[tokio::test]
+async fn delete_removes_scrobble_from_library_pages() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+
+    assert!(client
+        .delete_scrobble("Portishead", "Roads", 1_700_000_000)
+        .await
+        .unwrap());
+    assert!(!client
+        .delete_scrobble("Portishead", "Roads", 1_700_000_000)
+        .await
+        .unwrap());
+
+    let artists = client.artists().collect_all().await.unwrap();
+    assert_eq!(artists.len(), 1);
+    assert_eq!(artists[0].name, "Radiohead");
+    assert_eq!(artists[0].playcount, 3);
+}
+
+#[tokio::test]
+async fn library_pages_paginate() {
+    let scrobbles = (0..7)
+        .map(|i| FakeScrobble::new("Artist", &format!("Track {i}"), "Album", 1_700_000_000 + i));
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(scrobbles)
+        .with_page_size(3);
+    let client = client_for(&fake);
+
+    let first = client.get_recent_tracks_page(1).await.unwrap();
+    assert_eq!(first.tracks.len(), 3);
+    assert!(first.has_next_page);
+    assert_eq!(first.total_pages, Some(3));
+
+    let all = client.recent_tracks().collect_all().await.unwrap();
+    assert_eq!(all.len(), 7);
+    assert_eq!(all[0].name, "Track 6");
+
+    let albums = client.artist_albums("Artist").collect_all().await.unwrap();
+    assert_eq!(albums.len(), 1);
+    assert_eq!(albums[0].playcount, 7);
+
+    let album_tracks = client
+        .album_tracks("Album", "Artist")
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(album_tracks.len(), 7);
+}
+
+#[tokio::test]
+async fn iterators_stream_and_prefetch_pages_in_order() {
+    let scrobbles = (0..11)
+        .map(|i| FakeScrobble::new("Artist", &format!("Track {i}"), "Album", 1_700_000_000 + i));
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(scrobbles)
+        .with_page_size(3);
+    let client = client_for(&fake);
+
+    let expected: Vec<String> = client
+        .recent_tracks()
+        .collect_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(expected.len(), 11);
+
+    let first_five: Vec<String> = client
+        .recent_tracks()
+        .into_stream()
+        .map(|t| t.unwrap().name)
+        .take(5)
+        .collect()
+        .await;
+    assert_eq!(first_five, expected[..5]);
+
+    // Start prefetching part-way through a page: the buffered items come first, then
+    // pages 2..=4 are requested together once page 2 reveals the page count.
+    let mut iterator = RecentTracksIterator::new(client.clone());
+    let head = iterator.take(2).await.unwrap();
+    let before = fake.requests().len();
+    let rest: Vec<String> = iterator
+        .into_prefetching_stream(4)
+        .map(|t| t.unwrap().name)
+        .collect()
+        .await;
+    let names: Vec<String> = head.into_iter().map(|t| t.name).chain(rest).collect();
+    assert_eq!(names, expected);
+
+    let pages: Vec<String> = fake.requests()[before..]
+        .iter()
+        .map(|r| r.path.clone())
+        .collect();
+    assert_eq!(pages.len(), 3, "{pages:?}");
+    for page in 2..=4 {
+        assert!(
+            pages.iter().any(|p| p.ends_with(&format!("page={page}"))),
+            "{pages:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn iterators_resume_from_a_serialized_cursor() {
+    let scrobbles = (0..11).map(|i| {
+        FakeScrobble::new(
+            "Artist",
+            &format!("Track {i}"),
+            &format!("Album {}", i % 4),
+            1_700_000_000 + i,
+        )
+    });
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(scrobbles)
+        .with_page_size(3);
+    let client = client_for(&fake);
+
+    let names = |tracks: Vec<lastfm_edit::Track>| -> Vec<String> {
+        tracks.into_iter().map(|t| t.name).collect()
+    };
+
+    // Page-based and album-based iterators, stopped part-way through a page and an album
+    for iterator in [
+        client.library_tracks(LibraryDateRange::AllTime, LibrarySort::Name),
+        client.artist_tracks("Artist"),
+    ] {
+        let mut iterator = iterator;
+        let mut seen = names(iterator.take(4).await.unwrap());
+        let cursor = iterator.cursor().unwrap();
+        let rest = names(iterator.collect_all().await.unwrap());
+
+        let json = serde_json::to_string(&cursor).unwrap();
+        let cursor: IteratorCursor = serde_json::from_str(&json).unwrap();
+        let mut resumed = cursor.track_iterator(client.clone()).unwrap();
+        let resumed_rest = names(resumed.collect_all().await.unwrap());
+        assert_eq!(resumed_rest, rest, "{json}");
+
+        seen.extend(resumed_rest);
+        assert_eq!(seen.len(), 11, "{json}");
+    }
+
+    let cursor = client.artists().cursor().unwrap();
+    assert!(cursor.track_iterator(client.clone()).is_err());
+}
+
+#[tokio::test]
+async fn recent_tracks_resume_without_repeats_after_new_scrobbles() {
+    let scrobbles =
+        (0..11).map(|i| FakeScrobble::new("Artist", &format!("Track {i}"), "", 1_700_000_000 + i));
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(scrobbles)
+        .with_page_size(3);
+    let client = client_for(&fake);
+
+    let names = |tracks: Vec<lastfm_edit::Track>| -> Vec<String> {
+        tracks.into_iter().map(|t| t.name).collect()
+    };
+
+    let mut iterator = client.recent_tracks();
+    let mut seen = names(iterator.take(4).await.unwrap());
+    let cursor: IteratorCursor =
+        serde_json::from_str(&serde_json::to_string(&iterator.cursor().unwrap()).unwrap()).unwrap();
+    assert_eq!(cursor.last_timestamp, Some(1_700_000_007));
+
+    // Four new plays push everything one page further down the listing.
+    for i in 0..4 {
+        fake.add_scrobble(FakeScrobble::new("Artist", "New", "", 1_700_000_100 + i));
+    }
+
+    let mut resumed = cursor.track_iterator(client.clone()).unwrap();
+    seen.extend(names(resumed.collect_all().await.unwrap()));
+    let expected: Vec<String> = (0..11).rev().map(|i| format!("Track {i}")).collect();
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn library_pages_filter_by_date_range() {
+    // 2019-06-01, 2019-12-31T23:00 and 2020-01-01T01:00 (UTC)
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(vec![
+        FakeScrobble::new("Radiohead", "Creep", "Pablo Honey", 1_559_347_200),
+        FakeScrobble::new("Radiohead", "Airbag", "OK Computer", 1_577_833_200),
+        FakeScrobble::new("Radiohead", "Lucky", "OK Computer", 1_577_840_400),
+        FakeScrobble::new("Portishead", "Roads", "Dummy", 1_577_840_400),
+    ]);
+    let client = client_for(&fake);
+    let in_2019 = LibraryDateRange::year(2019).unwrap();
+
+    let mut tracks: Vec<String> = client
+        .artist_tracks_in_range("Radiohead", in_2019)
+        .collect_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    tracks.sort();
+    assert_eq!(tracks, vec!["Airbag", "Creep"]);
+
+    let artists = client
+        .artists_in_range(in_2019)
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(artists.len(), 1);
+    assert_eq!(artists[0].name, "Radiohead");
+    assert_eq!(artists[0].playcount, 2);
+
+    let albums = client
+        .artist_albums_in_range("Radiohead", in_2019)
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(albums.len(), 2);
+    assert!(albums.iter().all(|a| a.playcount == 1));
+
+    // The plain listings still cover all time.
+    let all_tracks = client
+        .artist_tracks_direct("Radiohead")
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(all_tracks.len(), 3);
+}
+
+#[tokio::test]
+async fn track_scrobbles_lists_every_timestamped_play() {
+    let mut scrobbles = sample_library();
+    scrobbles.push(FakeScrobble::new(
+        "Radiohead",
+        "Creep",
+        "Creep (Single)",
+        1_600_000_000,
+    ));
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(scrobbles)
+        .with_page_size(2);
+    let client = client_for(&fake);
+
+    let plays = client
+        .track_scrobbles("Radiohead", "Creep")
+        .collect_all()
+        .await
+        .unwrap();
+    let seen: Vec<(Option<u64>, Option<&str>)> = plays
+        .iter()
+        .map(|t| (t.timestamp, t.album.as_deref()))
+        .collect();
+    assert_eq!(
+        seen,
+        vec![
+            (Some(1_700_000_300), Some("Pablo Honey")),
+            (Some(1_700_000_200), Some("Pablo Honey")),
+            (Some(1_600_000_000), Some("Creep (Single)")),
+        ]
+    );
+    assert!(plays.iter().all(|t| t.name == "Creep" && t.playcount == 1));
+
+    let none = client
+        .track_scrobbles("Radiohead", "Nude")
+        .collect_all()
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn library_listings_cover_every_artist() {
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(sample_library())
+        .with_page_size(2);
+    let client = client_for(&fake);
+
+    let tracks = client
+        .library_tracks(LibraryDateRange::AllTime, LibrarySort::Scrobbles)
+        .collect_all()
+        .await
+        .unwrap();
+    let tracks: Vec<(&str, &str, u32)> = tracks
+        .iter()
+        .map(|t| (t.artist.as_str(), t.name.as_str(), t.playcount))
+        .collect();
+    assert_eq!(
+        tracks,
+        vec![
+            ("Radiohead", "Creep", 2),
+            ("Portishead", "Roads", 1),
+            ("Radiohead", "Karma Police", 1),
+        ]
+    );
+
+    let albums = client
+        .library_albums(LibraryDateRange::AllTime, LibrarySort::Name)
+        .collect_all()
+        .await
+        .unwrap();
+    let albums: Vec<&str> = albums.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(albums, vec!["Dummy", "OK Computer", "Pablo Honey"]);
+
+    // 2023-11-14 covers every sample scrobble; the day before covers none.
+    let day = |d| LibraryDateRange::between(d, d);
+    let nov_14 = chrono::NaiveDate::from_ymd_opt(2023, 11, 14).unwrap();
+    let in_day = client
+        .library_tracks(day(nov_14), LibrarySort::Scrobbles)
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(in_day.len(), 3);
+    let before = client
+        .library_albums(day(nov_14.pred_opt().unwrap()), LibrarySort::Scrobbles)
+        .collect_all()
+        .await
+        .unwrap();
+    assert!(before.is_empty());
+}
+
+#[tokio::test]
+async fn configured_rate_limits_are_retried() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+    let mut events = client.subscribe();
+
+    fake.rate_limit_next(
+        1,
+        RateLimitResponse::TooManyRequests {
+            retry_after: Some(0),
+        },
+    );
+    let page = client.get_recent_tracks_page(1).await.unwrap();
+    assert_eq!(page.tracks.len(), 4);
+
+    let mut saw_rate_limit = false;
+    while let Ok(event) = events.try_recv() {
+        saw_rate_limit |= matches!(event, ClientEvent::RateLimited { .. });
+    }
+    assert!(saw_rate_limit);
+
+    fake.rate_limit_next_posts(1, RateLimitResponse::Interstitial { status: 406 });
+    assert!(client
+        .delete_scrobble("Radiohead", "Karma Police", 1_700_000_100)
+        .await
+        .unwrap());
+
+    let requests = fake.requests();
+    assert_eq!(requests.iter().filter(|r| r.rate_limited).count(), 2);
+    assert!(fake.scrobbles().iter().all(|s| s.track != "Karma Police"));
+}
+
+#[tokio::test]
+async fn edit_fetches_a_fresh_csrf_token() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+
+    let edit =
+        ScrobbleEdit::from_track_and_artist("Roads", "Portishead").with_track_name("Roads (Live)");
+    // The client always fetches a fresh token, so rotating between requests is harmless.
+    fake.rotate_csrf_token("rotated-token");
+    let response = client.edit_scrobble(&edit).await.unwrap();
+    assert!(response.all_successful());
+    assert_eq!(fake.scrobbles()[3].track, "Roads (Live)");
+}
+
+#[tokio::test]
+async fn rejected_edits_carry_a_failure_kind() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+
+    // The scrobble no longer has this album, so Last.fm cannot find it.
+    let edit = ExactScrobbleEdit::new(
+        "Roads".to_string(),
+        "Third".to_string(),
+        "Portishead".to_string(),
+        "Portishead".to_string(),
+        "Roads (Live)".to_string(),
+        "Third".to_string(),
+        "Portishead".to_string(),
+        "Portishead".to_string(),
+        1_700_000_000,
+        false,
+    );
+    let response = client.edit_scrobble_single(&edit, 3).await.unwrap();
+    let result = &response.individual_results[0];
+    assert!(!result.success);
+    assert_eq!(result.failure_kind, Some(EditFailureKind::ScrobbleNotFound));
+
+    // Not retryable, so it was submitted exactly once.
+    let posts = fake
+        .requests()
+        .into_iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+}
+
+#[tokio::test]
+async fn server_errors_after_an_applied_edit_are_not_resubmitted() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+
+    let edit = ExactScrobbleEdit::new(
+        "Roads".to_string(),
+        "Dummy".to_string(),
+        "Portishead".to_string(),
+        "Portishead".to_string(),
+        "Roads (Live)".to_string(),
+        "Dummy".to_string(),
+        "Portishead".to_string(),
+        "Portishead".to_string(),
+        1_700_000_000,
+        false,
+    );
+    fake.fail_next_edits_after_applying(1);
+    let response = client.edit_scrobble_single(&edit, 3).await.unwrap();
+    let result = &response.individual_results[0];
+    assert!(!result.success);
+    assert_eq!(result.failure_kind, Some(EditFailureKind::ServerError));
+
+    // The edit went through, so submitting it again against the old metadata would fail.
+    let posts = fake
+        .requests()
+        .into_iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+    assert!(fake.scrobbles().iter().any(|s| s.track == "Roads (Live)"));
+}
+
+#[tokio::test]
+async fn page_loads_retry_server_errors() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake);
+
+    fake.fail_next_gets(2);
+    let page = client.get_recent_tracks_page(1).await.unwrap();
+    assert_eq!(page.tracks.len(), sample_library().len());
+    assert_eq!(fake.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn adaptive_pacing_backs_off_on_rate_limits_and_recovers() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let pacing = AdaptivePacingConfig {
+        speedup_step_ms: 5,
+        backoff_floor_ms: 20,
+        ..AdaptivePacingConfig::enabled().with_delay_bounds(0, 100)
+    };
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing().with_adaptive_pacing_config(pacing),
+    );
+    let mut events = client.subscribe();
+    assert_eq!(client.pacing_delay_ms(TrafficClass::Get), Some(0));
+
+    fake.rate_limit_next(
+        1,
+        RateLimitResponse::TooManyRequests {
+            retry_after: Some(0),
+        },
+    );
+    client.get_recent_tracks_page(1).await.unwrap();
+
+    let mut adjustments = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let ClientEvent::PacingAdjusted {
+            traffic,
+            delay_ms,
+            backoff,
+            ..
+        } = event
+        {
+            adjustments.push((traffic, delay_ms, backoff));
+        }
+    }
+    // The 429 backs off; the successful retry shortens the GET delay again.
+    assert!(adjustments.contains(&(TrafficClass::Get, 20, true)));
+    assert_eq!(adjustments.last(), Some(&(TrafficClass::Get, 15, false)));
+    // Edits and deletes keep their own budgets.
+    assert_eq!(client.pacing_delay_ms(TrafficClass::Delete), Some(0));
+}
+
+#[tokio::test]
+async fn love_and_unlove_tracks() {
+    let fake = FakeLastFm::new("test_user", "hunter2")
+        .with_scrobbles(sample_library())
+        .with_loved([("Portishead", "Roads")]);
+    let client = client_for(&fake);
+
+    assert!(client.love_track("Radiohead", "Creep").await.unwrap());
+    assert_eq!(
+        fake.loved(),
+        vec![
+            ("Radiohead".to_string(), "Creep".to_string()),
+            ("Portishead".to_string(), "Roads".to_string()),
+        ]
+    );
+
+    let loved: Vec<_> = client
+        .loved_tracks()
+        .collect_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| (t.artist, t.name, t.loved))
+        .collect();
+    assert_eq!(
+        loved,
+        vec![
+            ("Radiohead".to_string(), "Creep".to_string(), Some(true)),
+            ("Portishead".to_string(), "Roads".to_string(), Some(true)),
+        ]
+    );
+
+    // The recent scrobbles page shows the heart state of every row.
+    let recent = client.get_recent_tracks_page(1).await.unwrap();
+    let states: Vec<_> = recent
+        .tracks
+        .iter()
+        .map(|t| (t.name.as_str(), t.loved))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("Creep", Some(true)),
+            ("Creep", Some(true)),
+            ("Karma Police", Some(false)),
+            ("Roads", Some(true)),
+        ]
+    );
+
+    assert!(client.unlove_track("Portishead", "Roads").await.unwrap());
+    assert_eq!(
+        fake.loved(),
+        vec![("Radiohead".to_string(), "Creep".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn expired_session_is_refreshed_and_request_retried() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let data_dir = std::env::temp_dir().join(format!(
+        "lastfm-edit-session-refresh-{}",
+        std::process::id()
+    ));
+    let manager = SessionManager::with_data_dir("lastfm-edit-test", &data_dir);
+
+    let stale = client_for(&fake);
+    fake.expire_sessions();
+    let err = stale.get_recent_tracks_page(1).await.unwrap_err();
+    assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+
+    let client = client_for(&fake)
+        .with_credential_provider(Arc::new(StaticCredentials::new("test_user", "hunter2")))
+        .with_session_manager(manager.clone());
+    let clone = client.clone();
+    let mut events = client.subscribe();
+
+    let page = client.get_recent_tracks_page(1).await.unwrap();
+    assert_eq!(page.tracks.len(), 4);
+
+    let refreshes: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+        .filter_map(|event| match event {
+            ClientEvent::SessionRefreshed { username, .. } => Some(username),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(refreshes, vec!["test_user".to_string()]);
+
+    // Clones share the refreshed session, and it was saved for the next run.
+    assert_eq!(clone.get_session().cookies, client.get_session().cookies);
+    assert!(clone
+        .edit_scrobble(
+            &ScrobbleEdit::from_track_and_artist("Roads", "Portishead")
+                .with_track_name("Roads (Live)")
+        )
+        .await
+        .unwrap()
+        .success());
+    assert_eq!(
+        manager.load_session("test_user").unwrap().cookies,
+        client.get_session().cookies
+    );
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn failed_relogin_is_returned_as_the_error() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = client_for(&fake)
+        .with_credential_provider(Arc::new(StaticCredentials::new("test_user", "wrong")));
+
+    fake.expire_sessions();
+    let err = client.get_recent_tracks_page(1).await.unwrap_err();
+    assert!(matches!(err, LastFmError::Auth(_)), "{err}");
+}
+
+#[tokio::test]
+async fn cached_library_pages_are_reused_until_an_edit_invalidates_them() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let cache = Arc::new(InMemoryResponseCache::new());
+    let client = client_for(&fake).with_response_cache(
+        cache.clone(),
+        CachePolicy::new(Duration::from_secs(600)).with_ttl(PageKind::ArtistAlbums, Duration::ZERO),
+    );
+    let mut events = client.subscribe();
+    let fetches = |fake: &FakeLastFm, path: &str| {
+        fake.requests()
+            .iter()
+            .filter(|r| r.method == "GET" && r.path.contains(path))
+            .count()
+    };
+
+    let first = client.get_artist_tracks_page("Radiohead", 1).await.unwrap();
+    let second = client.get_artist_tracks_page("Radiohead", 1).await.unwrap();
+    assert_eq!(first.tracks, second.tracks);
+    assert_eq!(fetches(&fake, "/Radiohead/+tracks"), 1);
+
+    // A zero TTL turns caching off for that page kind.
+    client.get_artist_albums_page("Radiohead", 1).await.unwrap();
+    client.get_artist_albums_page("Radiohead", 1).await.unwrap();
+    assert_eq!(fetches(&fake, "/Radiohead/+albums"), 2);
+
+    client
+        .get_artist_tracks_page("Portishead", 1)
+        .await
+        .unwrap();
+    let edit = ScrobbleEdit::from_track_and_artist("Creep", "Radiohead")
+        .with_track_name("Creep (Acoustic)");
+    assert!(client.edit_scrobble(&edit).await.unwrap().all_successful());
+
+    // The edited artist's pages were dropped; the other artist's page is still served.
+    let after = client.get_artist_tracks_page("Radiohead", 1).await.unwrap();
+    assert!(after.tracks.iter().any(|t| t.name == "Creep (Acoustic)"));
+    assert_eq!(fetches(&fake, "/Radiohead/+tracks"), 2);
+    client
+        .get_artist_tracks_page("Portishead", 1)
+        .await
+        .unwrap();
+    assert_eq!(fetches(&fake, "/Portishead/+tracks"), 1);
+
+    let mut hits = 0;
+    let mut misses = 0;
+    let mut invalidated = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        match event {
+            ClientEvent::CacheHit { .. } => hits += 1,
+            ClientEvent::CacheMiss { .. } => misses += 1,
+            ClientEvent::CacheInvalidated { change, dropped } if dropped > 0 => {
+                invalidated.push(change.artist)
+            }
+            _ => {}
+        }
+    }
+    assert!(hits >= 2, "{hits} hits");
+    assert!(misses >= 3, "{misses} misses");
+    assert!(invalidated.iter().any(|artist| artist == "Radiohead"));
+}
+
+#[tokio::test]
+async fn simulated_edits_are_seen_by_later_reads_but_never_sent() {
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(sample_library());
+    let client = SimulatedEditClient::simulate(client_for(&fake));
+
+    let edit = ScrobbleEdit::from_track_and_artist("Creep", "Radiohead")
+        .with_track_name("Creep (Acoustic)");
+    assert!(client.edit_scrobble(&edit).await.unwrap().all_successful());
+
+    // A second step finds the scrobbles under the name the first step gave them.
+    let edit = ScrobbleEdit::from_track_and_artist("Creep (Acoustic)", "Radiohead")
+        .with_track_name("Creep (Live)");
+    let response = client.edit_scrobble(&edit).await.unwrap();
+    assert_eq!(response.individual_results.len(), 1);
+    assert!(client
+        .delete_scrobble("Portishead", "Roads", 1_700_000_000)
+        .await
+        .unwrap());
+
+    let page = client.get_recent_tracks_page(1).await.unwrap();
+    let names: Vec<_> = page.tracks.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Creep (Live)", "Creep (Live)", "Karma Police"]);
+
+    let tracks = client
+        .artist_tracks("Radiohead")
+        .collect_all()
+        .await
+        .unwrap();
+    assert!(tracks.iter().any(|t| t.name == "Creep (Live)"));
+    assert!(!tracks.iter().any(|t| t.name == "Creep"));
+
+    let artists = client.artists().collect_all().await.unwrap();
+    assert_eq!(artists.len(), 1);
+    assert_eq!(artists[0].name, "Radiohead");
+
+    // Last.fm itself is untouched.
+    assert!(fake.requests().iter().all(|r| r.method == "GET"));
+    assert_eq!(fake.scrobbles().len(), 4);
+    assert!(fake.scrobbles().iter().any(|s| s.track == "Creep"));
+
+    let report = client.report();
+    assert_eq!(report.changes.len(), 3);
+    let text = report.to_string();
+    assert!(
+        text.contains("  - track: Creep\n  + track: Creep (Acoustic)\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("- Portishead - Roads (scrobble at 1700000000)"),
+        "{text}"
+    );
+    assert!(
+        text.ends_with("2 edit(s), 1 deletion(s), 0 love change(s)\n"),
+        "{text}"
+    );
+}
@@ -0,0 +1,165 @@
+//! Fake-scrobble detection over synthetic listening histories.
+
+use lastfm_edit::delete_manifest::read_manifest;
+use lastfm_edit::fake_scrobbles::{
+    detect_fake_scrobbles, scan_recent_tracks, write_blocks_manifest, FakeScrobbleDetectorConfig,
+    SuspicionReason,
+};
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble};
+use lastfm_edit::{ClientConfig, LastFmEditClientImpl, Track};
+
+/// 2023-11-14 12:00:00 UTC
+const NOON: u64 = 1_699_963_200;
+
+fn play(artist: &str, track: &str, album: &str, timestamp: u64) -> Track {
+    Track {
+        name: track.to_string(),
+        artist: artist.to_string(),
+        playcount: 1,
+        timestamp: Some(timestamp),
+        album: Some(album.to_string()),
+        album_artist: None,
+        ..Default::default()
+    }
+}
+
+/// Plays listed oldest first, returned newest first like the recent-tracks iterator.
+fn history(plays: Vec<Track>) -> Vec<Track> {
+    plays.into_iter().rev().collect()
+}
+
+/// A varied afternoon: different albums, four-minute spacing.
+fn normal_listening(start: u64, count: u64) -> Vec<Track> {
+    (0..count)
+        .map(|i| {
+            play(
+                &format!("Artist {}", i % 4),
+                &format!("Song {i}"),
+                &format!("Album {}", i % 4),
+                start + i * 240,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn ordinary_listening_is_not_flagged() {
+    let tracks = history(normal_listening(NOON, 40));
+    assert!(detect_fake_scrobbles(&tracks, &FakeScrobbleDetectorConfig::default()).is_empty());
+}
+
+#[test]
+fn album_loop_is_flagged_with_offsets() {
+    let mut plays = normal_listening(NOON, 5);
+    let loop_start = NOON + 3_600;
+    for i in 0..12 {
+        plays.push(play(
+            "Boards of Canada",
+            &format!("Track {}", i % 4),
+            "Geogaddi",
+            loop_start + i * 200,
+        ));
+    }
+    plays.extend(normal_listening(NOON + 7_200, 3));
+    let tracks = history(plays);
+
+    let blocks = detect_fake_scrobbles(&tracks, &FakeScrobbleDetectorConfig::default());
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].offset_range(), "3-14");
+    assert_eq!(blocks[0].plays.len(), 12);
+    assert_eq!(
+        blocks[0].reasons,
+        [SuspicionReason::AlbumLoop {
+            artist: "Boards of Canada".to_string(),
+            album: "Geogaddi".to_string(),
+            repeats: 3,
+        }]
+    );
+}
+
+#[test]
+fn blocks_are_ranked_and_combine_reasons() {
+    // 1:00-4:00 local time (UTC+2): an unattended queue, partly re-submitted in bursts.
+    let night = NOON + 11 * 3_600;
+    let mut plays: Vec<Track> = (0..30)
+        .map(|i| {
+            play(
+                &format!("Artist {i}"),
+                &format!("Song {i}"),
+                "Radio",
+                night + i * 240,
+            )
+        })
+        .collect();
+    for i in 0..5 {
+        plays.push(play(
+            "Burst",
+            &format!("Burst {i}"),
+            "Single",
+            night + 30 * 240 + i * 5,
+        ));
+    }
+    // Later the same day, a short rapid burst on its own.
+    let afternoon = NOON + 30 * 3_600;
+    plays.extend(normal_listening(afternoon, 3));
+    for i in 0..4 {
+        plays.push(play(
+            "Other",
+            &format!("Skip {i}"),
+            "Skips",
+            afternoon + 3_000 + i * 10,
+        ));
+    }
+    let tracks = history(plays);
+
+    let config = FakeScrobbleDetectorConfig::default().with_utc_offset_secs(2 * 3_600);
+    let blocks = detect_fake_scrobbles(&tracks, &config);
+    assert_eq!(blocks.len(), 2);
+
+    // The overnight streak and its burst merge into the top-ranked block.
+    assert!(blocks[0].score > blocks[1].score);
+    assert_eq!(blocks[0].reasons.len(), 2);
+    assert!(blocks[0]
+        .reasons
+        .iter()
+        .any(|r| matches!(r, SuspicionReason::OvernightStreak { plays: 35 })));
+    assert_eq!(blocks[0].offset_range(), "7-41");
+
+    assert_eq!(blocks[1].offset_range(), "0-3");
+    assert_eq!(
+        blocks[1].reasons,
+        [SuspicionReason::ImplausibleSpacing {
+            shortest_gap_secs: 10
+        }]
+    );
+}
+
+#[tokio::test]
+async fn scanned_blocks_can_be_written_as_a_manifest() {
+    let scrobbles =
+        (0..10).map(|i| FakeScrobble::new("Artist", "Same Song", "Album", NOON + i * 10));
+    let fake = FakeLastFm::new("test_user", "hunter2").with_scrobbles(scrobbles);
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        ClientConfig::for_testing(),
+    );
+
+    let blocks = scan_recent_tracks(&client, 100, &FakeScrobbleDetectorConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].offset_range(), "0-9");
+
+    let path = std::env::temp_dir().join(format!(
+        "lastfm-edit-fake-scrobbles-test-{}.json",
+        std::process::id()
+    ));
+    write_blocks_manifest(&path, &blocks).unwrap();
+    let manifest = read_manifest(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(manifest.source.kind, "fake-scrobble-detector");
+    assert_eq!(manifest.source.range.as_deref(), Some("0-9"));
+    assert_eq!(manifest.targets(), blocks[0].plays);
+}
@@ -1,8 +1,9 @@
 #[cfg(feature = "mock")]
 mod mock_tests {
+    use lastfm_edit::middleware::AuditLayer;
     use lastfm_edit::{
-        Album, AlbumPage, EditResponse, ExactScrobbleEdit, LastFmEditClient, MockLastFmEditClient,
-        Result, ScrobbleEdit, Track, TrackPage,
+        Album, AlbumPage, DryRunLayer, EditResponse, ExactScrobbleEdit, LastFmEditClient, LayerExt,
+        MockLastFmEditClient, Mutation, MutationOutcome, Result, ScrobbleEdit, Track, TrackPage,
     };
     use mockall::predicate::*; // for eq(), any(), etc.
 
@@ -190,6 +191,7 @@ mod mock_tests {
                 album_artist: Some("Test Artist 1".to_string()),
                 playcount: 5,
                 timestamp: Some(1640995200),
+                ..Default::default()
             },
             Track {
                 name: "Test Track 2".to_string(),
@@ -198,6 +200,7 @@ mod mock_tests {
                 album_artist: Some("Test Artist 2".to_string()),
                 playcount: 3,
                 timestamp: Some(1640995100),
+                ..Default::default()
             },
         ];
 
@@ -248,6 +251,7 @@ mod mock_tests {
                         album_artist: Some("Mocked Artist".to_string()),
                         playcount: 10,
                         timestamp: Some(1640995200),
+                        ..Default::default()
                     }],
                     page_number: 1,
                     has_next_page: false,
@@ -286,6 +290,7 @@ mod mock_tests {
                         album_artist: Some("test_artist".to_string()),
                         playcount: 5,
                         timestamp: Some(1640995200),
+                        ..Default::default()
                     }],
                     page_number: 1,
                     has_next_page: false,
@@ -305,6 +310,7 @@ mod mock_tests {
                         album_artist: Some("Recent Artist".to_string()),
                         playcount: 1,
                         timestamp: Some(1640995300),
+                        ..Default::default()
                     }],
                     page_number: page,
                     has_next_page: false,
@@ -350,6 +356,82 @@ mod mock_tests {
 
         Ok(())
     }
+
+    #[test_log::test(tokio::test)]
+    async fn test_dry_run_layer_blocks_mutations_of_mock() -> Result<()> {
+        let mut mock_client = MockLastFmEditClient::new();
+        mock_client.expect_delete_scrobble().never();
+        mock_client.expect_love_track().never();
+
+        let dry_run = DryRunLayer::new();
+        let client = mock_client.with_layer(dry_run.clone());
+
+        assert!(
+            client
+                .delete_scrobble("Artist", "Track", 1640995200)
+                .await?
+        );
+        assert!(client.love_track("Artist", "Track").await?);
+        assert_eq!(
+            dry_run.recorded(),
+            vec![
+                Mutation::Delete {
+                    artist: "Artist".to_string(),
+                    track: "Track".to_string(),
+                    timestamp: 1640995200,
+                },
+                Mutation::Love {
+                    artist: "Artist".to_string(),
+                    track: "Track".to_string(),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_audit_layer_logs_mutations_of_mock() -> Result<()> {
+        let mut mock_client = MockLastFmEditClient::new();
+        mock_client
+            .expect_delete_scrobble()
+            .with(eq("Artist"), eq("Track"), eq(1640995200))
+            .times(1)
+            .returning(|_, _, _| Ok(true));
+        mock_client
+            .expect_unlove_track()
+            .times(1)
+            .returning(|_, _| Err(lastfm_edit::LastFmError::EditFailed("nope".to_string())));
+        mock_client
+            .expect_username()
+            .returning(|| "testuser".to_string());
+
+        let path =
+            std::env::temp_dir().join(format!("lastfm-edit-audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = mock_client.with_layer(AuditLayer::open(&path)?);
+
+        assert!(
+            client
+                .delete_scrobble("Artist", "Track", 1640995200)
+                .await?
+        );
+        assert!(client.unlove_track("Artist", "Track").await.is_err());
+
+        let entries = AuditLayer::read_entries(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].username, "testuser");
+        assert_eq!(
+            entries[0].outcome,
+            Some(MutationOutcome::Accepted { accepted: true })
+        );
+        assert!(matches!(entries[1].mutation, Mutation::Unlove { .. }));
+        assert!(entries[1].outcome.is_none());
+        assert!(entries[1].error.as_deref().unwrap().contains("nope"));
+
+        Ok(())
+    }
 }
 
 #[cfg(not(feature = "mock"))]
@@ -0,0 +1,128 @@
+//! Parser health checks against the recorded page corpus (the VCR cassette bodies) and
+//! against deliberately drifted markup.
+
+use lastfm_edit::parsing::LastFmParser;
+use lastfm_edit::{
+    ClientConfig, ClientEvent, Html, LastFmEditClientImpl, LastFmEditSession, PageKind,
+    ParseAnomaly,
+};
+
+/// One recorded page per kind: (cassette body, page kind, page number).
+const CORPUS: &[(&str, PageKind, u32)] = &[
+    (
+        "recent_tracks_dir/bodies/resp_001.txt",
+        PageKind::RecentScrobbles,
+        1,
+    ),
+    ("list_artists_dir/bodies/resp_001.txt", PageKind::Artists, 1),
+    (
+        "artist_tracks_direct_dir/bodies/resp_001.txt",
+        PageKind::ArtistTracks,
+        1,
+    ),
+    (
+        "artist_albums_beatles_dir/bodies/resp_001.txt",
+        PageKind::ArtistAlbums,
+        1,
+    ),
+    ("search_dir/bodies/resp_001.txt", PageKind::TrackSearch, 1),
+    (
+        "album_search_dir/bodies/resp_001.txt",
+        PageKind::AlbumSearch,
+        1,
+    ),
+];
+
+fn corpus_page(body: &str) -> String {
+    std::fs::read_to_string(format!("tests/vcr/fixtures/{body}"))
+        .unwrap_or_else(|e| panic!("missing corpus page {body}: {e}"))
+}
+
+/// Serves the same page for every request.
+#[derive(Debug)]
+struct StaticPage(String);
+
+#[async_trait::async_trait]
+impl http_client::HttpClient for StaticPage {
+    async fn send(
+        &self,
+        _req: http_client::Request,
+    ) -> std::result::Result<http_client::Response, http_types::Error> {
+        let mut response = http_types::Response::new(200);
+        response.set_body(self.0.clone());
+        Ok(response)
+    }
+}
+
+#[test]
+fn recorded_pages_match_every_selector() {
+    let parser = LastFmParser::new();
+    for (body, kind, page) in CORPUS {
+        let document = Html::parse_document(&corpus_page(body));
+        let report = parser.check_page(*kind, &document, *page);
+
+        assert!(report.rows > 0, "{kind}: no rows found");
+        assert_eq!(report.parsed, report.rows, "{kind}: rows skipped");
+        assert!(report.is_healthy(), "{kind}: {:?}", report.anomalies);
+        assert_eq!(
+            report.missing_selectors().collect::<Vec<_>>(),
+            Vec::<&str>::new(),
+            "{kind}"
+        );
+    }
+}
+
+#[test]
+fn renamed_column_is_reported_as_drift() {
+    // Simulate Last.fm renaming the artist column of the scrobble list.
+    let html = corpus_page(CORPUS[0].0).replace("chartlist-artist", "chartlist-performer");
+    let report =
+        LastFmParser::new().check_page(PageKind::RecentScrobbles, &Html::parse_document(&html), 1);
+
+    assert_eq!(report.parsed, 0);
+    assert_eq!(
+        report.missing_selectors().collect::<Vec<_>>(),
+        vec![".chartlist-artist a"]
+    );
+    assert!(report.anomalies.contains(&ParseAnomaly::RowsMissingFields {
+        failed: report.rows,
+        rows: report.rows,
+    }));
+    assert!(report
+        .anomalies
+        .iter()
+        .any(|a| matches!(a, ParseAnomaly::EmptyPageWithPagination { .. })));
+}
+
+#[tokio::test]
+async fn client_broadcasts_parse_drift_and_keeps_the_report() {
+    let html = corpus_page(CORPUS[0].0).replace("chartlist-name", "chartlist-title");
+    let client = LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(StaticPage(html)),
+        LastFmEditSession::new(
+            "test_user".to_string(),
+            vec!["sessionid=.test_session_id_12345".to_string()],
+            Some("test_csrf_token".to_string()),
+            "https://www.last.fm".to_string(),
+        ),
+        ClientConfig::for_testing(),
+    );
+    let mut events = client.subscribe();
+
+    let page = client.get_recent_tracks_page(1).await.unwrap();
+    assert!(page.tracks.is_empty());
+
+    let mut drift = None;
+    while let Ok(event) = events.try_recv() {
+        if let ClientEvent::ParseDrift { report } = event {
+            drift = Some(report);
+        }
+    }
+    let drift = drift.expect("a ParseDrift event");
+    assert_eq!(drift.page, PageKind::RecentScrobbles);
+    assert!(!drift.is_healthy());
+    assert_eq!(
+        client.last_parse_report(PageKind::RecentScrobbles),
+        Some(drift)
+    );
+}
@@ -0,0 +1,123 @@
+//! Tests for the cross-process rate-limit budget, using separate limiter handles on one
+//! state file in place of separate processes.
+
+use lastfm_edit::testing::{FakeLastFm, FakeScrobble, RateLimitResponse};
+use lastfm_edit::{
+    ClientConfig, LastFmEditClientImpl, LastFmError, RateLimitBehavior, RateLimitType,
+    SharedRateLimiter,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn state_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "lastfm-edit-shared-rate-limit-{name}-{}.json",
+        std::process::id()
+    ));
+    std::fs::remove_file(&path).ok();
+    path
+}
+
+fn client_for(fake: &FakeLastFm, config: ClientConfig) -> LastFmEditClientImpl {
+    LastFmEditClientImpl::from_session_with_client_config(
+        Box::new(fake.clone()),
+        fake.session(),
+        config,
+    )
+}
+
+fn fake_site() -> FakeLastFm {
+    FakeLastFm::new("test_user", "hunter2").with_scrobbles(vec![FakeScrobble::new(
+        "Portishead",
+        "Roads",
+        "Dummy",
+        1_700_000_000,
+    )])
+}
+
+#[test]
+fn limiters_on_one_file_space_their_requests() {
+    let path = state_path("spacing");
+    let first = SharedRateLimiter::new(&path).with_min_interval_ms(60_000);
+    let second = SharedRateLimiter::new(&path).with_min_interval_ms(60_000);
+
+    assert_eq!(first.reserve().unwrap(), Duration::ZERO);
+    // The second handle sees the slot the first one claimed.
+    let wait = second.reserve().unwrap();
+    assert!(wait > Duration::from_secs(59), "waited only {wait:?}");
+    assert!(wait <= Duration::from_secs(60));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn recorded_rate_limit_starts_a_cooldown_for_new_handles() {
+    let path = state_path("cooldown");
+    SharedRateLimiter::new(&path)
+        .record_rate_limit(120, RateLimitType::Http429)
+        .unwrap();
+
+    let fresh = SharedRateLimiter::new(&path);
+    let remaining = fresh.cooldown_remaining().unwrap().expect("cool-down");
+    assert!(remaining > Duration::from_secs(110));
+    assert!(fresh.reserve().unwrap() > Duration::from_secs(110));
+
+    let record = fresh.state().unwrap().last_rate_limit.expect("record");
+    assert_eq!(record.delay_seconds, 120);
+    assert_eq!(record.rate_limit_type, RateLimitType::Http429);
+    assert_eq!(record.pid, std::process::id());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn corrupt_state_is_reset_by_the_next_update() {
+    let path = state_path("corrupt");
+    std::fs::write(&path, r#"{"next_slot_unix_ms":17000"#).unwrap();
+    let limiter = SharedRateLimiter::new(&path).with_min_interval_ms(60_000);
+    assert!(matches!(limiter.state(), Err(LastFmError::Parse(_))));
+
+    assert_eq!(limiter.reserve().unwrap(), Duration::ZERO);
+    assert!(limiter.state().unwrap().next_slot_unix_ms > 0);
+    limiter
+        .record_rate_limit(120, RateLimitType::Http429)
+        .unwrap();
+    assert!(limiter.cooldown_remaining().unwrap().is_some());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn client_shares_detected_rate_limits_and_honours_them() {
+    let path = state_path("client");
+    let fake = fake_site();
+    let limiter = Arc::new(SharedRateLimiter::new(&path).with_min_interval_ms(0));
+
+    let non_blocking =
+        || ClientConfig::for_testing().with_rate_limit_behavior(RateLimitBehavior::ReturnError);
+
+    let first = client_for(&fake, non_blocking()).with_shared_rate_limiter(limiter.clone());
+    fake.rate_limit_next(
+        1,
+        RateLimitResponse::TooManyRequests {
+            retry_after: Some(300),
+        },
+    );
+    assert!(matches!(
+        first.get_recent_tracks_page(1).await,
+        Err(LastFmError::RateLimit { .. })
+    ));
+    let record = limiter.state().unwrap().last_rate_limit.expect("record");
+    assert_eq!(record.rate_limit_type, RateLimitType::Http429);
+
+    // A client started afterwards refuses to send during the cool-down.
+    let second = client_for(&fake, non_blocking())
+        .with_shared_rate_limiter(Arc::new(SharedRateLimiter::new(&path)));
+    match second.get_recent_tracks_page(1).await {
+        Err(LastFmError::RateLimit { retry_after }) => assert!(retry_after > 200),
+        other => panic!("expected a shared cool-down, got {other:?}"),
+    }
+
+    std::fs::remove_file(&path).ok();
+}
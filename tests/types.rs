@@ -27,6 +27,7 @@ fn test_display_implementations() {
         timestamp: Some(1234567890),
         album: Some("Abbey Road".to_string()),
         album_artist: None,
+        ..Default::default()
     };
     assert_eq!(
         format!("{track_with_album}"),
@@ -41,6 +42,7 @@ fn test_display_implementations() {
         timestamp: Some(1234567890),
         album: None,
         album_artist: None,
+        ..Default::default()
     };
     assert_eq!(format!("{track_without_album}"), "The Beatles - Yesterday");
 
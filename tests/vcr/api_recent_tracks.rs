@@ -16,6 +16,17 @@ async fn test_api_recent_tracks_page() {
     assert!(!page.tracks.is_empty(), "Should have tracks on page 1");
     assert_eq!(page.page_number, 1);
 
+    // MBIDs and cover art come straight from the recorded response.
+    let first = &page.tracks[0];
+    assert_eq!(
+        first.artist_mbid.as_deref(),
+        Some("d11c9778-a998-4349-8633-101502e82382")
+    );
+    assert_eq!(
+        first.image_url.as_deref(),
+        Some("https://lastfm.freetls.fastly.net/i/u/300x300/cf8dd70163734b7e84331653eabbfc7b.jpg")
+    );
+
     for track in &page.tracks {
         assert!(!track.name.is_empty(), "Track name should not be empty");
         assert!(!track.artist.is_empty(), "Track artist should not be empty");
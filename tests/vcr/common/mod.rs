@@ -77,6 +77,14 @@ impl VcrTestSetup {
     }
 
     async fn create_vcr_client(&self) -> Result<VcrClient, Box<dyn std::error::Error>> {
+        self.create_vcr_client_with_matcher(LastFmEditVcrMatcher::new)
+            .await
+    }
+
+    async fn create_vcr_client_with_matcher(
+        &self,
+        matcher: fn() -> LastFmEditVcrMatcher,
+    ) -> Result<VcrClient, Box<dyn std::error::Error>> {
         // Handle Filter mode by applying filters and saving the cassette
         if matches!(self.mode, VcrMode::Filter) {
             log::debug!("Filter mode: applying filters to existing cassette");
@@ -90,7 +98,7 @@ impl VcrTestSetup {
                 .inner_client(inner_client)
                 .mode(VcrMode::Replay)
                 .format(http_client_vcr::CassetteFormat::Directory)
-                .matcher(Box::new(LastFmEditVcrMatcher::new()))
+                .matcher(Box::new(matcher()))
                 .build()
                 .await?;
             return Ok(vcr_client);
@@ -105,7 +113,7 @@ impl VcrTestSetup {
             .inner_client(inner_client)
             .mode(self.mode.clone())
             .format(http_client_vcr::CassetteFormat::Directory)
-            .matcher(Box::new(LastFmEditVcrMatcher::new()));
+            .matcher(Box::new(matcher()));
 
         // Add filter chain for Record mode only (Filter mode is handled above)
         if matches!(self.mode, VcrMode::Record) {
@@ -259,7 +267,13 @@ pub async fn create_lastfm_api_vcr_test_client(
             .build()
             .await?
     } else {
-        setup.create_vcr_client().await?
+        // Cassettes recorded before recent-tracks requests asked for `extended=1` still
+        // replay; their responses just lack the loved flag.
+        setup
+            .create_vcr_client_with_matcher(|| {
+                LastFmEditVcrMatcher::new().with_ignored_query_params(["extended"])
+            })
+            .await?
     };
 
     Ok(lastfm_edit::LastFmApiClientImpl::new(